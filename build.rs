@@ -8,10 +8,36 @@ fn main() {
     println!("cargo:rerun-if-changed=frontend/svelte.config.js");
     println!("cargo:rerun-if-changed=frontend/vite.config.ts");
     
+    emit_build_metadata();
+
     // Always build frontend
     build_frontend();
 }
 
+/// Expose the build time and git commit to the crate as `BUILD_TIMESTAMP` and `GIT_COMMIT`
+fn emit_build_metadata() {
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-env-changed=GIT_COMMIT");
+
+    println!(
+        "cargo:rustc-env=BUILD_TIMESTAMP={}",
+        chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+    );
+
+    // Builds outside a checkout (e.g. from a source tarball) can pass GIT_COMMIT in
+    let commit = std::env::var("GIT_COMMIT").ok().or_else(|| {
+        Command::new("git")
+            .args(["rev-parse", "HEAD"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| String::from_utf8(output.stdout).ok())
+    });
+    if let Some(commit) = commit.map(|commit| commit.trim().to_string()).filter(|commit| !commit.is_empty()) {
+        println!("cargo:rustc-env=GIT_COMMIT={}", commit);
+    }
+}
+
 fn build_frontend() {
     let frontend_dir = Path::new("frontend");
     
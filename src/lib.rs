@@ -10,6 +10,34 @@ use chrono::Utc;
 pub use services::{SseService, StaticService, MetricsService, MetricsCache};
 pub use models::{ServerInfo, OsInfo};
 pub use routes::server_status;
+pub use routes::readiness::Readiness;
+
+/// Which optional middleware layers `build_router` applies
+///
+/// Integrators embedding the router in a larger app that already provides
+/// some of these layers can switch them off to avoid applying them twice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RouterConfig {
+    /// Apply the cross-origin policy
+    pub enable_cors: bool,
+    /// Add CSP and the other security headers
+    pub enable_security_headers: bool,
+    /// Wrap requests in a `tower_http` trace span
+    pub enable_tracing: bool,
+    /// Reject request floods per client
+    pub enable_rate_limit: bool,
+}
+
+impl Default for RouterConfig {
+    fn default() -> Self {
+        Self {
+            enable_cors: true,
+            enable_security_headers: true,
+            enable_tracing: true,
+            enable_rate_limit: true,
+        }
+    }
+}
 
 /// Create application router for testing
 pub async fn create_app() -> Router {
@@ -40,6 +68,7 @@ pub async fn create_app() -> Router {
         metrics_cache,
         metrics_service,
         server_info,
+        RouterConfig::default(),
     )
 }
 
@@ -50,33 +79,112 @@ pub fn build_router(
     metrics_cache: Arc<MetricsCache>,
     metrics_service: Arc<MetricsService>,
     server_info: ServerInfo,
+    router_config: RouterConfig,
+) -> Router {
+    build_router_with_shutdown(
+        sse_service,
+        static_service,
+        metrics_cache,
+        metrics_service,
+        server_info,
+        tokio::sync::broadcast::channel(1).0,
+        Readiness::ready(),
+        middleware::cors_layer(&middleware::CorsConfig::default()).expect("default CORS configuration is valid"),
+        router_config,
+    )
+}
+
+/// Build the application router; a send on `shutdown` ends open metrics streams
+/// with a `server_shutdown` event, `readiness` backs `/api/readiness`, `cors`
+/// is the cross-origin policy built from a validated `CorsConfig`, and
+/// `router_config` selects the optional middleware layers
+#[allow(clippy::too_many_arguments)]
+pub fn build_router_with_shutdown(
+    sse_service: Arc<SseService>,
+    static_service: Arc<StaticService>,
+    metrics_cache: Arc<MetricsCache>,
+    metrics_service: Arc<MetricsService>,
+    server_info: ServerInfo,
+    shutdown: tokio::sync::broadcast::Sender<()>,
+    readiness: Readiness,
+    cors: tower_http::cors::CorsLayer,
+    router_config: RouterConfig,
 ) -> Router {
     use axum::routing::{get, post};
-    use routes::{pages, api, server_status_stream};
-    use tower::ServiceBuilder;
+    use routes::{pages, api, cache_admin, maintenance, metrics_config, openapi, features, internal_stats_stream, prometheus, readiness, request_metrics, server_status_stream, server_status_ws};
     use tower_http::trace::TraceLayer;
     use middleware::{
-        cors_layer, security_headers, cache_control,
-        request_logging, error_handling, request_id_middleware
+        security_headers, cache_control, require_api_key, require_admin_key, ApiKeyConfig, SecurityHeadersConfig,
+        request_logging, error_handling, request_id_middleware, RequestIdConfig, RequestMetrics,
+        feature_toggle, FeatureToggles, rate_limit_layer, RateLimitConfig, compression_layer,
+        maintenance_mode, MaintenanceMode,
     };
     
+    // Runtime route toggles, seeded from configuration
+    let feature_toggles = FeatureToggles::from_env();
+
+    // API key for /api routes; admin endpoints require it even when the API is open
+    let api_key = ApiKeyConfig::from_env();
+
+    // Runtime maintenance switch, seeded from configuration
+    let maintenance_mode_state = MaintenanceMode::from_env();
+
+    // Per-endpoint request latency, shared by the middleware and its endpoint
+    let request_metrics = RequestMetrics::new();
+    
     // Create server status state
     let server_status_state = server_status::ServerStatusState::new(
         Arc::clone(&metrics_cache),
         Arc::clone(&metrics_service),
         server_info,
-    );
+    )
+    .with_sse_config(server_status_stream::SseStreamConfig::from_env())
+    .with_status_config(server_status::StatusEndpointConfig::from_env())
+    .with_health_thresholds(models::HealthThresholds::from_env())
+    .with_shutdown(shutdown);
     
+    // Liveness and readiness probes; orchestrators call them without credentials
+    let probe_routes = Router::new()
+        .route("/health", get(api::health_check))
+        .merge(readiness::create_router().with_state(readiness));
+
     // API routes
     let api_routes = Router::new()
-        .route("/time-stream", get(api::time_stream))
-        .route("/health", get(api::health_check))
+        .route("/time-stream", get(api::time_stream).head(server_status_stream::stream_head_not_allowed))
         .route("/status", get(api::service_status))
-        .route("/broadcast", post(api::manual_time_broadcast))
+        .route(
+            "/broadcast",
+            post(api::manual_time_broadcast)
+                .route_layer(axum::middleware::from_fn_with_state(api_key.clone(), require_admin_key)),
+        )
+        // Merge feature toggle routes
+        .merge(features::create_router(api_key.clone()).with_state(feature_toggles.clone()))
+        // Merge maintenance mode switch
+        .merge(maintenance::create_router(api_key.clone()).with_state(maintenance_mode_state.clone()))
+        // Merge request latency endpoint
+        .merge(request_metrics::create_router().with_state(request_metrics.clone()))
         // Merge server status routes
         .merge(server_status::create_router().with_state(server_status_state.clone()))
         // Merge SSE routes
-        .merge(server_status_stream::create_sse_router().with_state(server_status_state));
+        .merge(server_status_stream::create_sse_router().with_state(server_status_state.clone()))
+        // Merge WebSocket transport for proxies that buffer SSE
+        .merge(server_status_ws::create_router().with_state(server_status_state.clone()))
+        // Merge self-monitoring stream
+        .merge(internal_stats_stream::create_router().with_state(server_status_state.clone()))
+        // Merge runtime configuration endpoint
+        .merge(metrics_config::create_router(api_key.clone()).with_state(server_status_state.clone()))
+        // Merge cache admin endpoints
+        .merge(cache_admin::create_router(api_key.clone()).with_state(server_status_state.clone()))
+        // Merge OpenAPI document
+        .merge(openapi::create_router())
+        // Merge Prometheus scrape endpoint
+        .merge(prometheus::create_router().with_state(server_status_state))
+        // Unknown API paths answer JSON 404 instead of reaching the SPA fallback
+        .fallback(api::api_not_found)
+        // Opt-in API key check; page routes stay public
+        .layer(axum::middleware::from_fn_with_state(api_key, require_api_key))
+        // Probes are merged after the key check so it doesn't apply to them
+        .merge(probe_routes);
     
     // Page routes for SPA  
     let page_routes = Router::new()
@@ -89,27 +197,43 @@ pub fn build_router(
         .fallback(get(pages::serve_spa_fallback));
     
     // Build main application
-    Router::new()
+    let mut app = Router::new()
         // Mount API routes under /api prefix
         .nest("/api", api_routes)
         // Mount page routes at root
         .merge(page_routes)
         // Add service extensions
         .layer(axum::Extension(sse_service))
-        .layer(axum::Extension(static_service))
-        // Add middleware stack (order matters - first added runs last)
-        .layer(
-            ServiceBuilder::new()
-                // Request ID and logging first
-                .layer(axum::middleware::from_fn(request_id_middleware))
-                .layer(axum::middleware::from_fn(request_logging))
-                // Error handling
-                .layer(axum::middleware::from_fn(error_handling))
-                // Security layers
-                .layer(cors_layer())
-                .layer(axum::middleware::from_fn(security_headers))
-                .layer(axum::middleware::from_fn(cache_control))
-                // Tracing for detailed request/response logging
-                .layer(TraceLayer::new_for_http())
-        )
-}
\ No newline at end of file
+        .layer(axum::Extension(static_service));
+
+    // Add middleware stack (order matters - each layer wraps those added before it,
+    // so the last added runs first)
+    if router_config.enable_tracing {
+        // Tracing for detailed request/response logging
+        app = app.layer(TraceLayer::new_for_http());
+    }
+    app = app
+        // Reject routes switched off at runtime
+        .layer(axum::middleware::from_fn_with_state(feature_toggles, feature_toggle))
+        // Answer 503 everywhere but the probes while in maintenance
+        .layer(axum::middleware::from_fn_with_state(maintenance_mode_state, maintenance_mode))
+        .layer(axum::middleware::from_fn(cache_control));
+    if router_config.enable_security_headers {
+        app = app.layer(axum::middleware::from_fn_with_state(SecurityHeadersConfig::from_env(), security_headers));
+    }
+    // Compress JSON and pages; event streams pass through uncompressed
+    app = app.layer(compression_layer());
+    if router_config.enable_cors {
+        app = app.layer(cors);
+    }
+    // Error handling
+    app = app.layer(axum::middleware::from_fn(error_handling));
+    if router_config.enable_rate_limit {
+        // Reject request floods before doing any work
+        app = app.layer(rate_limit_layer(RateLimitConfig::from_env()));
+    }
+    // Request ID and logging first
+    app.layer(axum::middleware::from_fn_with_state(request_metrics, middleware::request_metrics))
+        .layer(axum::middleware::from_fn(request_logging))
+        .layer(axum::middleware::from_fn_with_state(RequestIdConfig::from_env(), request_id_middleware))
+}
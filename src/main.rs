@@ -1,4 +1,4 @@
-use std::{sync::Arc, net::SocketAddr};
+use std::{sync::Arc, net::SocketAddr, time::Duration};
 use tokio::signal;
 use tracing::{info, warn};
 use tracing_subscriber::{
@@ -8,29 +8,60 @@ use tracing_subscriber::{
     EnvFilter,
 };
 
-use axum_sse::{build_router, SseService, StaticService, MetricsService, MetricsCache, ServerInfo, OsInfo};
+use axum_sse::{build_router_with_shutdown, Readiness, RouterConfig, SseService, StaticService, MetricsService, MetricsCache, ServerInfo, OsInfo};
+use axum_sse::middleware::{cors_layer, CorsConfig};
+use axum_sse::models::MetricsResponse;
+use axum_sse::services::metrics_cache::MetricsCacheConfig;
+use axum_sse::services::jitter::MAX_JITTER_PERCENT;
+use axum_sse::services::sse_service::DEFAULT_BROADCAST_INTERVAL;
+use axum_sse::services::tasks::runtime_thread_name;
 
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Build the runtime by hand so worker and blocking threads get a recognisable name
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .thread_name(runtime_thread_name())
+        .build()?
+        .block_on(run())
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize tracing
     init_logging();
     
     info!("🚀 Starting axum-sse server...");
     
+    // Refuse to start with a CORS policy browsers would reject
+    let cors = cors_layer(&CorsConfig::from_env())?;
+    
     // Initialize services
-    let sse_service = Arc::new(SseService::new());
-    let static_service = Arc::new(StaticService::new());
+    let sse_service = Arc::new(
+        SseService::with_broadcast_interval(get_time_broadcast_interval())
+            .with_broadcast_jitter(get_time_broadcast_jitter_percent()),
+    );
+    let static_service = Arc::new(StaticService::from_env());
+    // An empty frontend build only warns unless STRICT_ASSETS asks for a hard failure
+    let asset_count = static_service.check_assets(strict_assets())?;
+    info!("📦 {} frontend assets embedded", asset_count);
     
     // Initialize metrics services
     let metrics_service = Arc::new(MetricsService::new());
-    let metrics_cache = Arc::new(MetricsCache::new(Arc::clone(&metrics_service)));
+    let metrics_cache = Arc::new(MetricsCache::with_config(
+        MetricsCacheConfig::from_env(),
+        Arc::clone(&metrics_service),
+    ));
     
     // Initialize metrics service
-    if let Err(e) = metrics_service.initialize().await {
-        warn!("Failed to initialize metrics service: {}", e);
-    } else {
-        info!("📊 Metrics service initialized");
-    }
+    let initialized = match metrics_service.initialize().await {
+        Ok(()) => {
+            info!("📊 Metrics service initialized");
+            true
+        }
+        Err(e) => {
+            warn!("Failed to initialize metrics service: {}", e);
+            false
+        }
+    };
     
     // Start metrics cache background refresh
     if let Err(e) = metrics_cache.start_background_refresh().await {
@@ -45,37 +76,48 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         OsInfo::fallback()
     });
     
-    // Create server info
-    let server_info = ServerInfo::new(
+    // Create server info, falling back per field rather than failing
+    let (server_info, fallbacks) = ServerInfo::with_fallbacks(
         hostname::get()
             .map(|h| h.to_string_lossy().to_string())
             .unwrap_or_else(|_| "unknown".to_string()),
         env!("CARGO_PKG_VERSION").to_string(),
         chrono::Utc::now(),
         std::env::var("ENVIRONMENT").unwrap_or_else(|_| "development".to_string()),
-        os_info.clone(),
-    ).unwrap_or_else(|e| {
-        warn!("Failed to create server info: {}, using defaults", e);
-        ServerInfo::new(
-            "unknown".to_string(),
-            env!("CARGO_PKG_VERSION").to_string(),
-            chrono::Utc::now(),
-            "development".to_string(),
-            os_info,
-        ).unwrap()
-    });
+        os_info,
+    );
+    if !fallbacks.is_empty() {
+        warn!("Server info used fallback values for: {}", fallbacks.join(", "));
+    }
     
     // Start the SSE time broadcaster
     SseService::start_time_broadcaster(&sse_service);
     info!("📡 SSE time broadcaster started");
     
+    // Readiness flips once the metrics service is up, the cache holds a collection
+    // and the startup grace period has passed
+    let readiness = Readiness::new().with_grace_period(get_readiness_grace_period());
+    tokio::spawn(warm_up(
+        Arc::clone(&metrics_service),
+        Arc::clone(&metrics_cache),
+        initialized,
+        readiness.clone(),
+    ));
+
+    // Tells open SSE streams to close once shutdown begins
+    let (shutdown_tx, _) = tokio::sync::broadcast::channel(1);
+
     // Build the application router
-    let app = build_router(
+    let app = build_router_with_shutdown(
         sse_service, 
         static_service, 
         metrics_cache, 
         metrics_service, 
-        server_info
+        server_info,
+        shutdown_tx.clone(),
+        readiness,
+        cors,
+        RouterConfig::default(),
     );
     
     // Configure server address
@@ -87,14 +129,41 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     info!("✅ Server listening on http://{}", addr);
     
     // Start server with graceful shutdown
-    axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal())
+    // Peer addresses feed the per-client rate limiter
+    axum::serve(listener, app.into_make_service_with_connect_info::<std::net::SocketAddr>())
+        .with_graceful_shutdown(async move {
+            shutdown_signal().await;
+            // Streams never finish on their own, so graceful shutdown would wait on them forever
+            let open_streams = shutdown_tx.send(()).unwrap_or(0);
+            info!("📴 Notified {} open SSE streams of shutdown", open_streams);
+        })
         .await?;
     
     info!("👋 Server shutdown complete");
     Ok(())
 }
 
+/// Retry initialization and the first collection until both succeed, then mark the server ready
+async fn warm_up(
+    metrics_service: Arc<MetricsService>,
+    metrics_cache: Arc<MetricsCache>,
+    mut initialized: bool,
+    readiness: Readiness,
+) {
+    const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+    loop {
+        if !initialized {
+            initialized = metrics_service.initialize().await.is_ok();
+        }
+        if initialized && !matches!(metrics_cache.get_metrics(None).await, MetricsResponse::Error(_)) {
+            readiness.mark_ready();
+            return;
+        }
+        tokio::time::sleep(RETRY_DELAY).await;
+    }
+}
+
 fn init_logging() {
     // Configure logging based on environment
     let env_filter = EnvFilter::try_from_default_env()
@@ -103,16 +172,34 @@ fn init_logging() {
             "axum_sse=debug,tower_http=debug,info".into()
         });
     
-    tracing_subscriber::registry()
-        .with(env_filter)
-        .with(
-            fmt::layer()
-                .with_target(true)
-                .with_thread_ids(true)
-                .with_line_number(true)
-                .compact()
-        )
-        .init();
+    // LOG_FORMAT=json emits one JSON object per line for log pipelines
+    let json = std::env::var("LOG_FORMAT")
+        .map(|format| format.trim().eq_ignore_ascii_case("json"))
+        .unwrap_or(false);
+
+    let registry = tracing_subscriber::registry().with(env_filter);
+    if json {
+        registry
+            .with(
+                fmt::layer()
+                    .json()
+                    .flatten_event(true)
+                    .with_target(true)
+                    .with_thread_ids(true)
+                    .with_line_number(true)
+            )
+            .init();
+    } else {
+        registry
+            .with(
+                fmt::layer()
+                    .with_target(true)
+                    .with_thread_ids(true)
+                    .with_line_number(true)
+                    .compact()
+            )
+            .init();
+    }
     
     info!("📋 Logging initialized");
 }
@@ -129,6 +216,49 @@ fn get_server_address() -> SocketAddr {
         .unwrap_or_else(|_| "127.0.0.1:3000".parse().unwrap())
 }
 
+/// Whether `STRICT_ASSETS` asks startup to fail without embedded frontend assets
+fn strict_assets() -> bool {
+    std::env::var("STRICT_ASSETS")
+        .map(|value| matches!(value.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false)
+}
+
+/// Time broadcast cadence from `TIME_BROADCAST_INTERVAL_MS`, defaulting to 10s
+fn get_time_broadcast_interval() -> Duration {
+    match std::env::var("TIME_BROADCAST_INTERVAL_MS").map(|ms| ms.trim().parse::<u64>()) {
+        Ok(Ok(ms)) => Duration::from_millis(ms),
+        Ok(Err(e)) => {
+            warn!("Ignoring invalid TIME_BROADCAST_INTERVAL_MS: {}", e);
+            DEFAULT_BROADCAST_INTERVAL
+        }
+        Err(_) => DEFAULT_BROADCAST_INTERVAL,
+    }
+}
+
+/// Random ± percentage for the time broadcast cadence from `TIME_BROADCAST_JITTER_PERCENT`, defaulting to 0
+fn get_time_broadcast_jitter_percent() -> f64 {
+    match std::env::var("TIME_BROADCAST_JITTER_PERCENT").map(|percent| percent.trim().parse::<f64>()) {
+        Ok(Ok(percent)) if (0.0..=MAX_JITTER_PERCENT).contains(&percent) => percent,
+        Ok(_) => {
+            warn!("Ignoring TIME_BROADCAST_JITTER_PERCENT: expected 0-{}", MAX_JITTER_PERCENT);
+            0.0
+        }
+        Err(_) => 0.0,
+    }
+}
+
+/// Startup grace period before the readiness probe can pass, from `READINESS_GRACE_SECONDS`, defaulting to none
+fn get_readiness_grace_period() -> Duration {
+    match std::env::var("READINESS_GRACE_SECONDS").map(|seconds| seconds.trim().parse::<u64>()) {
+        Ok(Ok(seconds)) => Duration::from_secs(seconds),
+        Ok(Err(_)) => {
+            warn!("Ignoring READINESS_GRACE_SECONDS: expected a whole number of seconds");
+            Duration::ZERO
+        }
+        Err(_) => Duration::ZERO,
+    }
+}
+
 async fn shutdown_signal() {
     let ctrl_c = async {
         signal::ctrl_c()
@@ -160,6 +290,7 @@ async fn shutdown_signal() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use axum_sse::build_router;
     use axum::http::{Request, StatusCode};
     use axum::body::Body;
     use tower::ServiceExt;
@@ -184,7 +315,7 @@ mod tests {
     async fn test_router_creation() {
         let (sse_service, static_service, metrics_cache, metrics_service, server_info) = create_test_services();
         
-        let app = build_router(sse_service, static_service, metrics_cache, metrics_service, server_info);
+        let app = build_router(sse_service, static_service, metrics_cache, metrics_service, server_info, RouterConfig::default());
         
         // Test that the router can handle requests
         let request = Request::builder()
@@ -200,7 +331,7 @@ mod tests {
     async fn test_api_routes() {
         let (sse_service, static_service, metrics_cache, metrics_service, server_info) = create_test_services();
         
-        let app = build_router(sse_service, static_service, metrics_cache, metrics_service, server_info);
+        let app = build_router(sse_service, static_service, metrics_cache, metrics_service, server_info, RouterConfig::default());
         
         // Test health endpoint
         let request = Request::builder()
@@ -216,7 +347,7 @@ mod tests {
     async fn test_page_routes() {
         let (sse_service, static_service, metrics_cache, metrics_service, server_info) = create_test_services();
         
-        let app = build_router(sse_service, static_service, metrics_cache, metrics_service, server_info);
+        let app = build_router(sse_service, static_service, metrics_cache, metrics_service, server_info, RouterConfig::default());
         
         // Test index page
         let request = Request::builder()
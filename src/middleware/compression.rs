@@ -0,0 +1,30 @@
+// Response compression for API payloads
+use axum::http::{header::CONTENT_RANGE, Extensions, HeaderMap, StatusCode, Version};
+use tower_http::compression::{
+    predicate::{DefaultPredicate, NotForContentType, Predicate},
+    CompressionLayer,
+};
+use tracing::debug;
+
+/// Which responses get compressed
+///
+/// Event streams are excluded so every event reaches the client as soon as it is
+/// written instead of waiting in an encoder buffer. Partial content is excluded
+/// because `Content-Range` offsets refer to the uncompressed bytes.
+pub fn compression_predicate() -> impl Predicate {
+    DefaultPredicate::new()
+        .and(NotForContentType::const_new("text/event-stream"))
+        .and(|_: StatusCode, _: Version, headers: &HeaderMap, _: &Extensions| {
+            !headers.contains_key(CONTENT_RANGE)
+        })
+}
+
+/// Create the gzip/brotli compression layer, negotiated via `Accept-Encoding`
+pub fn compression_layer() -> CompressionLayer<impl Predicate> {
+    debug!("Configuring compression layer");
+
+    CompressionLayer::new()
+        .gzip(true)
+        .br(true)
+        .compress_when(compression_predicate())
+}
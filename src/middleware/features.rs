@@ -0,0 +1,181 @@
+// Runtime feature toggles for API routes
+use axum::{
+    extract::{Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use serde_json::json;
+use std::collections::BTreeSet;
+use std::sync::{Arc, RwLock};
+use tracing::{info, warn};
+
+/// Path of the endpoint that manages toggles - it can never be disabled
+pub const FEATURES_ROUTE: &str = "/api/features";
+
+/// Registry of routes that have been switched off at runtime
+#[derive(Debug, Clone, Default)]
+pub struct FeatureToggles {
+    disabled: Arc<RwLock<BTreeSet<String>>>,
+}
+
+impl FeatureToggles {
+    /// Create a registry with every route enabled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry with the given routes disabled
+    pub fn with_disabled<I, S>(routes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let toggles = Self::new();
+        for route in routes {
+            toggles.set_enabled(&route.into(), false);
+        }
+        toggles
+    }
+
+    /// Create a registry from the comma-separated `DISABLED_ROUTES` environment variable
+    pub fn from_env() -> Self {
+        match std::env::var("DISABLED_ROUTES") {
+            Ok(value) => Self::with_disabled(
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|route| !route.is_empty())
+                    .map(str::to_string),
+            ),
+            Err(_) => Self::new(),
+        }
+    }
+
+    /// Check whether a request path is currently enabled
+    pub fn is_enabled(&self, path: &str) -> bool {
+        let disabled = self.disabled.read().unwrap();
+        !disabled.contains(normalize_route(path))
+    }
+
+    /// Enable or disable a route, returning false if the route cannot be toggled
+    pub fn set_enabled(&self, route: &str, enabled: bool) -> bool {
+        let route = normalize_route(route);
+        if route.is_empty() || route == FEATURES_ROUTE {
+            return false;
+        }
+
+        let mut disabled = self.disabled.write().unwrap();
+        if enabled {
+            disabled.remove(route);
+        } else {
+            disabled.insert(route.to_string());
+        }
+        true
+    }
+
+    /// Get the routes that are currently disabled
+    pub fn disabled_routes(&self) -> Vec<String> {
+        self.disabled.read().unwrap().iter().cloned().collect()
+    }
+}
+
+/// Strip a trailing slash so `/api/x/` and `/api/x` toggle the same route
+fn normalize_route(route: &str) -> &str {
+    if route.len() > 1 {
+        route.trim_end_matches('/')
+    } else {
+        route
+    }
+}
+
+/// Middleware rejecting requests to disabled routes with 503
+pub async fn feature_toggle(
+    State(toggles): State<FeatureToggles>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path().to_string();
+
+    if !toggles.is_enabled(&path) {
+        warn!("Rejected request to disabled route: {}", path);
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({
+                "error": "feature_disabled",
+                "message": format!("Route {} is temporarily disabled", path),
+                "route": path,
+                "timestamp": chrono::Utc::now(),
+            })),
+        )
+            .into_response();
+    }
+
+    next.run(request).await
+}
+
+/// Log a toggle change for the audit trail
+pub(crate) fn log_toggle_change(route: &str, enabled: bool) {
+    info!(
+        "Route {} {} at runtime",
+        route,
+        if enabled { "enabled" } else { "disabled" }
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request as HttpRequest, routing::get, Router};
+    use tower::ServiceExt;
+
+    async fn test_handler() -> &'static str {
+        "OK"
+    }
+
+    #[test]
+    fn test_toggle_registry() {
+        let toggles = FeatureToggles::new();
+        assert!(toggles.is_enabled("/api/server-status"));
+
+        assert!(toggles.set_enabled("/api/server-status/", false));
+        assert!(!toggles.is_enabled("/api/server-status"));
+        assert_eq!(toggles.disabled_routes(), vec!["/api/server-status".to_string()]);
+
+        assert!(toggles.set_enabled("/api/server-status", true));
+        assert!(toggles.is_enabled("/api/server-status"));
+
+        // The management endpoint itself cannot be switched off
+        assert!(!toggles.set_enabled(FEATURES_ROUTE, false));
+        assert!(toggles.is_enabled(FEATURES_ROUTE));
+    }
+
+    #[test]
+    fn test_with_disabled_defaults() {
+        let toggles = FeatureToggles::with_disabled(["/api/broadcast"]);
+        assert!(!toggles.is_enabled("/api/broadcast"));
+        assert!(toggles.is_enabled("/api/health"));
+    }
+
+    #[tokio::test]
+    async fn test_feature_toggle_middleware() {
+        let toggles = FeatureToggles::with_disabled(["/off"]);
+        let app = Router::new()
+            .route("/on", get(test_handler))
+            .route("/off", get(test_handler))
+            .layer(axum::middleware::from_fn_with_state(toggles, feature_toggle));
+
+        let response = app
+            .clone()
+            .oneshot(HttpRequest::builder().uri("/on").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .oneshot(HttpRequest::builder().uri("/off").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
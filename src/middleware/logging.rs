@@ -2,44 +2,51 @@
 use axum::{
     response::Response,
     middleware::Next,
-    extract::Request,
+    extract::{MatchedPath, Request, State},
+    http::{HeaderName, HeaderValue, Method},
 };
+use serde::{Deserialize, Serialize};
 use tracing::{info, warn, error, debug};
-use std::time::Instant;
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+/// Request id assigned by [`request_id_middleware`], available as a request extension
+#[derive(Debug, Clone)]
+pub struct RequestId(pub String);
 
 /// Request logging middleware
+///
+/// Method, path, status, latency and request id are recorded as structured
+/// fields, so they become separate keys when logging in JSON.
 pub async fn request_logging(request: Request, next: Next) -> Response {
     let method = request.method().clone();
-    let uri = request.uri().clone();
+    let path = request.uri().path().to_string();
+    let request_id = request
+        .extensions()
+        .get::<RequestId>()
+        .map(|RequestId(id)| id.clone())
+        .unwrap_or_default();
     let start = Instant::now();
     
-    info!("Request started: {} {}", method, uri);
+    info!(%method, %path, %request_id, "Request started");
     
     let response = next.run(request).await;
-    let status = response.status();
-    let duration = start.elapsed();
-    
-    match status.as_u16() {
-        200..=299 => info!(
-            "Request completed: {} {} -> {} ({:.2}ms)",
-            method, uri, status, duration.as_millis()
-        ),
-        300..=399 => info!(
-            "Request redirected: {} {} -> {} ({:.2}ms)",
-            method, uri, status, duration.as_millis()
-        ),
-        400..=499 => warn!(
-            "Client error: {} {} -> {} ({:.2}ms)",
-            method, uri, status, duration.as_millis()
-        ),
-        500..=599 => error!(
-            "Server error: {} {} -> {} ({:.2}ms)",
-            method, uri, status, duration.as_millis()
-        ),
-        _ => debug!(
-            "Request completed: {} {} -> {} ({:.2}ms)",
-            method, uri, status, duration.as_millis()
-        ),
+    let status = response.status().as_u16();
+    let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+    macro_rules! completed {
+        ($level:ident, $message:literal) => {
+            $level!(%method, %path, status, latency_ms, %request_id, $message)
+        };
+    }
+    
+    match status {
+        200..=299 => completed!(info, "Request completed"),
+        300..=399 => completed!(info, "Request redirected"),
+        400..=499 => completed!(warn, "Client error"),
+        500..=599 => completed!(error, "Server error"),
+        _ => completed!(debug, "Request completed"),
     }
     
     response
@@ -62,31 +69,259 @@ pub async fn error_handling(request: Request, next: Next) -> Response {
     response
 }
 
+/// Longest incoming request id that will be reused
+pub const MAX_REQUEST_ID_LENGTH: usize = 128;
+
+/// Which header carries the request id and whether incoming ids are honored
+#[derive(Debug, Clone)]
+pub struct RequestIdConfig {
+    /// Header read from the request and echoed on the response
+    pub header_name: HeaderName,
+    /// Reuse an id set by an upstream proxy instead of generating a new one
+    pub trust_incoming: bool,
+}
+
+impl Default for RequestIdConfig {
+    fn default() -> Self {
+        Self {
+            header_name: HeaderName::from_static("x-request-id"),
+            trust_incoming: true,
+        }
+    }
+}
+
+impl RequestIdConfig {
+    /// Load from `REQUEST_ID_HEADER` and `REQUEST_ID_TRUST_INCOMING`, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(value) = std::env::var("REQUEST_ID_HEADER") {
+            match HeaderName::try_from(value.trim()) {
+                Ok(name) => config.header_name = name,
+                Err(e) => warn!("Ignoring REQUEST_ID_HEADER: {}", e),
+            }
+        }
+
+        if let Ok(value) = std::env::var("REQUEST_ID_TRUST_INCOMING") {
+            match value.parse() {
+                Ok(trust) => config.trust_incoming = trust,
+                Err(e) => warn!("Ignoring REQUEST_ID_TRUST_INCOMING: {}", e),
+            }
+        }
+
+        config
+    }
+
+    /// Incoming id to reuse, if trusted and well-formed
+    fn incoming_id(&self, request: &Request) -> Option<HeaderValue> {
+        if !self.trust_incoming {
+            return None;
+        }
+
+        request
+            .headers()
+            .get(&self.header_name)
+            .filter(|value| {
+                let bytes = value.as_bytes();
+                !bytes.is_empty()
+                    && bytes.len() <= MAX_REQUEST_ID_LENGTH
+                    && bytes.iter().all(|byte| byte.is_ascii_graphic())
+            })
+            .cloned()
+    }
+}
+
 /// Middleware to add request ID for tracing
-pub async fn request_id_middleware(mut request: Request, next: Next) -> Response {
+///
+/// Reuses a trusted incoming id so traces correlate end to end, otherwise
+/// generates one, and echoes it back on the response either way.
+pub async fn request_id_middleware(
+    State(config): State<RequestIdConfig>,
+    mut request: Request,
+    next: Next,
+) -> Response {
     use uuid::Uuid;
     
-    let request_id = Uuid::new_v4().to_string();
+    let request_id = config.incoming_id(&request).unwrap_or_else(|| {
+        HeaderValue::from_str(&Uuid::new_v4().to_string()).expect("UUIDs are valid header values")
+    });
     
-    // Add to headers for potential client use
-    request.headers_mut().insert(
-        "X-Request-ID",
-        request_id.parse().unwrap_or_else(|_| "invalid".parse().unwrap())
-    );
+    // Add to headers for handlers further down the stack
+    request.headers_mut().insert(config.header_name.clone(), request_id.clone());
+    if let Ok(id) = request_id.to_str() {
+        request.extensions_mut().insert(RequestId(id.to_string()));
+    }
     
-    debug!("Request ID: {}", request_id);
+    debug!("Request ID: {:?}", request_id);
     
     let mut response = next.run(request).await;
     
     // Add to response headers
-    response.headers_mut().insert(
-        "X-Request-ID",
-        request_id.parse().unwrap_or_else(|_| "invalid".parse().unwrap())
-    );
+    response.headers_mut().insert(config.header_name, request_id);
     
     response
 }
 
+/// Upper bounds of the latency histogram buckets in milliseconds; slower requests land in an overflow bucket
+const LATENCY_BUCKETS_MS: [f64; 14] = [
+    0.5, 1.0, 2.5, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1000.0, 2500.0, 5000.0, 10000.0,
+];
+
+/// Route key for requests that matched no route, so unknown paths share one entry
+const UNMATCHED_ROUTE: &str = "<unmatched>";
+
+/// Method key for extension methods, so arbitrary method names share one entry
+const OTHER_METHOD: &str = "OTHER";
+
+/// Method key for `method`: the standard methods by name, anything else as `OTHER`
+fn method_key(method: &Method) -> &'static str {
+    match *method {
+        Method::GET => "GET",
+        Method::POST => "POST",
+        Method::PUT => "PUT",
+        Method::DELETE => "DELETE",
+        Method::PATCH => "PATCH",
+        Method::HEAD => "HEAD",
+        Method::OPTIONS => "OPTIONS",
+        Method::CONNECT => "CONNECT",
+        Method::TRACE => "TRACE",
+        _ => OTHER_METHOD,
+    }
+}
+
+/// Latency histogram of one endpoint
+#[derive(Debug, Clone, Default)]
+struct EndpointHistogram {
+    count: u64,
+    total: Duration,
+    max: Duration,
+    /// One counter per `LATENCY_BUCKETS_MS` entry plus the overflow bucket
+    buckets: [u64; LATENCY_BUCKETS_MS.len() + 1],
+}
+
+impl EndpointHistogram {
+    fn record(&mut self, duration: Duration) {
+        let ms = duration.as_secs_f64() * 1000.0;
+        let bucket = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|&bound| ms <= bound)
+            .unwrap_or(LATENCY_BUCKETS_MS.len());
+
+        self.buckets[bucket] += 1;
+        self.count += 1;
+        self.total += duration;
+        self.max = self.max.max(duration);
+    }
+
+    /// Estimated latency at quantile `q` (0-1): the upper bound of the bucket it falls in, capped at the maximum
+    fn quantile_ms(&self, q: f64) -> f64 {
+        let max_ms = self.max.as_secs_f64() * 1000.0;
+        let rank = ((q * self.count as f64).ceil() as u64).max(1);
+
+        let mut seen = 0;
+        for (bucket, &count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return LATENCY_BUCKETS_MS.get(bucket).map_or(max_ms, |&bound| bound.min(max_ms));
+            }
+        }
+        max_ms
+    }
+
+    fn stats(&self, method: &str, route: &str) -> EndpointStats {
+        let total_duration_ms = self.total.as_secs_f64() * 1000.0;
+        EndpointStats {
+            method: method.to_string(),
+            route: route.to_string(),
+            count: self.count,
+            total_duration_ms,
+            average_duration_ms: if self.count > 0 { total_duration_ms / self.count as f64 } else { 0.0 },
+            p50_ms: self.quantile_ms(0.50),
+            p95_ms: self.quantile_ms(0.95),
+            max_ms: self.max.as_secs_f64() * 1000.0,
+        }
+    }
+}
+
+/// Aggregated latency of one endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EndpointStats {
+    pub method: String,
+    /// Route pattern, e.g. `/api/health` or `/assets/*path`
+    pub route: String,
+    pub count: u64,
+    pub total_duration_ms: f64,
+    pub average_duration_ms: f64,
+    /// Median latency, estimated from histogram buckets
+    pub p50_ms: f64,
+    /// 95th percentile latency, estimated from histogram buckets
+    pub p95_ms: f64,
+    pub max_ms: f64,
+}
+
+/// Per-endpoint request latency, keyed by method and route pattern
+///
+/// Durations run until the response head is ready, so streaming endpoints
+/// are measured up to the start of the stream.
+#[derive(Debug, Clone, Default)]
+pub struct RequestMetrics {
+    endpoints: Arc<RwLock<BTreeMap<(String, String), EndpointHistogram>>>,
+}
+
+impl RequestMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one request
+    pub fn record(&self, method: &str, route: &str, duration: Duration) {
+        let mut endpoints = self.endpoints.write().unwrap();
+        endpoints
+            .entry((method.to_string(), route.to_string()))
+            .or_default()
+            .record(duration);
+    }
+
+    /// Stats of every endpoint seen so far, ordered by route then method
+    pub fn snapshot(&self) -> Vec<EndpointStats> {
+        let endpoints = self.endpoints.read().unwrap();
+        let mut stats: Vec<EndpointStats> = endpoints
+            .iter()
+            .map(|((method, route), histogram)| histogram.stats(method, route))
+            .collect();
+        stats.sort_by(|a, b| a.route.cmp(&b.route).then_with(|| a.method.cmp(&b.method)));
+        stats
+    }
+
+    /// Stats of a single endpoint, if it has been requested
+    pub fn endpoint(&self, method: &str, route: &str) -> Option<EndpointStats> {
+        let endpoints = self.endpoints.read().unwrap();
+        endpoints
+            .get(&(method.to_string(), route.to_string()))
+            .map(|histogram| histogram.stats(method, route))
+    }
+}
+
+/// Request duration middleware, recording into `RequestMetrics` by method and matched route
+pub async fn request_metrics(
+    State(metrics): State<RequestMetrics>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let method = method_key(request.method());
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| UNMATCHED_ROUTE.to_string());
+    let start = Instant::now();
+
+    let response = next.run(request).await;
+
+    metrics.record(method, &route, start.elapsed());
+    response
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -129,11 +364,135 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
     }
     
+    #[test]
+    fn test_latency_histogram_quantiles() {
+        let metrics = RequestMetrics::new();
+        for ms in [1, 2, 3, 4, 5, 6, 7, 8, 9, 400] {
+            metrics.record("GET", "/api/health", Duration::from_millis(ms));
+        }
+
+        let stats = metrics.endpoint("GET", "/api/health").unwrap();
+        assert_eq!(stats.count, 10);
+        assert!((stats.total_duration_ms - 445.0).abs() < 1e-6);
+        assert!((stats.average_duration_ms - 44.5).abs() < 1e-6);
+        assert_eq!(stats.p50_ms, 5.0);
+        assert_eq!(stats.p95_ms, 400.0);
+        assert_eq!(stats.max_ms, 400.0);
+
+        assert!(metrics.endpoint("POST", "/api/health").is_none());
+    }
+
     #[tokio::test]
-    async fn test_request_id_middleware() {
+    async fn test_request_metrics_middleware() {
+        let metrics = RequestMetrics::new();
+        let app = Router::new()
+            .route("/items/:id", get(test_handler))
+            .layer(axum::middleware::from_fn_with_state(metrics.clone(), request_metrics));
+
+        for path in ["/items/1", "/items/2", "/missing"] {
+            let request = HttpRequest::builder().uri(path).body(Body::empty()).unwrap();
+            app.clone().oneshot(request).await.unwrap();
+        }
+
+        // Requests are grouped by route pattern, not concrete path
+        let stats = metrics.snapshot();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(metrics.endpoint("GET", "/items/:id").unwrap().count, 2);
+        assert_eq!(metrics.endpoint("GET", UNMATCHED_ROUTE).unwrap().count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_request_metrics_groups_extension_methods() {
+        let metrics = RequestMetrics::new();
+        let app = Router::new()
+            .route("/items", get(test_handler))
+            .layer(axum::middleware::from_fn_with_state(metrics.clone(), request_metrics));
+
+        for method in ["FOO1", "FOO2"] {
+            let request = HttpRequest::builder().method(method).uri("/items").body(Body::empty()).unwrap();
+            app.clone().oneshot(request).await.unwrap();
+        }
+
+        // Made-up methods can't grow the table one entry at a time
+        assert_eq!(metrics.snapshot().len(), 1);
+        assert_eq!(metrics.endpoint(OTHER_METHOD, "/items").unwrap().count, 2);
+        assert!(metrics.endpoint("FOO1", "/items").is_none());
+    }
+
+    /// Log sink shared with a JSON fmt layer
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_logging_json_fields() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::registry().with(
+            tracing_subscriber::fmt::layer()
+                .json()
+                .flatten_event(true)
+                .with_writer(move || writer.clone()),
+        );
+        // The test runtime is single threaded, so the middleware logs on this thread
+        let _guard = tracing::subscriber::set_default(subscriber);
+
         let app = Router::new()
             .route("/test", get(test_handler))
-            .layer(axum::middleware::from_fn(request_id_middleware));
+            .layer(axum::middleware::from_fn(request_logging))
+            .layer(axum::middleware::from_fn_with_state(RequestIdConfig::default(), request_id_middleware));
+        let request = HttpRequest::builder()
+            .uri("/test?verbose=1")
+            .header("x-request-id", "log-test-id")
+            .body(Body::empty())
+            .unwrap();
+        app.oneshot(request).await.unwrap();
+
+        let output = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        let completed: serde_json::Value = output
+            .lines()
+            .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+            .find(|line| line["message"] == "Request completed")
+            .expect("no completion log line");
+
+        assert_eq!(completed["method"], "GET");
+        assert_eq!(completed["path"], "/test");
+        assert_eq!(completed["status"], 200);
+        assert!(completed["latency_ms"].as_f64().unwrap() >= 0.0);
+        assert_eq!(completed["request_id"], "log-test-id");
+    }
+
+    fn request_id_app(config: RequestIdConfig) -> Router {
+        Router::new()
+            .route("/test", get(test_handler))
+            .route("/echo", get(|headers: axum::http::HeaderMap| async move {
+                headers.get("x-trace-id").and_then(|v| v.to_str().ok()).unwrap_or_default().to_string()
+            }))
+            .layer(axum::middleware::from_fn_with_state(config, request_id_middleware))
+    }
+
+    fn trace_id_config() -> RequestIdConfig {
+        RequestIdConfig {
+            header_name: HeaderName::from_static("x-trace-id"),
+            ..RequestIdConfig::default()
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_request_id_middleware() {
+        let app = request_id_app(RequestIdConfig::default());
         
         let request = HttpRequest::builder()
             .uri("/test")
@@ -144,4 +503,54 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
         assert!(response.headers().get("X-Request-ID").is_some());
     }
+
+    #[tokio::test]
+    async fn test_request_id_generated_when_absent() {
+        let response = request_id_app(trace_id_config())
+            .oneshot(HttpRequest::builder().uri("/test").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        let id = response.headers().get("x-trace-id").unwrap().to_str().unwrap();
+        assert!(uuid::Uuid::parse_str(id).is_ok());
+        assert!(response.headers().get("x-request-id").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_request_id_reuses_incoming() {
+        let request = HttpRequest::builder()
+            .uri("/echo")
+            .header("x-trace-id", "edge-4f2a9c")
+            .body(Body::empty())
+            .unwrap();
+        let response = request_id_app(trace_id_config()).oneshot(request).await.unwrap();
+
+        assert_eq!(response.headers().get("x-trace-id").unwrap(), "edge-4f2a9c");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"edge-4f2a9c");
+    }
+
+    #[tokio::test]
+    async fn test_request_id_replaces_untrusted_or_malformed() {
+        let untrusted = RequestIdConfig {
+            trust_incoming: false,
+            ..trace_id_config()
+        };
+        let request = HttpRequest::builder()
+            .uri("/test")
+            .header("x-trace-id", "edge-4f2a9c")
+            .body(Body::empty())
+            .unwrap();
+        let response = request_id_app(untrusted).oneshot(request).await.unwrap();
+        assert_ne!(response.headers().get("x-trace-id").unwrap(), "edge-4f2a9c");
+
+        let request = HttpRequest::builder()
+            .uri("/test")
+            .header("x-trace-id", "a".repeat(MAX_REQUEST_ID_LENGTH + 1))
+            .body(Body::empty())
+            .unwrap();
+        let response = request_id_app(trace_id_config()).oneshot(request).await.unwrap();
+        let id = response.headers().get("x-trace-id").unwrap().to_str().unwrap();
+        assert!(uuid::Uuid::parse_str(id).is_ok());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,156 @@
+// Maintenance mode
+// Answers every request with 503 while operators work on the server, without a redeploy
+
+use axum::{
+    extract::{Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::{Html, IntoResponse, Json, Response},
+};
+use serde_json::json;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+/// Routes served during maintenance: probes, and the switch to leave it again
+pub const MAINTENANCE_EXEMPT_ROUTES: &[&str] = &["/api/health", "/api/readiness", "/api/maintenance"];
+
+/// Page shown to browsers during maintenance
+const MAINTENANCE_PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>Down for maintenance</title>
+    <style>
+        body { font-family: Arial, sans-serif; max-width: 800px; margin: 0 auto; padding: 20px; }
+    </style>
+</head>
+<body>
+    <h1>Down for maintenance</h1>
+    <p>The server status dashboard is temporarily unavailable while maintenance is carried out.
+    Please try again shortly.</p>
+</body>
+</html>"#;
+
+/// Shared maintenance flag, switchable at runtime
+#[derive(Debug, Clone, Default)]
+pub struct MaintenanceMode(Arc<AtomicBool>);
+
+impl MaintenanceMode {
+    /// A flag that starts out off
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start in maintenance when `MAINTENANCE_MODE` is `1` or `true`
+    pub fn from_env() -> Self {
+        let mode = Self::new();
+        if let Ok(value) = std::env::var("MAINTENANCE_MODE") {
+            match value.trim().to_ascii_lowercase().as_str() {
+                "1" | "true" => {
+                    mode.set_enabled(true);
+                }
+                "" | "0" | "false" => {}
+                _ => warn!("Ignoring MAINTENANCE_MODE: expected 1/0 or true/false, got {:?}", value),
+            }
+        }
+        mode
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+
+    /// Switch maintenance on or off, returning the previous state
+    pub fn set_enabled(&self, enabled: bool) -> bool {
+        let previous = self.0.swap(enabled, Ordering::AcqRel);
+        if previous != enabled {
+            warn!("🚧 Maintenance mode {}", if enabled { "enabled" } else { "disabled" });
+        }
+        previous
+    }
+}
+
+/// Whether a path stays reachable during maintenance
+fn is_exempt(path: &str) -> bool {
+    let path = if path.len() > 1 { path.trim_end_matches('/') } else { path };
+    MAINTENANCE_EXEMPT_ROUTES.contains(&path)
+}
+
+/// Middleware answering 503 during maintenance: JSON for `/api` routes, a page for everything else
+pub async fn maintenance_mode(
+    State(maintenance): State<MaintenanceMode>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path();
+    if !maintenance.is_enabled() || is_exempt(path) {
+        return next.run(request).await;
+    }
+
+    debug!("Rejected request to {} during maintenance", path);
+    if path == "/api" || path.starts_with("/api/") {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({
+                "error": "maintenance",
+                "message": "The server is down for maintenance",
+                "timestamp": chrono::Utc::now(),
+            })),
+        )
+            .into_response()
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, Html(MAINTENANCE_PAGE)).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::{header, Request as HttpRequest}, routing::get, Router};
+    use tower::ServiceExt;
+
+    async fn test_handler() -> &'static str {
+        "OK"
+    }
+
+    async fn get_status(app: &Router, path: &str) -> (StatusCode, String) {
+        let response = app
+            .clone()
+            .oneshot(HttpRequest::builder().uri(path).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let content_type = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .map(|value| value.to_str().unwrap().to_string())
+            .unwrap_or_default();
+        (response.status(), content_type)
+    }
+
+    #[tokio::test]
+    async fn test_maintenance_middleware() {
+        let maintenance = MaintenanceMode::new();
+        let app = Router::new()
+            .route("/", get(test_handler))
+            .route("/api/server-status", get(test_handler))
+            .route("/api/health", get(test_handler))
+            .layer(axum::middleware::from_fn_with_state(maintenance.clone(), maintenance_mode));
+
+        assert_eq!(get_status(&app, "/").await.0, StatusCode::OK);
+        assert_eq!(get_status(&app, "/api/server-status").await.0, StatusCode::OK);
+
+        assert!(!maintenance.set_enabled(true));
+        let (status, content_type) = get_status(&app, "/").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert!(content_type.starts_with("text/html"));
+        let (status, content_type) = get_status(&app, "/api/server-status").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert!(content_type.starts_with("application/json"));
+        assert_eq!(get_status(&app, "/api/health").await.0, StatusCode::OK);
+
+        assert!(maintenance.set_enabled(false));
+        assert_eq!(get_status(&app, "/").await.0, StatusCode::OK);
+    }
+}
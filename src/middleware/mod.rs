@@ -1,9 +1,18 @@
 // Middleware module
 pub mod security;
 pub mod logging;
+pub mod features;
+pub mod rate_limit;
+pub mod compression;
+pub mod maintenance;
 
 // Re-export commonly used middleware
-pub use security::{cors_layer, security_headers, cache_control};
+pub use security::{cors_layer, CorsConfig, CorsConfigError, security_headers, cache_control, require_api_key, require_admin_key, ApiKeyConfig, SecurityHeadersConfig};
 pub use logging::{
-    request_logging, error_handling, request_id_middleware
-};
\ No newline at end of file
+    request_logging, error_handling, request_id_middleware, RequestId, RequestIdConfig,
+    request_metrics, RequestMetrics, EndpointStats,
+};
+pub use features::{feature_toggle, FeatureToggles};
+pub use rate_limit::{rate_limit_layer, RateLimitConfig};
+pub use compression::compression_layer;
+pub use maintenance::{maintenance_mode, MaintenanceMode};
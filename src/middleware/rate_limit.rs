@@ -0,0 +1,331 @@
+// Per-client rate limiting with token buckets
+use axum::{
+    extract::{ConnectInfo, Request},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use dashmap::DashMap;
+use futures::future::BoxFuture;
+use serde_json::json;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tower::{Layer, Service};
+use tracing::{debug, warn};
+
+/// Default number of requests a client may make per window
+pub const DEFAULT_RATE_LIMIT_REQUESTS: u32 = 300;
+
+/// Default refill window in seconds
+pub const DEFAULT_RATE_LIMIT_WINDOW_SECS: u64 = 60;
+
+/// Number of tracked clients above which idle buckets are pruned
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// Rate limit settings
+#[derive(Debug, Clone)]
+pub struct RateLimitConfig {
+    /// Requests allowed per window, which is also the burst size (0 disables limiting)
+    pub requests: u32,
+    /// Time for an empty bucket to refill completely
+    pub window: Duration,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            requests: DEFAULT_RATE_LIMIT_REQUESTS,
+            window: Duration::from_secs(DEFAULT_RATE_LIMIT_WINDOW_SECS),
+        }
+    }
+}
+
+impl RateLimitConfig {
+    /// Read `RATE_LIMIT_REQUESTS` and `RATE_LIMIT_WINDOW_SECS`, keeping defaults for unset or invalid values
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Some(requests) = std::env::var("RATE_LIMIT_REQUESTS").ok().and_then(|v| v.trim().parse().ok()) {
+            config.requests = requests;
+        }
+        match std::env::var("RATE_LIMIT_WINDOW_SECS").ok().map(|v| v.trim().parse::<u64>()) {
+            Some(Ok(secs)) if secs > 0 => config.window = Duration::from_secs(secs),
+            Some(_) => warn!("Ignoring invalid RATE_LIMIT_WINDOW_SECS, using {}s", DEFAULT_RATE_LIMIT_WINDOW_SECS),
+            None => {}
+        }
+
+        config
+    }
+
+    /// Whether requests are limited at all
+    pub fn is_enabled(&self) -> bool {
+        self.requests > 0 && !self.window.is_zero()
+    }
+
+    /// Tokens added per second
+    fn refill_rate(&self) -> f64 {
+        self.requests as f64 / self.window.as_secs_f64()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Token buckets keyed by client address
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    buckets: Arc<DashMap<String, Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config,
+            buckets: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Take a token for `client`, or return how long until one is available
+    pub fn check(&self, client: &str) -> Result<(), Duration> {
+        self.check_at(client, Instant::now())
+    }
+
+    fn check_at(&self, client: &str, now: Instant) -> Result<(), Duration> {
+        if !self.config.is_enabled() {
+            return Ok(());
+        }
+
+        let capacity = self.config.requests as f64;
+        let rate = self.config.refill_rate();
+
+        if self.buckets.len() > PRUNE_THRESHOLD {
+            self.prune(now);
+        }
+
+        let mut bucket = self.buckets.entry(client.to_string()).or_insert(Bucket {
+            tokens: capacity,
+            last_refill: now,
+        });
+
+        let elapsed = now.saturating_duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(capacity);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / rate))
+        }
+    }
+
+    /// Drop buckets that have refilled completely, since a fresh bucket is equivalent
+    fn prune(&self, now: Instant) {
+        let full_after = self.config.window;
+        self.buckets
+            .retain(|_, bucket| now.saturating_duration_since(bucket.last_refill) < full_after);
+        debug!("Pruned rate limit buckets, {} clients tracked", self.buckets.len());
+    }
+}
+
+/// Client address: first `X-Forwarded-For` entry, else the peer address
+fn client_key(request: &Request) -> String {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| *addr);
+    client_address(request.headers(), peer).unwrap_or_else(|| "unknown".to_string())
+}
+
+/// First `X-Forwarded-For` entry, else the peer's IP
+pub(crate) fn client_address(headers: &HeaderMap, peer: Option<SocketAddr>) -> Option<String> {
+    headers
+        .get("x-forwarded-for")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(',').next())
+        .map(str::trim)
+        .filter(|ip| !ip.is_empty())
+        .map(str::to_string)
+        .or_else(|| peer.map(|addr| addr.ip().to_string()))
+}
+
+fn too_many_requests(client: &str, retry_after: Duration) -> Response {
+    // Retry-After takes whole seconds; round up so clients never retry too early
+    let secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+    let secs = secs.max(1);
+
+    let mut response = (
+        StatusCode::TOO_MANY_REQUESTS,
+        Json(json!({
+            "error": "rate_limited",
+            "message": format!("Too many requests, retry in {}s", secs),
+            "retry_after_seconds": secs,
+            "timestamp": chrono::Utc::now(),
+        })),
+    )
+        .into_response();
+    response.headers_mut().insert(header::RETRY_AFTER, HeaderValue::from(secs));
+
+    warn!("Rate limited client {} for {}s", client, secs);
+    response
+}
+
+/// Layer rejecting clients that exceed their request budget with 429
+pub fn rate_limit_layer(config: RateLimitConfig) -> RateLimitLayer {
+    RateLimitLayer {
+        limiter: RateLimiter::new(config),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RateLimitLayer {
+    limiter: RateLimiter,
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService {
+            inner,
+            limiter: self.limiter.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RateLimitService<S> {
+    inner: S,
+    limiter: RateLimiter,
+}
+
+impl<S> Service<Request> for RateLimitService<S>
+where
+    S: Service<Request, Response = Response, Error = Infallible> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Response, Infallible>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let client = client_key(&request);
+        if let Err(retry_after) = self.limiter.check(&client) {
+            return Box::pin(async move { Ok(too_many_requests(&client, retry_after)) });
+        }
+
+        // Use the instance that was polled ready, leaving a fresh clone in its place
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        Box::pin(async move { inner.call(request).await })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request as HttpRequest, routing::get, Router};
+    use tower::ServiceExt;
+
+    async fn test_handler() -> &'static str {
+        "OK"
+    }
+
+    fn request_from(ip: &str) -> HttpRequest<Body> {
+        HttpRequest::builder()
+            .uri("/")
+            .header("x-forwarded-for", ip)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_bucket_exhaustion_and_refill() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            requests: 3,
+            window: Duration::from_secs(3),
+        });
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            assert!(limiter.check_at("10.0.0.1", start).is_ok());
+        }
+        let retry_after = limiter.check_at("10.0.0.1", start).unwrap_err();
+        assert_eq!(retry_after, Duration::from_secs(1));
+
+        // Other clients have their own bucket
+        assert!(limiter.check_at("10.0.0.2", start).is_ok());
+
+        // One token per second comes back
+        let later = start + Duration::from_secs(1);
+        assert!(limiter.check_at("10.0.0.1", later).is_ok());
+        assert!(limiter.check_at("10.0.0.1", later).is_err());
+
+        // The bucket never holds more than its capacity
+        let much_later = start + Duration::from_secs(60);
+        for _ in 0..3 {
+            assert!(limiter.check_at("10.0.0.1", much_later).is_ok());
+        }
+        assert!(limiter.check_at("10.0.0.1", much_later).is_err());
+    }
+
+    #[test]
+    fn test_disabled_limit() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            requests: 0,
+            window: Duration::from_secs(1),
+        });
+        let now = Instant::now();
+        for _ in 0..1000 {
+            assert!(limiter.check_at("10.0.0.1", now).is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_middleware() {
+        let app = Router::new()
+            .route("/", get(test_handler))
+            .layer(rate_limit_layer(RateLimitConfig {
+                requests: 2,
+                window: Duration::from_millis(200),
+            }));
+
+        for _ in 0..2 {
+            let response = app.clone().oneshot(request_from("10.0.0.1")).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        let response = app.clone().oneshot(request_from("10.0.0.1, 192.168.0.1")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "1");
+
+        let response = app.clone().oneshot(request_from("10.0.0.2")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // A token is back after a refill interval
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        let response = app.oneshot(request_from("10.0.0.1")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn test_client_key_from_peer_addr() {
+        let mut request = HttpRequest::builder().uri("/").body(Body::empty()).unwrap();
+        assert_eq!(client_key(&request), "unknown");
+
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))));
+        assert_eq!(client_key(&request), "127.0.0.1");
+    }
+}
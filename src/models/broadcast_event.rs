@@ -0,0 +1,104 @@
+// Custom broadcast event model
+// Operator-pushed messages, such as maintenance banners, sent to time stream subscribers
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Longest accepted broadcast message, in bytes
+pub const MAX_BROADCAST_MESSAGE_BYTES: usize = 4096;
+
+/// Longest accepted broadcast event type, in characters
+pub const MAX_BROADCAST_EVENT_TYPE_LENGTH: usize = 64;
+
+/// SSE event type used when a broadcast doesn't name one
+pub const DEFAULT_BROADCAST_EVENT_TYPE: &str = "broadcast";
+
+/// Event types the time stream already uses, which custom broadcasts can't impersonate
+const RESERVED_EVENT_TYPES: [&str; 2] = ["time-update", "connection-lagged"];
+
+/// A custom message broadcast to every time stream subscriber
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BroadcastEvent {
+    /// SSE event type the message is sent as
+    pub event_type: String,
+    pub message: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Broadcast validation errors
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum BroadcastValidationError {
+    #[error("Message is {len} bytes, the limit is {max}")]
+    MessageTooLong { len: usize, max: usize },
+    #[error("Invalid event type {event_type:?}: {reason}")]
+    InvalidEventType { event_type: String, reason: String },
+}
+
+impl BroadcastEvent {
+    /// Create a validated broadcast, defaulting the event type to `broadcast`
+    pub fn new(event_type: Option<String>, message: String) -> Result<Self, BroadcastValidationError> {
+        if message.len() > MAX_BROADCAST_MESSAGE_BYTES {
+            return Err(BroadcastValidationError::MessageTooLong {
+                len: message.len(),
+                max: MAX_BROADCAST_MESSAGE_BYTES,
+            });
+        }
+
+        let event_type = event_type.unwrap_or_else(|| DEFAULT_BROADCAST_EVENT_TYPE.to_string());
+        Self::validate_event_type(&event_type)?;
+
+        Ok(Self {
+            event_type,
+            message,
+            timestamp: Utc::now(),
+        })
+    }
+
+    /// Event types end up in the SSE `event:` line, so only a safe token charset is allowed
+    fn validate_event_type(event_type: &str) -> Result<(), BroadcastValidationError> {
+        let invalid = |reason: &str| BroadcastValidationError::InvalidEventType {
+            event_type: event_type.to_string(),
+            reason: reason.to_string(),
+        };
+
+        if event_type.is_empty() || event_type.chars().count() > MAX_BROADCAST_EVENT_TYPE_LENGTH {
+            return Err(invalid(&format!("must be 1-{} characters", MAX_BROADCAST_EVENT_TYPE_LENGTH)));
+        }
+        if !event_type.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err(invalid("only ASCII letters, digits, '-' and '_' are allowed"));
+        }
+        if RESERVED_EVENT_TYPES.contains(&event_type) {
+            return Err(invalid("reserved for time stream events"));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_broadcast_event_validation() {
+        let event = BroadcastEvent::new(None, "Maintenance at 22:00".to_string()).unwrap();
+        assert_eq!(event.event_type, DEFAULT_BROADCAST_EVENT_TYPE);
+
+        let event = BroadcastEvent::new(Some("maintenance".to_string()), String::new()).unwrap();
+        assert_eq!(event.event_type, "maintenance");
+
+        assert_eq!(
+            BroadcastEvent::new(None, "x".repeat(MAX_BROADCAST_MESSAGE_BYTES + 1)).unwrap_err(),
+            BroadcastValidationError::MessageTooLong { len: MAX_BROADCAST_MESSAGE_BYTES + 1, max: MAX_BROADCAST_MESSAGE_BYTES }
+        );
+        assert!(BroadcastEvent::new(None, "x".repeat(MAX_BROADCAST_MESSAGE_BYTES)).is_ok());
+
+        for event_type in ["", "evil\nevent: forged", "time-update", "has space"] {
+            assert!(
+                BroadcastEvent::new(Some(event_type.to_string()), "hi".to_string()).is_err(),
+                "{:?} should be rejected",
+                event_type
+            );
+        }
+    }
+}
@@ -0,0 +1,88 @@
+// Cumulative counter deltas
+// Turns two samples of a monotonically increasing counter into a delta and rate
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Change in a cumulative counter between two samples
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct CounterDelta {
+    /// Increase since the previous sample (0 after a reset)
+    pub delta: u64,
+    /// Increase per second (0 after a reset)
+    pub rate_per_second: f64,
+    /// The counter went backwards, e.g. after an interface bounce or restart
+    pub counter_reset: bool,
+}
+
+impl CounterDelta {
+    /// Compute the delta between two samples taken `elapsed` apart
+    ///
+    /// A decreasing counter is treated as a reset: the delta and rate are
+    /// zeroed and the current sample becomes the new baseline, rather than
+    /// producing a negative or wrapped-around spike.
+    pub fn between(previous: u64, current: u64, elapsed: Duration) -> Self {
+        if current < previous {
+            return Self {
+                delta: 0,
+                rate_per_second: 0.0,
+                counter_reset: true,
+            };
+        }
+
+        let delta = current - previous;
+        let seconds = elapsed.as_secs_f64();
+        let rate_per_second = if seconds > 0.0 {
+            delta as f64 / seconds
+        } else {
+            0.0
+        };
+
+        Self {
+            delta,
+            rate_per_second,
+            counter_reset: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_increasing_counter() {
+        let delta = CounterDelta::between(1000, 3000, Duration::from_secs(2));
+        assert_eq!(delta.delta, 2000);
+        assert_eq!(delta.rate_per_second, 1000.0);
+        assert!(!delta.counter_reset);
+    }
+
+    #[test]
+    fn test_decreasing_counter_sequence() {
+        let samples = [5000u64, 6000, 200, 1200];
+        let deltas: Vec<CounterDelta> = samples
+            .windows(2)
+            .map(|pair| CounterDelta::between(pair[0], pair[1], Duration::from_secs(1)))
+            .collect();
+
+        assert!(!deltas[0].counter_reset);
+        assert_eq!(deltas[0].rate_per_second, 1000.0);
+
+        // 6000 -> 200 is a reset: flagged, and zero rather than negative
+        assert!(deltas[1].counter_reset);
+        assert_eq!(deltas[1].delta, 0);
+        assert_eq!(deltas[1].rate_per_second, 0.0);
+
+        // The reset sample becomes the new baseline
+        assert!(!deltas[2].counter_reset);
+        assert_eq!(deltas[2].rate_per_second, 1000.0);
+    }
+
+    #[test]
+    fn test_zero_elapsed() {
+        let delta = CounterDelta::between(10, 20, Duration::ZERO);
+        assert_eq!(delta.delta, 10);
+        assert_eq!(delta.rate_per_second, 0.0);
+    }
+}
@@ -2,16 +2,26 @@
 // CPU utilization and load average information
 
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 
 /// CPU utilization information
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct CpuMetrics {
-    /// Current CPU usage percentage (0-100+% for multi-core)
+    /// Current CPU usage percentage (0-100% average, or 0-N×100% total)
     pub usage_percentage: f32,
     /// Number of CPU cores
     pub core_count: u32,
+    /// Scale `usage_percentage` is expressed in
+    #[serde(default)]
+    pub scale: CpuScale,
     /// System load averages
     pub load_average: LoadAverage,
+    /// Usage of each core (0-100%), empty unless per-core collection is enabled
+    #[serde(default, rename = "per_core_usage")]
+    pub core_usage_percentages: Vec<f32>,
+    /// Average of the CPU thermal sensors, when enabled and any are present
+    #[serde(default)]
+    pub temperature_celsius: Option<f32>,
 }
 
 impl Default for CpuMetrics {
@@ -19,13 +29,39 @@ impl Default for CpuMetrics {
         Self {
             usage_percentage: 0.0,
             core_count: 1,
+            scale: CpuScale::default(),
             load_average: LoadAverage::default(),
+            core_usage_percentages: Vec::new(),
+            temperature_celsius: None,
+        }
+    }
+}
+
+/// How CPU usage percentage is normalized
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CpuScale {
+    /// Average across all cores (0-100%), as reported by sysinfo
+    #[default]
+    Average,
+    /// Linux-style sum across cores (0-N×100%)
+    Total,
+}
+
+impl std::str::FromStr for CpuScale {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "average" => Ok(Self::Average),
+            "total" => Ok(Self::Total),
+            other => Err(format!("Unknown CPU scale: {}", other)),
         }
     }
 }
 
 /// System load average data
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct LoadAverage {
     /// 1-minute load average
     pub one_minute: f32,
@@ -54,6 +90,8 @@ pub enum CpuValidationError {
     InvalidCoreCount { count: u32 },
     #[error("Load average invalid: {value} (must be >= 0)")]
     InvalidLoadAverage { value: f32 },
+    #[error("Core {core} usage invalid: {percentage}% (must be >= 0)")]
+    InvalidPerCoreUsage { core: usize, percentage: f32 },
 }
 
 #[allow(dead_code)]
@@ -63,7 +101,10 @@ impl CpuMetrics {
         let metrics = CpuMetrics {
             usage_percentage,
             core_count,
+            scale: CpuScale::Average,
             load_average,
+            core_usage_percentages: Vec::new(),
+            temperature_celsius: None,
         };
 
         metrics.validate()?;
@@ -89,12 +130,17 @@ impl CpuMetrics {
         // Load averages must be non-negative
         self.load_average.validate()?;
 
+        // Each core's usage must be non-negative
+        if let Some((core, &percentage)) = self.core_usage_percentages.iter().enumerate().find(|(_, usage)| **usage < 0.0) {
+            return Err(CpuValidationError::InvalidPerCoreUsage { core, percentage });
+        }
+
         Ok(())
     }
 
     /// Get CPU usage level description
     pub fn usage_level(&self) -> &'static str {
-        match self.usage_percentage {
+        match self.per_core_usage() {
             x if x < 25.0 => "Low",
             x if x < 50.0 => "Normal", 
             x if x < 75.0 => "High",
@@ -103,14 +149,14 @@ impl CpuMetrics {
         }
     }
 
-    /// Check if CPU usage is critical (>90%)
+    /// Check if CPU usage is critical (>90% per core)
     pub fn is_critical(&self) -> bool {
-        self.usage_percentage > 90.0
+        self.per_core_usage() > 90.0
     }
 
-    /// Check if CPU usage is high (>75%)
+    /// Check if CPU usage is high (>75% per core)
     pub fn is_high(&self) -> bool {
-        self.usage_percentage > 75.0
+        self.per_core_usage() > 75.0
     }
 
     /// Format CPU usage with core information
@@ -123,9 +169,26 @@ impl CpuMetrics {
         )
     }
 
-    /// Get per-core usage percentage (for multi-core systems)
+    /// Get average usage per core, whatever the scale
     pub fn per_core_usage(&self) -> f32 {
-        self.usage_percentage / self.core_count as f32
+        match self.scale {
+            CpuScale::Average => self.usage_percentage,
+            CpuScale::Total => self.usage_percentage / self.core_count.max(1) as f32,
+        }
+    }
+
+    /// Convert the usage percentage to the given scale
+    pub fn to_scale(&self, scale: CpuScale) -> Self {
+        let usage_percentage = match scale {
+            CpuScale::Average => self.per_core_usage(),
+            CpuScale::Total => self.per_core_usage() * self.core_count as f32,
+        };
+
+        Self {
+            usage_percentage,
+            scale,
+            ..self.clone()
+        }
     }
 }
 
@@ -200,6 +263,28 @@ mod tests {
         assert_eq!(metrics.load_average.one_minute, 1.2);
     }
 
+    #[test]
+    fn test_per_core_usage_validation() {
+        let mut metrics = CpuMetrics {
+            core_usage_percentages: vec![12.5, 80.0, 3.0, 0.0],
+            ..CpuMetrics::default()
+        };
+        assert!(metrics.validate().is_ok());
+
+        metrics.core_usage_percentages[2] = -1.0;
+        match metrics.validate() {
+            Err(CpuValidationError::InvalidPerCoreUsage { core, percentage }) => {
+                assert_eq!(core, 2);
+                assert_eq!(percentage, -1.0);
+            }
+            other => panic!("Expected InvalidPerCoreUsage error, got {:?}", other),
+        }
+
+        // Serialized as an empty list, not omitted
+        let json = serde_json::to_value(CpuMetrics::default()).unwrap();
+        assert_eq!(json["per_core_usage"], serde_json::json!([]));
+    }
+
     #[test]
     fn test_cpu_metrics_invalid_percentage() {
         let load_avg = LoadAverage::new(1.0, 1.0, 1.0).unwrap();
@@ -231,12 +316,40 @@ mod tests {
     #[test]
     fn test_cpu_usage_over_100_valid() {
         let load_avg = LoadAverage::new(2.0, 1.8, 1.5).unwrap();
-        let metrics = CpuMetrics::new(350.0, 4, load_avg).unwrap(); // 350% on 4-core system
+        let metrics = CpuMetrics {
+            scale: CpuScale::Total,
+            ..CpuMetrics::new(350.0, 4, load_avg).unwrap() // 350% on 4-core system
+        };
 
         assert_eq!(metrics.usage_percentage, 350.0);
         assert_eq!(metrics.per_core_usage(), 87.5); // 350% / 4 cores
     }
 
+    #[test]
+    fn test_cpu_scale_conversion() {
+        let load_avg = LoadAverage::new(1.0, 1.0, 1.0).unwrap();
+        let average = CpuMetrics::new(50.0, 4, load_avg).unwrap();
+        assert_eq!(average.scale, CpuScale::Average);
+        assert_eq!(average.per_core_usage(), 50.0);
+
+        // 4 cores at 50% average is 200% in total mode
+        let total = average.to_scale(CpuScale::Total);
+        assert_eq!(total.usage_percentage, 200.0);
+        assert_eq!(total.core_count, 4);
+        assert_eq!(total.per_core_usage(), 50.0);
+        assert!(!total.is_high());
+
+        // Converting back is lossless
+        assert_eq!(total.to_scale(CpuScale::Average), average);
+    }
+
+    #[test]
+    fn test_cpu_scale_parsing() {
+        assert_eq!("average".parse::<CpuScale>(), Ok(CpuScale::Average));
+        assert_eq!("Total".parse::<CpuScale>(), Ok(CpuScale::Total));
+        assert!("sum".parse::<CpuScale>().is_err());
+    }
+
     #[test]
     fn test_cpu_usage_levels() {
         let load_avg = LoadAverage::new(1.0, 1.0, 1.0).unwrap();
@@ -0,0 +1,164 @@
+// Disk metrics model
+// Storage usage totals and per-mount breakdown
+
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+
+/// Storage usage aggregated across all mounted filesystems
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default, JsonSchema)]
+pub struct DiskMetrics {
+    /// Total capacity of all mounts in bytes
+    pub total_bytes: u64,
+    /// Used space across all mounts in bytes
+    pub used_bytes: u64,
+    /// Space available to unprivileged users across all mounts in bytes
+    pub available_bytes: u64,
+    /// Overall usage as percentage (0-100%)
+    pub usage_percentage: f32,
+    /// Usage of each mounted filesystem
+    #[serde(default)]
+    pub mounts: Vec<MountMetrics>,
+}
+
+/// Storage usage of a single mounted filesystem
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default, JsonSchema)]
+pub struct MountMetrics {
+    /// Where the filesystem is mounted, e.g. "/" or "/var"
+    pub mount_point: String,
+    /// Filesystem type, e.g. "ext4"
+    pub file_system: String,
+    /// Capacity in bytes
+    pub total_bytes: u64,
+    /// Used space in bytes
+    pub used_bytes: u64,
+    /// Space available to unprivileged users in bytes
+    pub available_bytes: u64,
+    /// Usage as percentage (0-100%)
+    pub usage_percentage: f32,
+}
+
+/// Disk validation errors
+#[derive(Debug, thiserror::Error)]
+pub enum DiskValidationError {
+    #[error("Mount {mount_point} inconsistent: used + available ({sum}) > total ({total})")]
+    MountInconsistent { mount_point: String, sum: u64, total: u64 },
+}
+
+fn usage_percentage(used_bytes: u64, total_bytes: u64) -> f32 {
+    if total_bytes > 0 {
+        ((used_bytes as f64 / total_bytes as f64) * 100.0) as f32
+    } else {
+        0.0
+    }
+}
+
+impl MountMetrics {
+    /// Create new MountMetrics with validation
+    pub fn new(
+        mount_point: impl Into<String>,
+        file_system: impl Into<String>,
+        total_bytes: u64,
+        used_bytes: u64,
+        available_bytes: u64,
+    ) -> Result<Self, DiskValidationError> {
+        let mount = MountMetrics {
+            mount_point: mount_point.into(),
+            file_system: file_system.into(),
+            total_bytes,
+            used_bytes,
+            available_bytes,
+            usage_percentage: usage_percentage(used_bytes, total_bytes),
+        };
+
+        mount.validate()?;
+        Ok(mount)
+    }
+
+    /// Validate mount metrics: used + available ≤ total
+    pub fn validate(&self) -> Result<(), DiskValidationError> {
+        let sum = self.used_bytes.saturating_add(self.available_bytes);
+        if sum > self.total_bytes {
+            return Err(DiskValidationError::MountInconsistent {
+                mount_point: self.mount_point.clone(),
+                sum,
+                total: self.total_bytes,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl DiskMetrics {
+    /// Aggregate per-mount metrics into totals
+    pub fn from_mounts(mounts: Vec<MountMetrics>) -> Self {
+        let total_bytes = mounts.iter().map(|m| m.total_bytes).sum();
+        let used_bytes = mounts.iter().map(|m| m.used_bytes).sum();
+        let available_bytes = mounts.iter().map(|m| m.available_bytes).sum();
+
+        DiskMetrics {
+            total_bytes,
+            used_bytes,
+            available_bytes,
+            usage_percentage: usage_percentage(used_bytes, total_bytes),
+            mounts,
+        }
+    }
+
+    /// Validate every mount
+    pub fn validate(&self) -> Result<(), DiskValidationError> {
+        self.mounts.iter().try_for_each(MountMetrics::validate)
+    }
+
+    /// Mount with the highest usage percentage, if any
+    pub fn fullest_mount(&self) -> Option<&MountMetrics> {
+        self.mounts
+            .iter()
+            .max_by(|a, b| a.usage_percentage.total_cmp(&b.usage_percentage))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GB: u64 = 1024 * 1024 * 1024;
+
+    #[test]
+    fn test_disk_metrics_aggregation() {
+        let disk = DiskMetrics::from_mounts(vec![
+            MountMetrics::new("/", "ext4", 100 * GB, 25 * GB, 75 * GB).unwrap(),
+            MountMetrics::new("/data", "xfs", 300 * GB, 270 * GB, 30 * GB).unwrap(),
+        ]);
+
+        assert_eq!(disk.total_bytes, 400 * GB);
+        assert_eq!(disk.used_bytes, 295 * GB);
+        assert_eq!(disk.available_bytes, 105 * GB);
+        assert!((disk.usage_percentage - 73.75).abs() < 0.01);
+        assert_eq!(disk.fullest_mount().unwrap().mount_point, "/data");
+        assert!(disk.validate().is_ok());
+
+        assert_eq!(DiskMetrics::default().usage_percentage, 0.0);
+        assert!(DiskMetrics::default().fullest_mount().is_none());
+    }
+
+    #[test]
+    fn test_mount_inconsistent() {
+        match MountMetrics::new("/", "ext4", 10 * GB, 8 * GB, 4 * GB).unwrap_err() {
+            DiskValidationError::MountInconsistent { mount_point, sum, total } => {
+                assert_eq!(mount_point, "/");
+                assert_eq!(sum, 12 * GB);
+                assert_eq!(total, 10 * GB);
+            }
+        }
+
+        let disk = DiskMetrics::from_mounts(vec![MountMetrics {
+            mount_point: "/broken".to_string(),
+            total_bytes: GB,
+            used_bytes: GB,
+            available_bytes: 1,
+            ..Default::default()
+        }]);
+        assert!(disk.validate().is_err());
+    }
+}
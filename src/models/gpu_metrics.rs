@@ -0,0 +1,55 @@
+// GPU metrics model
+// Per-device utilization, memory, temperature and power for NVIDIA GPUs
+
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+
+/// Metrics for a single GPU device
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default, JsonSchema)]
+pub struct GpuMetrics {
+    /// Device index as reported by the driver
+    pub index: u32,
+    /// Device name, e.g. "NVIDIA A100-SXM4-40GB"
+    pub name: String,
+    /// GPU core utilization (0-100)
+    pub utilization_percentage: f32,
+    /// Device memory in use
+    pub memory_used_bytes: u64,
+    /// Total device memory
+    pub memory_total_bytes: u64,
+    /// Core temperature, if the device reports it
+    pub temperature_celsius: Option<f32>,
+    /// Current power draw, if the device reports it
+    pub power_draw_watts: Option<f32>,
+}
+
+impl GpuMetrics {
+    /// Device memory usage as a percentage (0 when total is unknown)
+    pub fn memory_usage_percentage(&self) -> f32 {
+        if self.memory_total_bytes == 0 {
+            0.0
+        } else {
+            (self.memory_used_bytes as f64 / self.memory_total_bytes as f64 * 100.0) as f32
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_usage_percentage() {
+        let gpu = GpuMetrics {
+            index: 0,
+            name: "Test GPU".to_string(),
+            utilization_percentage: 87.5,
+            memory_used_bytes: 10 * 1024 * 1024 * 1024,
+            memory_total_bytes: 40 * 1024 * 1024 * 1024,
+            temperature_celsius: Some(65.0),
+            power_draw_watts: Some(250.0),
+        };
+        assert_eq!(gpu.memory_usage_percentage(), 25.0);
+        assert_eq!(GpuMetrics::default().memory_usage_percentage(), 0.0);
+    }
+}
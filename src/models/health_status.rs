@@ -1,9 +1,13 @@
 // Health status enumeration for system status reporting
 
+use super::{MemoryMetrics, ServerMetrics};
+use crate::services::metrics_service::ConfigValidationError;
 use serde::{Deserialize, Serialize};
 
 /// Represents the overall health status of the system
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Variants are ordered by severity, so `max` picks the worse of two.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum HealthStatus {
     /// All systems operating normally
     Healthy,
@@ -42,6 +46,158 @@ impl HealthStatus {
     }
 }
 
+/// Usage levels at which metrics turn a status Warning or Critical
+///
+/// Percentages are compared with `>`, so a CPU at exactly the critical
+/// threshold is still only a warning. Available memory is compared with `<`:
+/// a high usage percentage is often just page cache, while little memory
+/// left in absolute terms is what actually hurts.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HealthThresholds {
+    /// CPU usage (percent) above which status is Warning
+    pub cpu_warning: f32,
+    /// CPU usage (percent) above which status is Critical
+    pub cpu_critical: f32,
+    /// Memory usage (percent) above which status is Warning
+    pub memory_warning: f32,
+    /// Memory usage (percent) above which status is Critical
+    pub memory_critical: f32,
+    /// Active connections above which status is Warning
+    pub connections_warning: u32,
+    /// Active connections above which status is Critical
+    pub connections_critical: u32,
+    /// Available memory (bytes) below which status is Warning
+    #[serde(default = "default_memory_available_warning_bytes")]
+    pub memory_available_warning_bytes: u64,
+    /// Available memory (bytes) below which status is Critical
+    #[serde(default = "default_memory_available_critical_bytes")]
+    pub memory_available_critical_bytes: u64,
+}
+
+fn default_memory_available_warning_bytes() -> u64 {
+    512 * 1024 * 1024
+}
+
+fn default_memory_available_critical_bytes() -> u64 {
+    128 * 1024 * 1024
+}
+
+impl Default for HealthThresholds {
+    fn default() -> Self {
+        Self {
+            cpu_warning: 75.0,
+            cpu_critical: 90.0,
+            memory_warning: 75.0,
+            memory_critical: 90.0,
+            connections_warning: 100,
+            connections_critical: 500,
+            memory_available_warning_bytes: default_memory_available_warning_bytes(),
+            memory_available_critical_bytes: default_memory_available_critical_bytes(),
+        }
+    }
+}
+
+impl HealthThresholds {
+    /// Load thresholds from `HEALTH_*` environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        fn read<T: std::str::FromStr>(name: &str, target: &mut T) {
+            if let Ok(value) = std::env::var(name) {
+                match value.trim().parse() {
+                    Ok(parsed) => *target = parsed,
+                    Err(_) => tracing::warn!("Ignoring {}: invalid value {:?}", name, value),
+                }
+            }
+        }
+
+        let mut thresholds = Self::default();
+        read("HEALTH_CPU_WARNING", &mut thresholds.cpu_warning);
+        read("HEALTH_CPU_CRITICAL", &mut thresholds.cpu_critical);
+        read("HEALTH_MEMORY_WARNING", &mut thresholds.memory_warning);
+        read("HEALTH_MEMORY_CRITICAL", &mut thresholds.memory_critical);
+        read("HEALTH_CONNECTIONS_WARNING", &mut thresholds.connections_warning);
+        read("HEALTH_CONNECTIONS_CRITICAL", &mut thresholds.connections_critical);
+        read("HEALTH_MEMORY_AVAILABLE_WARNING_BYTES", &mut thresholds.memory_available_warning_bytes);
+        read("HEALTH_MEMORY_AVAILABLE_CRITICAL_BYTES", &mut thresholds.memory_available_critical_bytes);
+
+        match thresholds.validate() {
+            Ok(()) => thresholds,
+            Err(error) => {
+                tracing::warn!("Ignoring HEALTH_* thresholds: {}", error);
+                Self::default()
+            }
+        }
+    }
+
+    /// Check that every warning threshold trips before its critical one
+    pub fn validate(&self) -> Result<(), ConfigValidationError> {
+        let check = |field, warning: f64, limit, critical: f64| {
+            if warning <= critical {
+                Ok(())
+            } else {
+                Err(ConfigValidationError::Misordered { field, limit })
+            }
+        };
+
+        check("cpu_warning", self.cpu_warning as f64, "cpu_critical", self.cpu_critical as f64)?;
+        check("memory_warning", self.memory_warning as f64, "memory_critical", self.memory_critical as f64)?;
+        check(
+            "connections_warning",
+            self.connections_warning as f64,
+            "connections_critical",
+            self.connections_critical as f64,
+        )?;
+        // Available memory warns below its threshold, so the order is reversed
+        check(
+            "memory_available_critical_bytes",
+            self.memory_available_critical_bytes as f64,
+            "memory_available_warning_bytes",
+            self.memory_available_warning_bytes as f64,
+        )
+    }
+
+    /// Classify usage levels against these thresholds
+    pub fn evaluate(&self, cpu_usage: f32, memory_usage: f32, active_connections: u32) -> HealthStatus {
+        if cpu_usage > self.cpu_critical
+            || memory_usage > self.memory_critical
+            || active_connections > self.connections_critical
+        {
+            HealthStatus::Critical
+        } else if cpu_usage > self.cpu_warning
+            || memory_usage > self.memory_warning
+            || active_connections > self.connections_warning
+        {
+            HealthStatus::Warning
+        } else {
+            HealthStatus::Healthy
+        }
+    }
+
+    /// Classify the absolute amount of available memory
+    ///
+    /// Memory that was never measured (zero total) is not judged.
+    pub fn evaluate_available_memory(&self, memory: &MemoryMetrics) -> HealthStatus {
+        if memory.total_bytes == 0 {
+            HealthStatus::Healthy
+        } else if memory.available_bytes < self.memory_available_critical_bytes {
+            HealthStatus::Critical
+        } else if memory.available_bytes < self.memory_available_warning_bytes {
+            HealthStatus::Warning
+        } else {
+            HealthStatus::Healthy
+        }
+    }
+
+    /// Classify metrics against every threshold, taking the worst result
+    pub fn evaluate_metrics(&self, metrics: &ServerMetrics) -> HealthStatus {
+        self.evaluate(
+            metrics.cpu_usage.per_core_usage(),
+            metrics.memory_usage.usage_percentage,
+            metrics.network_metrics.active_connections,
+        )
+        .max(self.evaluate_available_memory(&metrics.memory_usage))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -76,6 +232,124 @@ mod tests {
         assert_eq!(status, HealthStatus::Critical);
     }
 
+    #[test]
+    fn test_health_thresholds() {
+        let defaults = HealthThresholds::default();
+        assert_eq!(defaults.evaluate(50.0, 50.0, 10), HealthStatus::Healthy);
+        assert_eq!(defaults.evaluate(80.0, 50.0, 10), HealthStatus::Warning);
+        assert_eq!(defaults.evaluate(92.0, 50.0, 10), HealthStatus::Critical);
+        assert_eq!(defaults.evaluate(50.0, 50.0, 200), HealthStatus::Warning);
+        assert_eq!(defaults.evaluate(50.0, 50.0, 501), HealthStatus::Critical);
+
+        // A beefy box that is fine running hot
+        let relaxed = HealthThresholds {
+            cpu_warning: 85.0,
+            cpu_critical: 95.0,
+            ..HealthThresholds::default()
+        };
+        assert_eq!(relaxed.evaluate(80.0, 50.0, 10), HealthStatus::Healthy);
+        assert_eq!(relaxed.evaluate(92.0, 50.0, 10), HealthStatus::Warning);
+        assert_eq!(relaxed.evaluate(96.0, 50.0, 10), HealthStatus::Critical);
+    }
+
+    fn memory(total_bytes: u64, available_bytes: u64) -> MemoryMetrics {
+        MemoryMetrics {
+            total_bytes,
+            used_bytes: total_bytes - available_bytes,
+            available_bytes,
+            usage_percentage: (total_bytes - available_bytes) as f32 / total_bytes as f32 * 100.0,
+            ..Default::default()
+        }
+    }
+
+    fn metrics(memory_usage: MemoryMetrics) -> ServerMetrics {
+        ServerMetrics {
+            timestamp: chrono::Utc::now(),
+            memory_usage,
+            cpu_usage: Default::default(),
+            uptime: std::time::Duration::ZERO,
+            network_metrics: Default::default(),
+            gpu_metrics: Vec::new(),
+            disk_metrics: None,
+            process_metrics: None,
+            sources: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_high_usage_with_plenty_available() {
+        const GIB: u64 = 1024 * 1024 * 1024;
+        let thresholds = HealthThresholds::default();
+
+        // 98% of 256 GiB used still leaves over 5 GiB available
+        let memory = memory(256 * GIB, 256 * GIB / 50);
+        assert_eq!(thresholds.evaluate_available_memory(&memory), HealthStatus::Healthy);
+
+        // The percentage alone still decides the overall status
+        let metrics = metrics(memory);
+        assert_eq!(metrics.memory_usage.usage_percentage.round(), 98.0);
+        assert_eq!(thresholds.evaluate_metrics(&metrics), HealthStatus::Critical);
+
+        let relaxed = HealthThresholds { memory_warning: 99.0, memory_critical: 99.5, ..thresholds };
+        assert_eq!(relaxed.evaluate_metrics(&metrics), HealthStatus::Healthy);
+    }
+
+    #[test]
+    fn test_low_absolute_available_memory() {
+        const MIB: u64 = 1024 * 1024;
+        let thresholds = HealthThresholds::default();
+
+        // Only 60% used, but a small box has little left
+        let low = metrics(memory(1000 * MIB, 400 * MIB));
+        assert_eq!(thresholds.evaluate(10.0, low.memory_usage.usage_percentage, 0), HealthStatus::Healthy);
+        assert_eq!(thresholds.evaluate_metrics(&low), HealthStatus::Warning);
+
+        let very_low = metrics(memory(1000 * MIB, 100 * MIB));
+        assert_eq!(thresholds.evaluate_metrics(&very_low), HealthStatus::Critical);
+
+        let tolerant = HealthThresholds {
+            memory_available_warning_bytes: 64 * MIB,
+            memory_available_critical_bytes: 32 * MIB,
+            memory_warning: 95.0,
+            memory_critical: 99.0,
+            ..thresholds
+        };
+        assert_eq!(tolerant.evaluate_metrics(&very_low), HealthStatus::Healthy);
+
+        // Unmeasured memory is not judged
+        assert_eq!(thresholds.evaluate_available_memory(&MemoryMetrics::default()), HealthStatus::Healthy);
+    }
+
+    #[test]
+    fn test_threshold_order_validation() {
+        assert_eq!(HealthThresholds::default().validate(), Ok(()));
+
+        // Equal thresholds are allowed: the status goes straight to Critical
+        let equal = HealthThresholds { cpu_warning: 90.0, cpu_critical: 90.0, ..HealthThresholds::default() };
+        assert_eq!(equal.validate(), Ok(()));
+
+        let inverted = HealthThresholds { cpu_warning: 95.0, cpu_critical: 90.0, ..HealthThresholds::default() };
+        let error = inverted.validate().unwrap_err();
+        assert_eq!(error.field(), "cpu_warning");
+        assert_eq!(error.to_string(), "cpu_warning must not exceed cpu_critical");
+
+        let inverted = HealthThresholds { connections_warning: 600, ..HealthThresholds::default() };
+        assert_eq!(inverted.validate().unwrap_err().field(), "connections_warning");
+
+        let inverted = HealthThresholds {
+            memory_available_warning_bytes: 1024,
+            memory_available_critical_bytes: 2048,
+            ..HealthThresholds::default()
+        };
+        assert_eq!(inverted.validate().unwrap_err().field(), "memory_available_critical_bytes");
+    }
+
+    #[test]
+    fn test_health_status_ordering() {
+        assert_eq!(HealthStatus::Healthy.max(HealthStatus::Warning), HealthStatus::Warning);
+        assert_eq!(HealthStatus::Critical.max(HealthStatus::Warning), HealthStatus::Critical);
+    }
+
     #[test]
     fn test_health_status_serialization() {
         let status = HealthStatus::Warning;
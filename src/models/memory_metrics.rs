@@ -2,9 +2,10 @@
 // Memory usage information structure
 
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 
 /// Memory usage information
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct MemoryMetrics {
     /// Total system memory in bytes
     pub total_bytes: u64,
@@ -14,6 +15,15 @@ pub struct MemoryMetrics {
     pub available_bytes: u64,
     /// Memory usage as percentage (0-100%)
     pub usage_percentage: f32,
+    /// Total swap space in bytes (0 when swap is disabled)
+    #[serde(default)]
+    pub swap_total_bytes: u64,
+    /// Swap space in use in bytes
+    #[serde(default)]
+    pub swap_used_bytes: u64,
+    /// Swap usage as percentage (0-100%, 0 when swap is disabled)
+    #[serde(default)]
+    pub swap_usage_percentage: f32,
 }
 
 impl Default for MemoryMetrics {
@@ -23,6 +33,9 @@ impl Default for MemoryMetrics {
             used_bytes: 0,
             available_bytes: 0,
             usage_percentage: 0.0,
+            swap_total_bytes: 0,
+            swap_used_bytes: 0,
+            swap_usage_percentage: 0.0,
         }
     }
 }
@@ -34,6 +47,8 @@ pub enum MemoryValidationError {
     MemoryInconsistent { sum: u64, total: u64 },
     #[error("Memory percentage invalid: {percentage}% (must be 0-100%)")]
     InvalidMemoryPercentage { percentage: f32 },
+    #[error("Swap usage inconsistent: used ({used}) > total ({total})")]
+    SwapInconsistent { used: u64, total: u64 },
     #[error("Memory value invalid: {value} (must be >= 0)")]
     #[allow(dead_code)]
     InvalidMemoryValue { value: u64 },
@@ -55,12 +70,34 @@ impl MemoryMetrics {
             used_bytes,
             available_bytes,
             usage_percentage,
+            swap_total_bytes: 0,
+            swap_used_bytes: 0,
+            swap_usage_percentage: 0.0,
         };
 
         metrics.validate()?;
         Ok(metrics)
     }
 
+    /// Attach swap usage, computing the percentage and re-validating
+    pub fn with_swap(mut self, swap_total_bytes: u64, swap_used_bytes: u64) -> Result<Self, MemoryValidationError> {
+        self.swap_total_bytes = swap_total_bytes;
+        self.swap_used_bytes = swap_used_bytes;
+        self.swap_usage_percentage = Self::swap_percentage(swap_total_bytes, swap_used_bytes);
+
+        self.validate()?;
+        Ok(self)
+    }
+
+    /// Swap usage percentage for the given totals (0 when swap is disabled)
+    pub fn swap_percentage(swap_total_bytes: u64, swap_used_bytes: u64) -> f32 {
+        if swap_total_bytes > 0 {
+            ((swap_used_bytes as f64 / swap_total_bytes as f64) * 100.0) as f32
+        } else {
+            0.0
+        }
+    }
+
     /// Validate memory metrics according to business rules
     pub fn validate(&self) -> Result<(), MemoryValidationError> {
         // Memory consistency: used + available ≤ total
@@ -79,16 +116,38 @@ impl MemoryMetrics {
             });
         }
 
+        // Swap consistency: used ≤ total
+        if self.swap_used_bytes > self.swap_total_bytes {
+            return Err(MemoryValidationError::SwapInconsistent {
+                used: self.swap_used_bytes,
+                total: self.swap_total_bytes,
+            });
+        }
+
         Ok(())
     }
 
     /// Get memory usage in human-readable format
+    ///
+    /// Swap usage is appended when the system has swap configured.
     pub fn format_usage(&self) -> String {
-        format!(
+        let usage = format!(
             "{:.1}% ({} / {} GB)",
             self.usage_percentage,
             self.used_bytes / (1024 * 1024 * 1024),
             self.total_bytes / (1024 * 1024 * 1024)
+        );
+
+        if self.swap_total_bytes == 0 {
+            return usage;
+        }
+
+        format!(
+            "{}, swap {:.1}% ({} / {} GB)",
+            usage,
+            self.swap_usage_percentage,
+            self.swap_used_bytes / (1024 * 1024 * 1024),
+            self.swap_total_bytes / (1024 * 1024 * 1024)
         )
     }
 
@@ -107,6 +166,25 @@ impl MemoryMetrics {
     pub fn is_high(&self) -> bool {
         self.usage_percentage > 75.0
     }
+
+    /// Percentage of total memory not accounted for by used + available
+    ///
+    /// A large value usually means the collector missed a category such as
+    /// cache or buffers, even though `validate` accepts the reading.
+    pub fn unaccounted_percentage(&self) -> f32 {
+        if self.total_bytes == 0 {
+            return 0.0;
+        }
+
+        let accounted = self.used_bytes.saturating_add(self.available_bytes);
+        let unaccounted = self.total_bytes.saturating_sub(accounted);
+        ((unaccounted as f64 / self.total_bytes as f64) * 100.0) as f32
+    }
+
+    /// Check whether the unaccounted share of memory exceeds a tolerance (in percent)
+    pub fn has_accounting_discrepancy(&self, tolerance_percent: f32) -> bool {
+        self.unaccounted_percentage() > tolerance_percent
+    }
 }
 
 #[cfg(test)]
@@ -194,6 +272,61 @@ mod tests {
         assert!(critical_metrics.is_critical());
     }
 
+    #[test]
+    fn test_memory_accounting_discrepancy() {
+        let consistent = MemoryMetrics::new(
+            8 * 1024 * 1024 * 1024, // 8GB total
+            3 * 1024 * 1024 * 1024, // 3GB used
+            5 * 1024 * 1024 * 1024, // 5GB available
+        ).unwrap();
+        assert_eq!(consistent.unaccounted_percentage(), 0.0);
+        assert!(!consistent.has_accounting_discrepancy(10.0));
+
+        // 2GB of the 8GB total is missing from used + available
+        let undercounted = MemoryMetrics::new(
+            8 * 1024 * 1024 * 1024, // 8GB total
+            2 * 1024 * 1024 * 1024, // 2GB used
+            4 * 1024 * 1024 * 1024, // 4GB available
+        ).unwrap();
+        assert!((undercounted.unaccounted_percentage() - 25.0).abs() < 0.1);
+        assert!(undercounted.has_accounting_discrepancy(10.0));
+        assert!(!undercounted.has_accounting_discrepancy(30.0));
+
+        // Zero total never reports a discrepancy
+        assert_eq!(MemoryMetrics::default().unaccounted_percentage(), 0.0);
+    }
+
+    #[test]
+    fn test_memory_swap() {
+        let metrics = MemoryMetrics::new(
+            8 * 1024 * 1024 * 1024, // 8GB total
+            3 * 1024 * 1024 * 1024, // 3GB used
+            5 * 1024 * 1024 * 1024, // 5GB available
+        ).unwrap();
+        assert!(!metrics.format_usage().contains("swap"));
+
+        let with_swap = metrics.clone().with_swap(
+            4 * 1024 * 1024 * 1024, // 4GB swap
+            1024 * 1024 * 1024,     // 1GB swap used
+        ).unwrap();
+        assert!((with_swap.swap_usage_percentage - 25.0).abs() < 0.1);
+        assert!(with_swap.format_usage().contains("swap 25.0% (1 / 4 GB)"));
+
+        match metrics.with_swap(1024, 2048).unwrap_err() {
+            MemoryValidationError::SwapInconsistent { used, total } => {
+                assert_eq!(used, 2048);
+                assert_eq!(total, 1024);
+            }
+            _ => panic!("Expected SwapInconsistent error"),
+        }
+
+        // Payloads from before swap was reported still deserialize
+        let legacy: MemoryMetrics = serde_json::from_str(
+            r#"{"total_bytes":100,"used_bytes":50,"available_bytes":50,"usage_percentage":50.0}"#,
+        ).unwrap();
+        assert_eq!(legacy.swap_total_bytes, 0);
+    }
+
     #[test]
     fn test_memory_serialization() {
         let metrics = MemoryMetrics::new(
@@ -0,0 +1,74 @@
+// Counter/gauge classification for exported metrics
+// Exporters use this table so cumulative fields get counter semantics and levels stay gauges
+
+use serde::{Deserialize, Serialize};
+
+/// How an exported value behaves over time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricKind {
+    /// Monotonically increasing total; only its rate is meaningful
+    Counter,
+    /// Point-in-time level that can go up or down
+    Gauge,
+}
+
+/// Cumulative fields - everything else is a gauge
+const COUNTER_FIELDS: &[&str] = &[
+    "bytes_sent",
+    "bytes_received",
+    "packets_sent",
+    "packets_received",
+    "total_requests",
+    "cache_hits",
+    "cache_misses",
+    "evictions",
+    "background_refreshes",
+    "failed_refreshes",
+    "total_collections",
+    "successful_collections",
+    "failed_collections",
+];
+
+impl MetricKind {
+    /// Classify a metric by its field name
+    pub fn of(field: &str) -> Self {
+        if COUNTER_FIELDS.contains(&field) {
+            Self::Counter
+        } else {
+            Self::Gauge
+        }
+    }
+
+    /// Exported metric name, with the `_total` suffix counters require
+    pub fn export_name(prefix: &str, field: &str) -> String {
+        match Self::of(field) {
+            Self::Counter => format!("{}_{}_total", prefix, field),
+            Self::Gauge => format!("{}_{}", prefix, field),
+        }
+    }
+
+    /// Type name used in the Prometheus `# TYPE` line
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Counter => "counter",
+            Self::Gauge => "gauge",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counters_and_gauges() {
+        assert_eq!(MetricKind::of("bytes_sent"), MetricKind::Counter);
+        assert_eq!(MetricKind::export_name("axum_sse_network", "bytes_sent"), "axum_sse_network_bytes_sent_total");
+        assert_eq!(MetricKind::of("bytes_sent").as_str(), "counter");
+
+        assert_eq!(MetricKind::of("usage_percentage"), MetricKind::Gauge);
+        assert_eq!(MetricKind::export_name("axum_sse_cpu", "usage_percentage"), "axum_sse_cpu_usage_percentage");
+        assert_eq!(MetricKind::of("active_connections"), MetricKind::Gauge);
+    }
+}
@@ -40,6 +40,12 @@ pub enum MetricsCollectionError {
     #[error("Metrics collection service not initialized")]
     ServiceNotInitialized,
     
+    #[error("Skipped {collector} collection: {available_bytes} bytes available, below the {floor_bytes} byte floor")]
+    SkippedDueToMemoryPressure { collector: String, available_bytes: u64, floor_bytes: u64 },
+    
+    #[error("Serving cached metrics from {age_seconds}s ago after collection failed: {cause}")]
+    StaleData { age_seconds: u64, cause: String },
+    
     #[error("Internal error: {message}")]
     Internal { message: String },
 }
@@ -115,12 +121,43 @@ impl MetricsCollectionError {
         }
     }
 
+    /// Create an error for a collector skipped because available memory is below the floor
+    pub fn skipped_due_to_memory_pressure(collector: impl Into<String>, available_bytes: u64, floor_bytes: u64) -> Self {
+        Self::SkippedDueToMemoryPressure {
+            collector: collector.into(),
+            available_bytes,
+            floor_bytes,
+        }
+    }
+
+    /// Create an error marking data as a stale cache entry served in place of a failed collection
+    pub fn stale_data(age_seconds: u64, cause: impl Into<String>) -> Self {
+        Self::StaleData {
+            age_seconds,
+            cause: cause.into(),
+        }
+    }
+
     /// Combine multiple errors into a single error
     pub fn multiple(errors: Vec<MetricsCollectionError>) -> Self {
         let count = errors.len();
         Self::MultipleErrors { count, errors }
     }
 
+    /// Metric categories this error left at their defaults, if any
+    pub fn missing_categories(&self) -> Vec<String> {
+        match self {
+            Self::NetworkError { .. } => vec!["network".to_string()],
+            Self::CpuError { .. } => vec!["cpu".to_string()],
+            Self::MemoryError { .. } => vec!["memory".to_string()],
+            Self::SkippedDueToMemoryPressure { collector, .. } => vec![collector.clone()],
+            Self::MultipleErrors { errors, .. } => {
+                errors.iter().flat_map(|e| e.missing_categories()).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
     /// Check if error is recoverable (temporary)
     pub fn is_recoverable(&self) -> bool {
         match self {
@@ -129,6 +166,8 @@ impl MetricsCollectionError {
             Self::OutOfMemory => true,
             Self::NetworkError { .. } => true,
             Self::Internal { .. } => true,
+            Self::SkippedDueToMemoryPressure { .. } => true,
+            Self::StaleData { .. } => true,
             
             // These are likely permanent until system changes
             Self::SystemUnavailable { .. } => false,
@@ -153,6 +192,8 @@ impl MetricsCollectionError {
             Self::Timeout { .. } => ErrorSeverity::Warning,
             Self::NetworkError { .. } => ErrorSeverity::Warning,
             Self::Internal { .. } => ErrorSeverity::Warning,
+            Self::SkippedDueToMemoryPressure { .. } => ErrorSeverity::Warning,
+            Self::StaleData { .. } => ErrorSeverity::Warning,
             
             Self::SystemUnavailable { .. } => ErrorSeverity::Error,
             Self::PermissionDenied { .. } => ErrorSeverity::Error,
@@ -172,6 +213,28 @@ impl MetricsCollectionError {
         }
     }
 
+    /// Get the stable machine-readable error code
+    ///
+    /// Unlike the `Display` message, codes never change once published, so
+    /// clients can branch on them safely.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::SystemUnavailable { .. } => "METRICS_SYSTEM_UNAVAILABLE",
+            Self::PermissionDenied { .. } => "METRICS_PERMISSION_DENIED",
+            Self::ParseError { .. } => "METRICS_PARSE_ERROR",
+            Self::Timeout { .. } => "METRICS_TIMEOUT",
+            Self::OutOfMemory => "METRICS_OUT_OF_MEMORY",
+            Self::NetworkError { .. } => "METRICS_NETWORK_ERROR",
+            Self::CpuError { .. } => "METRICS_CPU_ERROR",
+            Self::MemoryError { .. } => "METRICS_MEMORY_ERROR",
+            Self::MultipleErrors { .. } => "METRICS_MULTIPLE_ERRORS",
+            Self::ServiceNotInitialized => "METRICS_SERVICE_NOT_INITIALIZED",
+            Self::Internal { .. } => "METRICS_INTERNAL_ERROR",
+            Self::SkippedDueToMemoryPressure { .. } => "METRICS_SKIPPED_DUE_TO_MEMORY_PRESSURE",
+            Self::StaleData { .. } => "METRICS_STALE_DATA",
+        }
+    }
+
     /// Get suggested retry delay in milliseconds
     pub fn retry_delay_ms(&self) -> Option<u64> {
         match self {
@@ -179,6 +242,8 @@ impl MetricsCollectionError {
             Self::OutOfMemory => Some(5000),         // Retry after 5 seconds
             Self::NetworkError { .. } => Some(2000), // Retry after 2 seconds
             Self::Internal { .. } => Some(1000),     // Retry after 1 second
+            Self::SkippedDueToMemoryPressure { .. } => Some(5000), // Give memory time to free up
+            Self::StaleData { .. } => Some(1000),
             
             // No retry for these errors
             Self::SystemUnavailable { .. } => None,
@@ -259,6 +324,45 @@ impl<T> MetricsResponse<T> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_error_codes() {
+        let cases = vec![
+            (MetricsCollectionError::system_unavailable("x"), "METRICS_SYSTEM_UNAVAILABLE"),
+            (MetricsCollectionError::permission_denied("x"), "METRICS_PERMISSION_DENIED"),
+            (MetricsCollectionError::parse_error("x"), "METRICS_PARSE_ERROR"),
+            (MetricsCollectionError::timeout(100), "METRICS_TIMEOUT"),
+            (MetricsCollectionError::OutOfMemory, "METRICS_OUT_OF_MEMORY"),
+            (MetricsCollectionError::network_error("eth0", "x"), "METRICS_NETWORK_ERROR"),
+            (MetricsCollectionError::cpu_error("x"), "METRICS_CPU_ERROR"),
+            (MetricsCollectionError::memory_error("x"), "METRICS_MEMORY_ERROR"),
+            (MetricsCollectionError::multiple(vec![]), "METRICS_MULTIPLE_ERRORS"),
+            (MetricsCollectionError::ServiceNotInitialized, "METRICS_SERVICE_NOT_INITIALIZED"),
+            (MetricsCollectionError::internal("x"), "METRICS_INTERNAL_ERROR"),
+            (MetricsCollectionError::skipped_due_to_memory_pressure("network", 1, 2), "METRICS_SKIPPED_DUE_TO_MEMORY_PRESSURE"),
+            (MetricsCollectionError::stale_data(3, "x"), "METRICS_STALE_DATA"),
+        ];
+
+        for (error, expected) in cases {
+            assert_eq!(error.code(), expected, "unexpected code for {:?}", error);
+        }
+    }
+
+    #[test]
+    fn test_error_code_independent_of_message() {
+        let first = MetricsCollectionError::timeout(100);
+        let second = MetricsCollectionError::timeout(5000);
+        assert_ne!(first.to_string(), second.to_string());
+        assert_eq!(first.code(), second.code());
+
+        let first = MetricsCollectionError::multiple(vec![MetricsCollectionError::OutOfMemory]);
+        let second = MetricsCollectionError::multiple(vec![
+            MetricsCollectionError::timeout(100),
+            MetricsCollectionError::cpu_error("no cpus"),
+        ]);
+        assert_ne!(first.to_string(), second.to_string());
+        assert_eq!(first.code(), second.code());
+    }
+
     #[test]
     fn test_error_creation() {
         let error = MetricsCollectionError::system_unavailable("sysinfo crate failed");
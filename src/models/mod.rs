@@ -1,21 +1,35 @@
 // Re-export all models
 pub mod time_event;
+pub mod broadcast_event;
 pub mod connection_state;
+pub mod counter_delta;
 pub mod cpu_metrics;
+pub mod disk_metrics;
+pub mod gpu_metrics;
 pub mod memory_metrics;
+pub mod metric_kind;
 pub mod metrics_errors;
 pub mod network_metrics;
+pub mod process_metrics;
+pub mod sensor_metrics;
 pub mod server_metrics;
 pub mod status_data;
 pub mod health_status;
 pub mod os_info;
 
-pub use time_event::TimeEvent;
-pub use cpu_metrics::CpuMetrics;
+pub use time_event::{TimeEvent, TimeFormat};
+pub use broadcast_event::{BroadcastEvent, BroadcastValidationError};
+pub use counter_delta::CounterDelta;
+pub use cpu_metrics::{CpuMetrics, CpuScale};
+pub use disk_metrics::{DiskMetrics, MountMetrics};
+pub use gpu_metrics::GpuMetrics;
 pub use memory_metrics::MemoryMetrics;
+pub use metric_kind::MetricKind;
 pub use metrics_errors::{MetricsCollectionError, MetricsResponse};
 pub use network_metrics::NetworkMetrics;
-pub use server_metrics::{ServerMetrics, MetricsValidationError};
-pub use status_data::{StatusData, ServerInfo};
-pub use health_status::HealthStatus;
+pub use process_metrics::{ProcessInfo, ProcessMetrics};
+pub use sensor_metrics::{FanReading, SensorMetrics, TemperatureReading};
+pub use server_metrics::{ServerMetrics, MetricSource, MetricsValidationError};
+pub use status_data::{DataQuality, StatusData, ServerInfo};
+pub use health_status::{HealthStatus, HealthThresholds};
 pub use os_info::{OsInfo, OsInfoValidationError};
\ No newline at end of file
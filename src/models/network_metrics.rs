@@ -2,10 +2,11 @@
 // Network activity and connection statistics
 
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 use thiserror::Error;
 
 /// Network activity statistics
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct NetworkMetrics {
     /// Total bytes transmitted
     pub bytes_sent: u64,
@@ -17,6 +18,15 @@ pub struct NetworkMetrics {
     pub packets_received: u64,
     /// Current active network connections
     pub active_connections: u32,
+    /// Set when a cumulative counter went backwards since the previous sample
+    #[serde(default)]
+    pub counter_reset: bool,
+    /// Transmit throughput since the previous sample (0 on the first sample or after a reset)
+    #[serde(default)]
+    pub rate_bytes_sent_per_sec: f64,
+    /// Receive throughput since the previous sample (0 on the first sample or after a reset)
+    #[serde(default)]
+    pub rate_bytes_received_per_sec: f64,
 }
 
 impl Default for NetworkMetrics {
@@ -27,6 +37,9 @@ impl Default for NetworkMetrics {
             packets_sent: 0,
             packets_received: 0,
             active_connections: 0,
+            counter_reset: false,
+            rate_bytes_sent_per_sec: 0.0,
+            rate_bytes_received_per_sec: 0.0,
         }
     }
 }
@@ -57,6 +70,9 @@ impl NetworkMetrics {
             packets_sent,
             packets_received,
             active_connections,
+            counter_reset: false,
+            rate_bytes_sent_per_sec: 0.0,
+            rate_bytes_received_per_sec: 0.0,
         };
 
         metrics.validate()?;
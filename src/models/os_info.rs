@@ -2,11 +2,12 @@
 // Represents comprehensive OS details for system identification and troubleshooting
 
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 use std::fmt;
 
 /// Operating system information structure
 /// Contains static OS details that don't change during runtime
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 pub struct OsInfo {
     /// Operating system name (e.g., "Linux", "macOS", "Windows")
     pub name: String,
@@ -0,0 +1,87 @@
+// Process metrics model
+// Process counts and the heaviest processes by CPU
+
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+
+/// Process counts plus the processes using the most CPU
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default, JsonSchema)]
+pub struct ProcessMetrics {
+    /// Processes visible to the server
+    pub total_processes: u32,
+    /// Processes currently in the running state
+    pub running_processes: u32,
+    /// Heaviest processes by CPU, then memory, highest first
+    #[serde(default)]
+    pub top: Vec<ProcessInfo>,
+}
+
+/// Resource usage of a single process
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default, JsonSchema)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub name: String,
+    /// CPU usage since the previous refresh (can exceed 100% on multi-core hosts)
+    pub cpu_percentage: f32,
+    /// Resident memory in bytes
+    pub memory_bytes: u64,
+}
+
+impl ProcessMetrics {
+    /// Summarize `processes`, keeping the `max_top` heaviest in `top`
+    pub fn from_processes(processes: Vec<ProcessInfo>, running_processes: u32, max_top: usize) -> Self {
+        let total_processes = processes.len() as u32;
+
+        let mut top = processes;
+        top.sort_by(|a, b| {
+            b.cpu_percentage
+                .total_cmp(&a.cpu_percentage)
+                .then(b.memory_bytes.cmp(&a.memory_bytes))
+        });
+        top.truncate(max_top);
+
+        Self {
+            total_processes,
+            running_processes,
+            top,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(pid: u32, cpu_percentage: f32, memory_bytes: u64) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            name: format!("proc-{}", pid),
+            cpu_percentage,
+            memory_bytes,
+        }
+    }
+
+    #[test]
+    fn test_top_processes_by_cpu_then_memory() {
+        let processes = vec![
+            process(1, 0.5, 100),
+            process(2, 80.0, 10),
+            process(3, 0.5, 900),
+            process(4, 12.0, 50),
+        ];
+
+        let metrics = ProcessMetrics::from_processes(processes, 2, 3);
+        assert_eq!(metrics.total_processes, 4);
+        assert_eq!(metrics.running_processes, 2);
+
+        let pids: Vec<u32> = metrics.top.iter().map(|p| p.pid).collect();
+        assert_eq!(pids, vec![2, 4, 3]);
+    }
+
+    #[test]
+    fn test_zero_top_processes() {
+        let metrics = ProcessMetrics::from_processes(vec![process(1, 1.0, 1)], 1, 0);
+        assert_eq!(metrics.total_processes, 1);
+        assert!(metrics.top.is_empty());
+    }
+}
@@ -0,0 +1,83 @@
+// Sensor metrics model
+// Labelled temperature and fan readings from hardware monitoring chips
+
+use serde::{Deserialize, Serialize};
+
+/// A single temperature sensor reading
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TemperatureReading {
+    /// Sensor label as reported by the platform, e.g. "nvme Composite"
+    pub label: String,
+    /// Current temperature
+    pub temperature_celsius: f32,
+    /// Highest temperature seen since the sensor was first read
+    pub max_celsius: Option<f32>,
+    /// Threshold at which the hardware considers the component critical
+    pub critical_celsius: Option<f32>,
+}
+
+/// A single fan speed reading
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FanReading {
+    /// Fan label, e.g. "nct6775/fan2" when the chip gives no label of its own
+    pub label: String,
+    /// Current speed in revolutions per minute
+    pub rpm: u32,
+}
+
+/// Thermal and fan sensors beyond the CPU
+///
+/// Both lists are empty, not an error, on hosts without readable sensors.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SensorMetrics {
+    /// Every temperature sensor the platform exposes
+    pub temperatures: Vec<TemperatureReading>,
+    /// Fan speeds from hwmon (Linux only)
+    pub fans: Vec<FanReading>,
+}
+
+impl SensorMetrics {
+    /// Whether no sensors were found
+    pub fn is_empty(&self) -> bool {
+        self.temperatures.is_empty() && self.fans.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sensor_metrics_serialization() {
+        let sensors = SensorMetrics {
+            temperatures: vec![TemperatureReading {
+                label: "nvme Composite".to_string(),
+                temperature_celsius: 41.9,
+                max_celsius: Some(44.0),
+                critical_celsius: Some(84.8),
+            }],
+            fans: vec![FanReading {
+                label: "nct6775/fan2".to_string(),
+                rpm: 1180,
+            }],
+        };
+
+        let json = serde_json::to_value(&sensors).unwrap();
+        assert_eq!(json["temperatures"][0]["label"], "nvme Composite");
+        assert_eq!(json["fans"][0]["rpm"], 1180);
+
+        let roundtrip: SensorMetrics = serde_json::from_value(json).unwrap();
+        assert_eq!(roundtrip, sensors);
+        assert!(!roundtrip.is_empty());
+    }
+
+    #[test]
+    fn test_empty_sensor_metrics() {
+        let sensors = SensorMetrics::default();
+        assert!(sensors.is_empty());
+        assert_eq!(
+            serde_json::to_value(&sensors).unwrap(),
+            serde_json::json!({ "temperatures": [], "fans": [] })
+        );
+    }
+}
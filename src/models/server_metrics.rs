@@ -3,11 +3,13 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use std::collections::BTreeMap;
 use std::time::Duration;
-use super::{MemoryMetrics, CpuMetrics, NetworkMetrics};
+use super::{MemoryMetrics, CpuMetrics, DiskMetrics, GpuMetrics, NetworkMetrics, ProcessMetrics};
 
 /// Represents real-time system performance data
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ServerMetrics {
     /// When metrics were collected
     pub timestamp: DateTime<Utc>,
@@ -17,9 +19,36 @@ pub struct ServerMetrics {
     pub cpu_usage: CpuMetrics,
     /// Time since system/service start
     #[serde(with = "duration_serde")]
+    #[schemars(with = "Duration")]
     pub uptime: Duration,
     /// Network activity data
     pub network_metrics: NetworkMetrics,
+    /// Per-device GPU metrics (empty unless GPU collection is enabled)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gpu_metrics: Vec<GpuMetrics>,
+    /// Storage usage (absent unless disk collection is enabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_metrics: Option<DiskMetrics>,
+    /// Process counts and top processes (absent unless process collection is enabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub process_metrics: Option<ProcessMetrics>,
+    /// Backend that produced each metric category, reported in response metadata
+    #[serde(skip)]
+    pub sources: BTreeMap<String, MetricSource>,
+}
+
+/// Where a metric category's values came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricSource {
+    /// Read through the sysinfo crate
+    Sysinfo,
+    /// Parsed directly from `/proc`
+    Proc,
+    /// Served from a previously collected sample
+    Cache,
+    /// Collection failed or was disabled, zeroed defaults were used
+    Default,
 }
 
 /// Validation errors for metrics
@@ -31,24 +60,118 @@ pub enum MetricsValidationError {
     Cpu(#[from] super::cpu_metrics::CpuValidationError),
     #[error("Network validation failed: {0}")]
     Network(#[from] super::network_metrics::NetworkValidationError),
+    #[error("Disk validation failed: {0}")]
+    Disk(#[from] super::disk_metrics::DiskValidationError),
 }
 
 impl ServerMetrics {
+    /// Zeroed metrics stamped now, for fallback events when nothing was collected
+    pub fn empty() -> Self {
+        Self {
+            timestamp: Utc::now(),
+            memory_usage: MemoryMetrics::default(),
+            cpu_usage: CpuMetrics::default(),
+            uptime: Duration::ZERO,
+            network_metrics: NetworkMetrics::default(),
+            sources: BTreeMap::new(),
+            gpu_metrics: Vec::new(),
+            disk_metrics: None,
+            process_metrics: None,
+        }
+    }
+
     /// Validate server metrics according to business rules
     pub fn validate(&self) -> Result<(), MetricsValidationError> {
         // Validate sub-components using their own validation
         self.memory_usage.validate()?;
         self.cpu_usage.validate()?;
         self.network_metrics.validate()?;
+        if let Some(disk) = &self.disk_metrics {
+            disk.validate()?;
+        }
 
         Ok(())
     }
 
     /// Check if timestamp is stale (for warnings, not blocking validation)
     pub fn is_timestamp_stale(&self) -> Option<i64> {
+        self.is_timestamp_stale_after(DEFAULT_STALE_THRESHOLD_SECONDS)
+    }
+
+    /// Check staleness against the collection interval in use
+    ///
+    /// Data up to two intervals old is expected when polling slowly, so the
+    /// threshold is `2 × interval`, never lower than the 10-second default.
+    pub fn is_timestamp_stale_for_interval(&self, collection_interval_seconds: u32) -> Option<i64> {
+        let threshold = (i64::from(collection_interval_seconds) * 2).max(DEFAULT_STALE_THRESHOLD_SECONDS);
+        self.is_timestamp_stale_after(threshold)
+    }
+
+    /// Whether any metric moved since `previous` by more than `epsilon`
+    ///
+    /// Every collected category is compared. Percentages, load averages and
+    /// temperatures use the epsilon; counters, connection and process counts
+    /// are exact, and a category appearing or disappearing is a change.
+    /// Timestamps and uptime always advance, so they're ignored.
+    pub fn differs_from(&self, previous: &ServerMetrics, epsilon: f32) -> bool {
+        let moved = |current: f32, before: f32| (current - before).abs() > epsilon;
+        let moved_all = |current: &[f32], before: &[f32]| {
+            current.len() != before.len() || current.iter().zip(before).any(|(now, then)| moved(*now, *then))
+        };
+        let (cpu, previous_cpu) = (&self.cpu_usage, &previous.cpu_usage);
+        let (load, previous_load) = (&cpu.load_average, &previous_cpu.load_average);
+        let (memory, previous_memory) = (&self.memory_usage, &previous.memory_usage);
+
+        let cpu_moved = moved(cpu.usage_percentage, previous_cpu.usage_percentage)
+            || moved_all(
+                &[load.one_minute, load.five_minute, load.fifteen_minute],
+                &[previous_load.one_minute, previous_load.five_minute, previous_load.fifteen_minute],
+            )
+            || moved_all(&cpu.core_usage_percentages, &previous_cpu.core_usage_percentages)
+            || match (cpu.temperature_celsius, previous_cpu.temperature_celsius) {
+                (Some(now), Some(then)) => moved(now, then),
+                (now, then) => now.is_some() != then.is_some(),
+            };
+        let memory_moved = moved(memory.usage_percentage, previous_memory.usage_percentage)
+            || moved(memory.swap_usage_percentage, previous_memory.swap_usage_percentage);
+        let network_moved = self.network_metrics.bytes_sent != previous.network_metrics.bytes_sent
+            || self.network_metrics.bytes_received != previous.network_metrics.bytes_received
+            || self.network_metrics.active_connections != previous.network_metrics.active_connections;
+        let gpu_moved = self.gpu_metrics.len() != previous.gpu_metrics.len()
+            || self.gpu_metrics.iter().zip(&previous.gpu_metrics).any(|(gpu, before)| {
+                moved(gpu.utilization_percentage, before.utilization_percentage)
+                    || moved(gpu.memory_usage_percentage(), before.memory_usage_percentage())
+            });
+        let disk_moved = match (&self.disk_metrics, &previous.disk_metrics) {
+            (Some(disk), Some(before)) => {
+                moved(disk.usage_percentage, before.usage_percentage)
+                    || disk.mounts.len() != before.mounts.len()
+                    || disk.mounts.iter().zip(&before.mounts).any(|(mount, then)| {
+                        mount.mount_point != then.mount_point || moved(mount.usage_percentage, then.usage_percentage)
+                    })
+            }
+            (disk, before) => disk.is_some() != before.is_some(),
+        };
+        let process_moved = match (&self.process_metrics, &previous.process_metrics) {
+            (Some(processes), Some(before)) => {
+                processes.total_processes != before.total_processes
+                    || processes.running_processes != before.running_processes
+                    || processes.top.len() != before.top.len()
+                    || processes.top.iter().zip(&before.top).any(|(process, then)| {
+                        process.pid != then.pid || moved(process.cpu_percentage, then.cpu_percentage)
+                    })
+            }
+            (processes, before) => processes.is_some() != before.is_some(),
+        };
+
+        cpu_moved || memory_moved || network_moved || gpu_moved || disk_moved || process_moved
+    }
+
+    /// Age in seconds if the timestamp is more than `threshold_seconds` old
+    pub fn is_timestamp_stale_after(&self, threshold_seconds: i64) -> Option<i64> {
         let now = Utc::now();
         let age = now.signed_duration_since(self.timestamp);
-        if age.num_seconds() > 10 {
+        if age.num_seconds() > threshold_seconds {
             Some(age.num_seconds())
         } else {
             None
@@ -56,6 +179,9 @@ impl ServerMetrics {
     }
 }
 
+/// Age in seconds beyond which a timestamp is reported stale
+pub const DEFAULT_STALE_THRESHOLD_SECONDS: i64 = 10;
+
 // Duration serialization module
 mod duration_serde {
     use serde::{Deserializer, Serializer};
@@ -125,6 +251,28 @@ mod duration_serde {
     }
 }
 
+/// Serialize a `Duration` as whole seconds, for JS clients that expect a number
+///
+/// Use with `#[serde(with = "duration_seconds")]`; sub-second precision is dropped.
+pub mod duration_seconds {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u64(duration.as_secs())
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        u64::deserialize(deserializer).map(Duration::from_secs)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -137,6 +285,9 @@ mod tests {
             used_bytes: 4_000_000_000,
             available_bytes: 4_000_000_000,
             usage_percentage: 50.0,
+            swap_total_bytes: 0,
+            swap_used_bytes: 0,
+            swap_usage_percentage: 0.0,
         }
     }
 
@@ -145,11 +296,14 @@ mod tests {
         CpuMetrics {
             usage_percentage: 25.0,
             core_count: 4,
+            scale: crate::models::CpuScale::Average,
             load_average: LoadAverage {
                 one_minute: 1.0,
                 five_minute: 1.2,
                 fifteen_minute: 1.1,
             },
+            core_usage_percentages: Vec::new(),
+            temperature_celsius: None,
         }
     }
 
@@ -160,9 +314,36 @@ mod tests {
             packets_sent: 1000,
             packets_received: 2000,
             active_connections: 10,
+            counter_reset: false,
+            rate_bytes_sent_per_sec: 0.0,
+            rate_bytes_received_per_sec: 0.0,
         }
     }
 
+    #[test]
+    fn test_empty_metrics_are_valid_and_zeroed() {
+        let metrics = ServerMetrics::empty();
+        assert!(metrics.validate().is_ok());
+        assert_eq!(metrics.memory_usage.total_bytes, 0);
+        assert_eq!(metrics.cpu_usage.usage_percentage, 0.0);
+        assert_eq!(metrics.uptime, Duration::ZERO);
+        assert!(metrics.sources.is_empty());
+    }
+
+    #[test]
+    fn test_duration_seconds_serde() {
+        #[derive(Serialize, Deserialize)]
+        struct Wrapper {
+            #[serde(with = "duration_seconds")]
+            uptime: Duration,
+        }
+
+        let json = serde_json::to_string(&Wrapper { uptime: Duration::new(3661, 900_000_000) }).unwrap();
+        assert_eq!(json, r#"{"uptime":3661}"#);
+        let wrapper: Wrapper = serde_json::from_str(&json).unwrap();
+        assert_eq!(wrapper.uptime, Duration::from_secs(3661));
+    }
+
     #[test]
     fn test_server_metrics_creation() {
         let metrics = ServerMetrics {
@@ -171,6 +352,10 @@ mod tests {
             cpu_usage: create_test_cpu_metrics(),
             uptime: Duration::from_secs(3600),
             network_metrics: create_test_network_metrics(),
+            sources: Default::default(),
+            gpu_metrics: Vec::new(),
+            disk_metrics: None,
+            process_metrics: None,
         };
 
         assert!(metrics.timestamp <= Utc::now());
@@ -179,6 +364,80 @@ mod tests {
         assert_eq!(metrics.uptime, Duration::from_secs(3600));
     }
 
+    #[test]
+    fn test_differs_from_respects_epsilon() {
+        let previous = ServerMetrics {
+            timestamp: Utc::now(),
+            memory_usage: create_test_memory_metrics(),
+            cpu_usage: create_test_cpu_metrics(),
+            uptime: Duration::from_secs(3600),
+            network_metrics: create_test_network_metrics(),
+            sources: Default::default(),
+            gpu_metrics: Vec::new(),
+            disk_metrics: None,
+            process_metrics: None,
+        };
+
+        // Only timestamp and uptime moved
+        let mut current = previous.clone();
+        current.timestamp = Utc::now() + chrono::Duration::seconds(5);
+        current.uptime = Duration::from_secs(3605);
+        assert!(!current.differs_from(&previous, 0.5));
+
+        current.cpu_usage.usage_percentage += 0.4;
+        assert!(!current.differs_from(&previous, 0.5));
+        current.cpu_usage.usage_percentage += 0.4;
+        assert!(current.differs_from(&previous, 0.5));
+
+        let mut current = previous.clone();
+        current.network_metrics.bytes_received += 1;
+        assert!(current.differs_from(&previous, 0.5));
+    }
+
+    #[test]
+    fn test_differs_from_compares_every_category() {
+        let previous = ServerMetrics {
+            disk_metrics: Some(DiskMetrics {
+                total_bytes: 1000,
+                used_bytes: 500,
+                available_bytes: 500,
+                usage_percentage: 50.0,
+                mounts: Vec::new(),
+            }),
+            ..ServerMetrics::empty()
+        };
+
+        // CPU and memory stay flat while the disk fills
+        let mut current = previous.clone();
+        if let Some(disk) = current.disk_metrics.as_mut() {
+            disk.used_bytes = 900;
+            disk.usage_percentage = 90.0;
+        }
+        assert!(current.differs_from(&previous, 0.5));
+
+        let mut current = previous.clone();
+        current.memory_usage.swap_usage_percentage = 40.0;
+        assert!(current.differs_from(&previous, 0.5));
+
+        let mut current = previous.clone();
+        current.cpu_usage.load_average.fifteen_minute = 3.0;
+        assert!(current.differs_from(&previous, 0.5));
+
+        let mut current = previous.clone();
+        current.cpu_usage.temperature_celsius = Some(70.0);
+        assert!(current.differs_from(&previous, 0.5));
+
+        let mut current = previous.clone();
+        current.process_metrics = Some(crate::models::ProcessMetrics {
+            total_processes: 10,
+            running_processes: 1,
+            top: Vec::new(),
+        });
+        assert!(current.differs_from(&previous, 0.5));
+
+        assert!(!previous.clone().differs_from(&previous, 0.5));
+    }
+
     #[test]
     fn test_server_metrics_validation_success() {
         let metrics = ServerMetrics {
@@ -187,6 +446,10 @@ mod tests {
             cpu_usage: create_test_cpu_metrics(),
             uptime: Duration::from_secs(3600),
             network_metrics: create_test_network_metrics(),
+            sources: Default::default(),
+            gpu_metrics: Vec::new(),
+            disk_metrics: None,
+            process_metrics: None,
         };
 
         assert!(metrics.validate().is_ok());
@@ -201,6 +464,10 @@ mod tests {
             cpu_usage: create_test_cpu_metrics(),
             uptime: Duration::from_secs(3600),
             network_metrics: create_test_network_metrics(),
+            sources: Default::default(),
+            gpu_metrics: Vec::new(),
+            disk_metrics: None,
+            process_metrics: None,
         };
 
         // Validation should pass (no timestamp check in main validation)
@@ -213,6 +480,26 @@ mod tests {
         assert!(stale_age.unwrap() >= 15);
     }
 
+    #[test]
+    fn test_stale_threshold_follows_collection_interval() {
+        let metrics = ServerMetrics {
+            timestamp: Utc::now() - chrono::Duration::seconds(15),
+            memory_usage: create_test_memory_metrics(),
+            cpu_usage: create_test_cpu_metrics(),
+            uptime: Duration::from_secs(3600),
+            network_metrics: create_test_network_metrics(),
+            sources: Default::default(),
+            gpu_metrics: Vec::new(),
+            disk_metrics: None,
+            process_metrics: None,
+        };
+
+        // 15 seconds is within two 30-second intervals
+        assert!(metrics.is_timestamp_stale_for_interval(30).is_none());
+        // Short intervals keep the 10-second floor
+        assert!(metrics.is_timestamp_stale_for_interval(5).is_some());
+    }
+
     #[test]
     fn test_duration_serialization() {
         let metrics = ServerMetrics {
@@ -221,6 +508,10 @@ mod tests {
             cpu_usage: create_test_cpu_metrics(),
             uptime: Duration::from_secs(3661), // 1 hour, 1 minute, 1 second
             network_metrics: create_test_network_metrics(),
+            sources: Default::default(),
+            gpu_metrics: Vec::new(),
+            disk_metrics: None,
+            process_metrics: None,
         };
 
         let json = serde_json::to_string(&metrics).unwrap();
@@ -238,6 +529,10 @@ mod tests {
             cpu_usage: create_test_cpu_metrics(),
             uptime: Duration::from_secs(3600),
             network_metrics: create_test_network_metrics(),
+            sources: Default::default(),
+            gpu_metrics: Vec::new(),
+            disk_metrics: None,
+            process_metrics: None,
         };
 
         let debug_str = format!("{:?}", metrics);
@@ -254,6 +549,10 @@ mod tests {
             cpu_usage: create_test_cpu_metrics(),
             uptime: Duration::from_secs(3600),
             network_metrics: create_test_network_metrics(),
+            sources: Default::default(),
+            gpu_metrics: Vec::new(),
+            disk_metrics: None,
+            process_metrics: None,
         };
 
         let cloned = metrics.clone();
@@ -3,11 +3,12 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 
-use super::{ServerMetrics, OsInfoValidationError, OsInfo};
+use super::{MetricsCollectionError, ServerMetrics, OsInfoValidationError, OsInfo};
 
 /// Complete data structure for API consumption
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct StatusData {
     /// Current system metrics
     pub server_metrics: ServerMetrics,
@@ -15,10 +16,50 @@ pub struct StatusData {
     pub collection_interval_seconds: u32,
     /// Static server information
     pub server_info: ServerInfo,
+    /// Whether every metric category was collected
+    #[serde(default)]
+    pub data_quality: DataQuality,
+}
+
+/// Completeness of the metrics in a [`StatusData`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum DataQuality {
+    #[default]
+    Complete,
+    /// Collection partly failed; `missing` categories hold default values
+    Partial { missing: Vec<String> },
+}
+
+impl DataQuality {
+    /// Quality of data returned alongside `errors`
+    pub fn from_errors(errors: &[MetricsCollectionError]) -> Self {
+        if errors.is_empty() {
+            return Self::Complete;
+        }
+
+        let mut missing: Vec<String> = errors.iter().flat_map(|e| e.missing_categories()).collect();
+        missing.sort();
+        missing.dedup();
+        Self::Partial { missing }
+    }
+}
+
+/// When this binary was built (RFC 3339), set by the build script
+pub const BUILD_TIMESTAMP: &str = match option_env!("BUILD_TIMESTAMP") {
+    Some(timestamp) => timestamp,
+    None => "unknown",
+};
+
+/// Git commit this binary was built from, if it was built from a checkout
+pub const GIT_COMMIT: Option<&str> = option_env!("GIT_COMMIT");
+
+fn default_build_timestamp() -> String {
+    "unknown".to_string()
 }
 
 /// Static server identification and configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ServerInfo {
     /// Server hostname
     pub hostname: String,
@@ -30,6 +71,12 @@ pub struct ServerInfo {
     pub environment: String,
     /// Operating system information
     pub os_info: OsInfo,
+    /// When the running binary was built (RFC 3339), or "unknown"
+    #[serde(default = "default_build_timestamp")]
+    pub build_timestamp: String,
+    /// Git commit the running binary was built from
+    #[serde(default)]
+    pub git_commit: Option<String>,
 }
 
 /// Validation errors for status data
@@ -90,6 +137,17 @@ impl From<OsInfoValidationError> for StatusValidationError {
     }
 }
 
+#[cfg(test)]
+thread_local! {
+    static STATUS_DATA_CONSTRUCTIONS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// Number of `StatusData::new` calls on the current thread (test builds only)
+#[cfg(test)]
+pub(crate) fn status_data_constructions() -> usize {
+    STATUS_DATA_CONSTRUCTIONS.with(|count| count.get())
+}
+
 #[allow(dead_code)]
 impl StatusData {
     /// Create new StatusData with validation
@@ -98,16 +156,26 @@ impl StatusData {
         collection_interval_seconds: u32,
         server_info: ServerInfo,
     ) -> Result<Self, StatusValidationError> {
+        #[cfg(test)]
+        STATUS_DATA_CONSTRUCTIONS.with(|count| count.set(count.get() + 1));
+
         let data = StatusData {
             server_metrics,
             collection_interval_seconds,
             server_info,
+            data_quality: DataQuality::Complete,
         };
 
         data.validate()?;
         Ok(data)
     }
 
+    /// Mark how complete the metrics are
+    pub fn with_data_quality(mut self, data_quality: DataQuality) -> Self {
+        self.data_quality = data_quality;
+        self
+    }
+
     /// Validate status data
     pub fn validate(&self) -> Result<(), StatusValidationError> {
         // Validate collection interval
@@ -127,11 +195,8 @@ impl StatusData {
     }
 
     /// Get the overall health status based on current metrics
-    pub fn get_health_status(&self) -> super::HealthStatus {
-        super::HealthStatus::from_metrics(
-            self.server_metrics.cpu_usage.usage_percentage,
-            self.server_metrics.memory_usage.usage_percentage,
-        )
+    pub fn get_health_status(&self, thresholds: &super::HealthThresholds) -> super::HealthStatus {
+        thresholds.evaluate_metrics(&self.server_metrics)
     }
 
     /// Get server uptime in human-readable format
@@ -162,24 +227,17 @@ impl StatusData {
         }
     }
 
-    /// Check if any metrics are in critical state
+    /// Check if any metrics are in critical state under the default thresholds
     pub fn has_critical_issues(&self) -> bool {
-        self.server_metrics.memory_usage.usage_percentage > 90.0
-            || self.server_metrics.cpu_usage.usage_percentage > 90.0
-            || self.server_metrics.network_metrics.active_connections > 500
+        self.get_health_status(&super::HealthThresholds::default()) == super::HealthStatus::Critical
     }
 
-    /// Get overall health status
+    /// Get overall health status under the default thresholds
     pub fn health_status(&self) -> &'static str {
-        if self.has_critical_issues() {
-            "Critical"
-        } else if self.server_metrics.memory_usage.usage_percentage > 75.0
-            || self.server_metrics.cpu_usage.usage_percentage > 75.0
-            || self.server_metrics.network_metrics.active_connections > 100
-        {
-            "Warning"
-        } else {
-            "Healthy"
+        match self.get_health_status(&super::HealthThresholds::default()) {
+            super::HealthStatus::Critical => "Critical",
+            super::HealthStatus::Warning => "Warning",
+            super::HealthStatus::Healthy => "Healthy",
         }
     }
 }
@@ -200,12 +258,77 @@ impl ServerInfo {
             start_time,
             environment,
             os_info,
+            build_timestamp: BUILD_TIMESTAMP.to_string(),
+            git_commit: GIT_COMMIT.map(str::to_string),
         };
 
         info.validate()?;
         Ok(info)
     }
 
+    /// Replace the build metadata of this binary, e.g. for a binary built elsewhere
+    pub fn with_build_info(mut self, build_timestamp: impl Into<String>, git_commit: Option<String>) -> Self {
+        self.build_timestamp = build_timestamp.into();
+        self.git_commit = git_commit;
+        self
+    }
+
+    /// Create ServerInfo without failing, replacing each invalid field with a fallback
+    ///
+    /// Returns the names of the fields that were replaced so callers can log them.
+    pub fn with_fallbacks(
+        hostname: String,
+        version: String,
+        start_time: DateTime<Utc>,
+        environment: String,
+        os_info: OsInfo,
+    ) -> (Self, Vec<&'static str>) {
+        let mut info = ServerInfo {
+            hostname,
+            version,
+            start_time,
+            environment,
+            os_info,
+            build_timestamp: BUILD_TIMESTAMP.to_string(),
+            git_commit: GIT_COMMIT.map(str::to_string),
+        };
+        let mut fallbacks = Vec::new();
+
+        // Each pass fixes the first invalid field; there are only five to fix
+        while let Err(error) = info.validate() {
+            let field = match error {
+                StatusValidationError::InvalidHostname { .. } => {
+                    info.hostname = "unknown".to_string();
+                    "hostname"
+                }
+                StatusValidationError::InvalidVersion { .. } => {
+                    info.version = "0.0.0".to_string();
+                    "version"
+                }
+                StatusValidationError::InvalidStartTime { .. } => {
+                    info.start_time = Utc::now();
+                    "start_time"
+                }
+                StatusValidationError::InvalidEnvironment { .. } => {
+                    info.environment = "development".to_string();
+                    "environment"
+                }
+                _ => {
+                    info.os_info = OsInfo::fallback();
+                    "os_info"
+                }
+            };
+
+            if fallbacks.contains(&field) {
+                // The fallback itself failed validation - nothing more to try
+                break;
+            }
+            fallbacks.push(field);
+        }
+
+        (info, fallbacks)
+    }
+
     /// Validate server info
     pub fn validate(&self) -> Result<(), StatusValidationError> {
         // Validate hostname (basic DNS hostname validation)
@@ -315,6 +438,28 @@ mod tests {
         
         assert_eq!(status_data.collection_interval_seconds, 5);
         assert_eq!(status_data.server_info.hostname, "test-server");
+        assert_eq!(status_data.data_quality, DataQuality::Complete);
+    }
+
+    #[test]
+    fn test_data_quality_from_errors() {
+        assert_eq!(DataQuality::from_errors(&[]), DataQuality::Complete);
+
+        let errors = vec![
+            MetricsCollectionError::network_error("eth0", "read failed"),
+            MetricsCollectionError::multiple(vec![
+                MetricsCollectionError::cpu_error("no CPUs"),
+                MetricsCollectionError::network_error("all", "read failed"),
+            ]),
+            MetricsCollectionError::stale_data(3, "timeout"),
+        ];
+        assert_eq!(
+            DataQuality::from_errors(&errors),
+            DataQuality::Partial { missing: vec!["cpu".to_string(), "network".to_string()] }
+        );
+
+        let json = serde_json::to_value(DataQuality::from_errors(&errors[..1])).unwrap();
+        assert_eq!(json, serde_json::json!({"status": "partial", "missing": ["network"]}));
     }
 
     #[test]
@@ -346,6 +491,29 @@ mod tests {
         assert_eq!(info.hostname, "test-server");
         assert_eq!(info.version, "1.0.0");
         assert_eq!(info.environment, "production");
+        assert_eq!(info.build_timestamp, BUILD_TIMESTAMP);
+        assert_eq!(info.git_commit.as_deref(), GIT_COMMIT);
+    }
+
+    #[test]
+    fn test_server_info_build_metadata_serialized() {
+        let info = create_test_server_info().with_build_info("2024-05-01T12:00:00Z", Some("abc1234".to_string()));
+
+        let json = serde_json::to_value(&info).unwrap();
+        assert_eq!(json["build_timestamp"], "2024-05-01T12:00:00Z");
+        assert_eq!(json["git_commit"], "abc1234");
+
+        let json = serde_json::to_value(info.with_build_info("unknown", None)).unwrap();
+        assert_eq!(json["build_timestamp"], "unknown");
+        assert!(json["git_commit"].is_null());
+
+        // Payloads from older servers lack the fields
+        let mut older = json.clone();
+        older.as_object_mut().unwrap().remove("build_timestamp");
+        older.as_object_mut().unwrap().remove("git_commit");
+        let parsed: ServerInfo = serde_json::from_value(older).unwrap();
+        assert_eq!(parsed.build_timestamp, "unknown");
+        assert_eq!(parsed.git_commit, None);
     }
 
     #[test]
@@ -432,6 +600,35 @@ mod tests {
         assert!(status_data.has_critical_issues());
     }
 
+    #[test]
+    fn test_health_status_custom_thresholds() {
+        use crate::models::{HealthStatus, HealthThresholds};
+
+        let mut server_metrics = create_test_metrics();
+        server_metrics.cpu_usage.usage_percentage = 92.0;
+        let status_data = StatusData::new(server_metrics, 5, create_test_server_info()).unwrap();
+        assert_eq!(status_data.get_health_status(&HealthThresholds::default()), HealthStatus::Critical);
+
+        let thresholds = HealthThresholds {
+            cpu_critical: 95.0,
+            ..HealthThresholds::default()
+        };
+        assert_eq!(status_data.get_health_status(&thresholds), HealthStatus::Warning);
+
+        let thresholds = HealthThresholds {
+            cpu_warning: 93.0,
+            cpu_critical: 95.0,
+            ..HealthThresholds::default()
+        };
+        assert_eq!(status_data.get_health_status(&thresholds), HealthStatus::Healthy);
+
+        let thresholds = HealthThresholds {
+            connections_critical: 10,
+            ..thresholds
+        };
+        assert_eq!(status_data.get_health_status(&thresholds), HealthStatus::Critical);
+    }
+
     #[test]
     fn test_formatting() {
         let server_metrics = create_test_metrics();
@@ -482,6 +679,36 @@ mod tests {
         assert_eq!(status_data.server_info.hostname, deserialized.server_info.hostname);
     }
 
+    #[test]
+    fn test_server_info_with_fallbacks() {
+        let mut os_info = OsInfo::fallback();
+        os_info.name = String::new();
+
+        let (info, fallbacks) = ServerInfo::with_fallbacks(
+            "bad host!".to_string(),
+            "1.0.0".to_string(),
+            Utc::now(),
+            "qa".to_string(),
+            os_info,
+        );
+
+        assert_eq!(fallbacks, vec!["hostname", "environment", "os_info"]);
+        assert_eq!(info.hostname, "unknown");
+        assert_eq!(info.version, "1.0.0");
+        assert_eq!(info.environment, "development");
+        assert!(info.os_info.is_fallback());
+        assert!(info.validate().is_ok());
+
+        let (_, fallbacks) = ServerInfo::with_fallbacks(
+            "test-server".to_string(),
+            "1.0.0".to_string(),
+            Utc::now(),
+            "production".to_string(),
+            OsInfo::fallback(),
+        );
+        assert!(fallbacks.is_empty());
+    }
+
     // Helper functions
     fn create_test_metrics() -> ServerMetrics {
         use crate::models::{MemoryMetrics, CpuMetrics, NetworkMetrics};
@@ -494,15 +721,21 @@ mod tests {
                 used_bytes: 4 * 1024 * 1024 * 1024,
                 available_bytes: 4 * 1024 * 1024 * 1024,
                 usage_percentage: 50.0,
+                swap_total_bytes: 0,
+                swap_used_bytes: 0,
+                swap_usage_percentage: 0.0,
             },
             cpu_usage: CpuMetrics {
                 usage_percentage: 25.0,
                 core_count: 8,
+                scale: crate::models::CpuScale::Average,
                 load_average: LoadAverage {
                     one_minute: 1.2,
                     five_minute: 1.1,
                     fifteen_minute: 0.9,
                 },
+                core_usage_percentages: Vec::new(),
+                temperature_celsius: None,
             },
             uptime: Duration::from_secs(3600),
             network_metrics: NetworkMetrics {
@@ -511,7 +744,14 @@ mod tests {
                 packets_sent: 1000,
                 packets_received: 1500,
                 active_connections: 42,
+                counter_reset: false,
+                rate_bytes_sent_per_sec: 0.0,
+                rate_bytes_received_per_sec: 0.0,
             },
+            sources: Default::default(),
+            gpu_metrics: Vec::new(),
+            disk_metrics: None,
+            process_metrics: None,
         }
     }
 
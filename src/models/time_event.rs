@@ -1,6 +1,8 @@
 // Time Event model with UK formatting support
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use std::fmt::Write;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct TimeEvent {
@@ -30,6 +32,69 @@ impl TimeEvent {
     }
 }
 
+/// Per-connection rendering of `formatted_time`: a timezone and strftime pattern
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeFormat {
+    pub tz: Tz,
+    pub format: String,
+}
+
+impl Default for TimeFormat {
+    fn default() -> Self {
+        Self {
+            tz: Tz::UTC,
+            format: TimeFormat::RFC3339.to_string(),
+        }
+    }
+}
+
+impl TimeFormat {
+    /// Pattern used when no `format` is requested
+    pub const RFC3339: &'static str = "%Y-%m-%dT%H:%M:%S%:z";
+
+    /// Build a format from optional IANA timezone and strftime strings
+    ///
+    /// An unknown timezone falls back to UTC with a warning. A pattern
+    /// chrono can't render is an error, found by formatting the current
+    /// time once.
+    pub fn parse(tz: Option<&str>, format: Option<&str>) -> Result<Self, String> {
+        let tz = match tz {
+            Some(name) => name.parse::<Tz>().unwrap_or_else(|_| {
+                tracing::warn!("Unknown timezone {:?}, using UTC", name);
+                Tz::UTC
+            }),
+            None => Tz::UTC,
+        };
+
+        let time_format = Self {
+            tz,
+            format: format.unwrap_or(Self::RFC3339).to_string(),
+        };
+        time_format.try_render(&Utc::now())?;
+        Ok(time_format)
+    }
+
+    /// Render a timestamp in this timezone and pattern
+    pub fn render(&self, timestamp: &DateTime<Utc>) -> String {
+        self.try_render(timestamp).unwrap_or_else(|_| timestamp.to_rfc3339())
+    }
+
+    /// Re-render an event's `formatted_time`
+    pub fn apply(&self, event: TimeEvent) -> TimeEvent {
+        TimeEvent {
+            formatted_time: self.render(&event.timestamp),
+            ..event
+        }
+    }
+
+    fn try_render(&self, timestamp: &DateTime<Utc>) -> Result<String, String> {
+        let mut rendered = String::new();
+        write!(rendered, "{}", timestamp.with_timezone(&self.tz).format(&self.format))
+            .map_err(|_| format!("Invalid time format {:?}", self.format))?;
+        Ok(rendered)
+    }
+}
+
 impl Default for TimeEvent {
     fn default() -> Self {
         Self::new()
@@ -60,6 +125,23 @@ mod tests {
         assert_eq!(event.formatted_time, "20/09/2025 10:30:45");
     }
 
+    #[test]
+    fn test_time_format_timezone() {
+        let timestamp = Utc.with_ymd_and_hms(2025, 1, 20, 15, 30, 0).unwrap();
+        let format = TimeFormat::parse(Some("America/New_York"), Some("%H:%M %Z")).unwrap();
+        assert_eq!(format.apply(TimeEvent::from_timestamp(timestamp)).formatted_time, "10:30 EST");
+
+        let format = TimeFormat::parse(Some("Mars/Olympus_Mons"), None).unwrap();
+        assert_eq!(format, TimeFormat::default());
+        assert_eq!(format.render(&timestamp), "2025-01-20T15:30:00+00:00");
+    }
+
+    #[test]
+    fn test_time_format_rejects_invalid_pattern() {
+        assert!(TimeFormat::parse(None, Some("%Q")).is_err());
+        assert!(TimeFormat::parse(None, Some("%Y-%m-%d")).is_ok());
+    }
+
     #[test]
     fn test_serialization() {
         let event = TimeEvent::new();
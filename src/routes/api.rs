@@ -1,6 +1,7 @@
 // API endpoint implementations
 use axum::{
-    extract::Extension,
+    body::Bytes,
+    extract::{Extension, OriginalUri, Query},
     response::{
         sse::Event,
         Sse,
@@ -8,20 +9,84 @@ use axum::{
     http::StatusCode,
     Json,
 };
-use std::{sync::Arc, convert::Infallible};
+use std::{sync::Arc, convert::Infallible, time::Duration};
 use futures::stream::Stream;
+use crate::models::{BroadcastEvent, BroadcastValidationError, TimeFormat};
+use crate::routes::server_status::ErrorResponse;
 use crate::services::{SseService, StaticService};
+use crate::services::sse_service::{DEFAULT_TIME_STREAM_RETRY_MS, TIME_STREAM_RETRY_RANGE_MS};
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use tracing::{info, error};
 
+/// Response body for the service status endpoint
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ServiceStatusResponse {
+    pub service: String,
+    pub version: String,
+    /// RFC 3339 timestamp of the response
+    pub timestamp: String,
+    pub uptime_seconds: u64,
+    pub sse: SseComponentStatus,
+    pub static_assets: StaticAssetsStatus,
+}
+
+/// SSE time broadcaster status
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SseComponentStatus {
+    pub healthy: bool,
+    pub active_connections: usize,
+    /// Broadcast interval rounded down to whole seconds
+    pub broadcast_interval_seconds: u64,
+    #[serde(default)]
+    pub broadcast_interval_ms: u64,
+}
+
+/// Embedded static asset status
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StaticAssetsStatus {
+    pub healthy: bool,
+    pub embedded_asset_count: usize,
+    pub available_assets: usize,
+}
+
+/// Query parameters for the time stream
+#[derive(Debug, Default, Deserialize)]
+pub struct TimeStreamQuery {
+    /// IANA timezone for `formatted_time`, e.g. `Europe/London` (default: UTC)
+    pub tz: Option<String>,
+    /// strftime pattern for `formatted_time` (default: RFC 3339)
+    pub format: Option<String>,
+    /// Reconnect delay advertised to the client, clamped to 500-30000 (default: 3000)
+    pub retry_ms: Option<u64>,
+}
+
 /// SSE endpoint for time stream (/api/time/stream)
+///
+/// Without `tz` or `format` events keep the broadcast UK format. The first
+/// event sets the client's reconnect delay from `retry_ms`.
 pub async fn time_stream(
+    Query(params): Query<TimeStreamQuery>,
     Extension(sse_service): Extension<Arc<SseService>>,
-) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, (StatusCode, Json<Value>)> {
     info!("New SSE time stream connection requested");
-    
+
+    let time_format = if params.tz.is_some() || params.format.is_some() {
+        let time_format = TimeFormat::parse(params.tz.as_deref(), params.format.as_deref()).map_err(|e| {
+            (StatusCode::BAD_REQUEST, Json(json!({ "error": e, "code": "INVALID_TIME_FORMAT" })))
+        })?;
+        Some(time_format)
+    } else {
+        None
+    };
+
+    let retry_ms = params
+        .retry_ms
+        .unwrap_or(DEFAULT_TIME_STREAM_RETRY_MS)
+        .clamp(*TIME_STREAM_RETRY_RANGE_MS.start(), *TIME_STREAM_RETRY_RANGE_MS.end());
+
     // Create a new SSE stream for this client
-    sse_service.create_time_stream()
+    Ok(sse_service.create_time_stream(time_format, Some(Duration::from_millis(retry_ms))))
 }
 
 /// Health check endpoint (/health)
@@ -70,42 +135,95 @@ pub async fn health_check(
 pub async fn service_status(
     Extension(sse_service): Extension<Arc<SseService>>,
     Extension(static_service): Extension<Arc<StaticService>>,
-) -> Json<Value> {
+) -> Json<ServiceStatusResponse> {
     info!("Service status requested");
     
-    let response = json!({
-        "service": "axum-sse",
-        "version": env!("CARGO_PKG_VERSION"),
-        "timestamp": chrono::Utc::now().to_rfc3339(),
-        "uptime_seconds": std::time::SystemTime::now()
+    let response = ServiceStatusResponse {
+        service: "axum-sse".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        uptime_seconds: std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap_or_default()
             .as_secs(),
-        "sse": {
-            "healthy": sse_service.is_healthy(),
-            "active_connections": sse_service.receiver_count(),
-            "broadcast_interval_seconds": 10
+        sse: SseComponentStatus {
+            healthy: sse_service.is_healthy(),
+            active_connections: sse_service.receiver_count(),
+            broadcast_interval_seconds: sse_service.broadcast_interval().as_secs(),
+            broadcast_interval_ms: sse_service.broadcast_interval().as_millis() as u64,
         },
-        "static_assets": {
-            "healthy": static_service.is_healthy(),
-            "embedded_asset_count": static_service.asset_count(),
-            "available_assets": static_service.list_assets().len()
-        }
-    });
+        static_assets: StaticAssetsStatus {
+            healthy: static_service.is_healthy(),
+            embedded_asset_count: static_service.asset_count(),
+            available_assets: static_service.list_assets().len(),
+        },
+    };
     
     Json(response)
 }
 
-/// Endpoint to trigger a manual time broadcast (/api/time/broadcast) - for testing
+/// Optional body of a manual broadcast
+#[derive(Debug, Default, Deserialize)]
+pub struct ManualBroadcastRequest {
+    /// Text sent to subscribers; without it the current time is broadcast
+    pub message: Option<String>,
+    /// SSE event type for the message, `broadcast` by default
+    pub event_type: Option<String>,
+}
+
+fn bad_broadcast_request(error: &str, message: String) -> (StatusCode, Json<Value>) {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(json!({
+            "error": error,
+            "message": message,
+            "timestamp": chrono::Utc::now().to_rfc3339()
+        })),
+    )
+}
+
+/// Endpoint to trigger a manual broadcast (/api/broadcast)
+///
+/// An empty body sends a time update right away. A JSON body with a
+/// `message` sends it to every time stream subscriber as a custom event.
+/// Requires the API key.
 pub async fn manual_time_broadcast(
     Extension(sse_service): Extension<Arc<SseService>>,
-) -> Result<Json<Value>, StatusCode> {
-    info!("Manual time broadcast requested");
-    
-    // For now, we'll just return success since our SSE service 
-    // broadcasts automatically every 10 seconds
+    body: Bytes,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    info!("Manual broadcast requested");
+
+    let request: ManualBroadcastRequest = if body.iter().all(u8::is_ascii_whitespace) {
+        ManualBroadcastRequest::default()
+    } else {
+        serde_json::from_slice(&body)
+            .map_err(|e| bad_broadcast_request("invalid_body", format!("Invalid broadcast body: {}", e)))?
+    };
+
+    let (event_type, delivered) = match request.message {
+        Some(message) => {
+            let event = BroadcastEvent::new(request.event_type, message).map_err(|e| {
+                let error = match e {
+                    BroadcastValidationError::MessageTooLong { .. } => "message_too_long",
+                    BroadcastValidationError::InvalidEventType { .. } => "invalid_event_type",
+                };
+                bad_broadcast_request(error, e.to_string())
+            })?;
+            let event_type = event.event_type.clone();
+            (event_type, sse_service.broadcast_custom_event(event))
+        }
+        None if request.event_type.is_some() => {
+            return Err(bad_broadcast_request(
+                "missing_message",
+                "event_type requires a message".to_string(),
+            ));
+        }
+        None => ("time-update".to_string(), sse_service.broadcast_time_now()),
+    };
+
     let response = json!({
-        "message": "Time is broadcast automatically every 10 seconds",
+        "event_type": event_type,
+        "delivered_to": delivered,
         "active_connections": sse_service.receiver_count(),
         "timestamp": chrono::Utc::now().to_rfc3339()
     });
@@ -113,11 +231,25 @@ pub async fn manual_time_broadcast(
     Ok(Json(response))
 }
 
+/// Fallback for unknown `/api` paths, so API clients get a JSON 404 rather
+/// than the SPA's index page
+pub async fn api_not_found(OriginalUri(uri): OriginalUri) -> (StatusCode, Json<ErrorResponse>) {
+    info!("No API route for {}", uri.path());
+    (
+        StatusCode::NOT_FOUND,
+        Json(ErrorResponse::new(
+            &format!("No API endpoint at {}", uri.path()),
+            "not_found",
+            "not_found",
+        )),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::services::{SseService, StaticService};
-    use axum::{body::Body, http::Request, Router, routing::get};
+    use axum::{body::Body, http::Request, Router, routing::{get, post}};
     use tower::util::ServiceExt;
 
     #[tokio::test]
@@ -154,6 +286,14 @@ mod tests {
             .unwrap();
 
         assert_eq!(response.status(), StatusCode::OK);
+
+        // The payload must match the typed response
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let status: ServiceStatusResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(status.service, "axum-sse");
+        assert_eq!(status.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(status.sse.broadcast_interval_seconds, 10);
+        assert_eq!(status.sse.broadcast_interval_ms, 10_000);
     }
 
     #[tokio::test]
@@ -172,6 +312,138 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
     }
 
+    use axum::response::IntoResponse;
+    use futures::StreamExt;
+
+    fn broadcast_request(body: impl Into<Body>) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri("/api/broadcast")
+            .header("content-type", "application/json")
+            .body(body.into())
+            .unwrap()
+    }
+
+    async fn json_body(response: axum::response::Response) -> Value {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_empty_body_sends_time_update() {
+        let sse_service = Arc::new(SseService::new());
+        let mut stream = sse_service
+            .create_time_stream(None, None)
+            .into_response()
+            .into_body()
+            .into_data_stream();
+        let app = Router::new()
+            .route("/api/broadcast", post(manual_time_broadcast))
+            .layer(Extension(Arc::clone(&sse_service)));
+
+        let response = app.oneshot(broadcast_request(Body::empty())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = json_body(response).await;
+        assert_eq!(body["event_type"], "time-update");
+        assert_eq!(body["delivered_to"], 1);
+
+        let chunk = tokio::time::timeout(std::time::Duration::from_secs(2), stream.next())
+            .await
+            .expect("time update should arrive")
+            .unwrap()
+            .unwrap();
+        assert!(String::from_utf8(chunk.to_vec()).unwrap().contains("event: time-update"));
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_custom_message() {
+        let sse_service = Arc::new(SseService::new());
+        let mut stream = sse_service
+            .create_time_stream(None, None)
+            .into_response()
+            .into_body()
+            .into_data_stream();
+        let app = Router::new()
+            .route("/api/broadcast", post(manual_time_broadcast))
+            .layer(Extension(Arc::clone(&sse_service)));
+
+        let response = app
+            .oneshot(broadcast_request(r#"{"message": "Deploying at 22:00", "event_type": "maintenance"}"#))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = json_body(response).await;
+        assert_eq!(body["event_type"], "maintenance");
+        assert_eq!(body["delivered_to"], 1);
+
+        let chunk = tokio::time::timeout(std::time::Duration::from_secs(2), stream.next())
+            .await
+            .expect("broadcast should arrive")
+            .unwrap()
+            .unwrap();
+        let text = String::from_utf8(chunk.to_vec()).unwrap();
+        assert!(text.contains("event: maintenance"), "unexpected event: {}", text);
+        assert!(text.contains("Deploying at 22:00"));
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_rejects_invalid_payloads() {
+        let sse_service = Arc::new(SseService::new());
+        let app = Router::new()
+            .route("/api/broadcast", post(manual_time_broadcast))
+            .layer(Extension(sse_service));
+
+        let oversized = json!({ "message": "x".repeat(4097) }).to_string();
+        let response = app.clone().oneshot(broadcast_request(oversized)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(json_body(response).await["error"], "message_too_long");
+
+        let response = app.clone().oneshot(broadcast_request("{not json")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(json_body(response).await["error"], "invalid_body");
+
+        let forged = json!({ "message": "hi", "event_type": "time-update" }).to_string();
+        let response = app.oneshot(broadcast_request(forged)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(json_body(response).await["error"], "invalid_event_type");
+    }
+
+    fn gated_broadcast_app(api_key: crate::middleware::ApiKeyConfig) -> Router {
+        Router::new()
+            .route(
+                "/api/broadcast",
+                post(manual_time_broadcast).route_layer(axum::middleware::from_fn_with_state(
+                    api_key,
+                    crate::middleware::require_admin_key,
+                )),
+            )
+            .layer(Extension(Arc::new(SseService::new())))
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_refused_without_configured_key() {
+        let app = gated_broadcast_app(crate::middleware::ApiKeyConfig::default());
+
+        let response = app.oneshot(broadcast_request(r#"{"message": "hi"}"#)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_rejects_wrong_key() {
+        let app = gated_broadcast_app(crate::middleware::ApiKeyConfig::new(Some("secret".to_string())));
+
+        let mut request = broadcast_request(r#"{"message": "hi"}"#);
+        request.headers_mut().insert("x-api-key", "wrong".parse().unwrap());
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let mut request = broadcast_request(r#"{"message": "hi"}"#);
+        request.headers_mut().insert("x-api-key", "secret".parse().unwrap());
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
     #[tokio::test]
     async fn test_sse_time_stream_endpoint() {
         let sse_service = Arc::new(SseService::new());
@@ -192,4 +464,74 @@ mod tests {
         let headers = response.headers();
         assert_eq!(headers.get("content-type").unwrap(), "text/event-stream");
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_time_stream_in_timezone() {
+        use chrono::TimeZone;
+        use futures::StreamExt;
+
+        let sse_service = Arc::new(SseService::new());
+        let app = Router::new()
+            .route("/api/time/stream", get(time_stream))
+            .layer(Extension(Arc::clone(&sse_service)));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/time/stream?tz=America/New_York&format=%25Y-%25m-%25d%20%25H:%25M%20%25Z")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let timestamp = chrono::Utc.with_ymd_and_hms(2025, 7, 4, 16, 0, 0).unwrap();
+        sse_service.send_time_event(crate::models::TimeEvent::from_timestamp(timestamp));
+
+        let mut body = response.into_body().into_data_stream();
+        // Skip the reconnect hint
+        body.next().await.unwrap().unwrap();
+        let frame = String::from_utf8(body.next().await.unwrap().unwrap().to_vec()).unwrap();
+        let data = frame.lines().find_map(|line| line.strip_prefix("data:")).expect("Expected a data line");
+        let event: crate::models::TimeEvent = serde_json::from_str(data.trim()).unwrap();
+        assert_eq!(event.formatted_time, "2025-07-04 12:00 EDT");
+    }
+
+    #[tokio::test]
+    async fn test_time_stream_retry_hint() {
+        use futures::StreamExt;
+
+        let app = Router::new()
+            .route("/api/time/stream", get(time_stream))
+            .layer(Extension(Arc::new(SseService::new())));
+
+        for (query, expected) in [("", "retry:3000"), ("?retry_ms=10000", "retry:10000"), ("?retry_ms=50", "retry:500")] {
+            let response = app
+                .clone()
+                .oneshot(Request::builder().uri(format!("/api/time/stream{}", query)).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let mut body = response.into_body().into_data_stream();
+            let frame = String::from_utf8(body.next().await.unwrap().unwrap().to_vec()).unwrap();
+            let retry = frame.lines().find(|line| line.starts_with("retry:")).expect("Expected a retry line");
+            assert_eq!(retry, expected);
+            assert!(!frame.contains("data:"), "The hint should carry no data: {}", frame);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_time_stream_rejects_invalid_format() {
+        let app = Router::new()
+            .route("/api/time/stream", get(time_stream))
+            .layer(Extension(Arc::new(SseService::new())));
+
+        let response = app
+            .oneshot(Request::builder().uri("/api/time/stream?format=%25Q").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
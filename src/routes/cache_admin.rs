@@ -0,0 +1,209 @@
+// Metrics cache admin endpoints
+// Lists cache entries and evicts them on demand; eviction always requires the API key
+
+use crate::middleware::{require_admin_key, ApiKeyConfig};
+use crate::routes::server_status::ServerStatusState;
+use crate::services::metrics_cache::{CacheEntryInfo, CacheStats};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::{delete, get},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tracing::{debug, info};
+
+/// Response body for GET /api/cache
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CacheListing {
+    pub entries: Vec<CacheEntryInfo>,
+    pub stats: CacheStats,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// GET /api/cache - Every cache entry with its age and access count, plus cache statistics
+pub async fn list_cache(State(state): State<ServerStatusState>) -> Json<CacheListing> {
+    let entries = state.metrics_cache.get_cache_entries().await;
+    debug!("GET /api/cache - {} entries", entries.len());
+
+    Json(CacheListing {
+        entries,
+        stats: state.metrics_cache.get_stats(),
+        timestamp: Utc::now(),
+    })
+}
+
+/// DELETE /api/cache/:key - Evict a single entry, 404 when the key isn't cached
+pub async fn evict_cache_entry(
+    Path(key): Path<String>,
+    State(state): State<ServerStatusState>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    if !state.metrics_cache.invalidate(&key).await {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "cache_key_not_found",
+                "message": format!("No cache entry for key '{}'", key),
+                "timestamp": Utc::now(),
+            })),
+        ));
+    }
+
+    info!("Evicted cache entry '{}'", key);
+    Ok(Json(json!({
+        "evicted": key,
+        "timestamp": Utc::now(),
+    })))
+}
+
+/// DELETE /api/server-status/cache/:key - Legacy alias for evicting a single entry
+///
+/// Kept so clients of the original route keep working; it shares the eviction
+/// path and the API key check with DELETE /api/cache/:key, but keeps its
+/// original response shape and reports a missing key as `existed: false`
+/// rather than a 404.
+pub async fn invalidate_cache_key(
+    Path(key): Path<String>,
+    State(state): State<ServerStatusState>,
+) -> Json<Value> {
+    let existed = state.metrics_cache.invalidate(&key).await;
+
+    debug!("DELETE /api/server-status/cache/{} - existed: {}", key, existed);
+    Json(json!({
+        "key": key,
+        "existed": existed,
+        "timestamp": Utc::now(),
+        "api_version": "1.0"
+    }))
+}
+
+/// DELETE /api/cache - Evict every entry
+pub async fn clear_cache(State(state): State<ServerStatusState>) -> Json<Value> {
+    let evicted = state.metrics_cache.get_stats().current_entries;
+    state.metrics_cache.clear().await;
+
+    info!("Cleared metrics cache ({} entries)", evicted);
+    Json(json!({
+        "evicted_entries": evicted,
+        "timestamp": Utc::now(),
+    }))
+}
+
+/// Create the cache admin router; `api_key` guards evictions
+pub fn create_router(api_key: ApiKeyConfig) -> Router<ServerStatusState> {
+    let admin = axum::middleware::from_fn_with_state(api_key, require_admin_key);
+    Router::new()
+        .route("/cache", get(list_cache).merge(delete(clear_cache).route_layer(admin.clone())))
+        .route("/cache/:key", delete(evict_cache_entry).route_layer(admin.clone()))
+        .route("/server-status/cache/:key", delete(invalidate_cache_key).route_layer(admin))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::test_support::create_test_state;
+    use axum_test::TestServer;
+
+    async fn populated_state() -> ServerStatusState {
+        let state = create_test_state();
+        state.metrics_service.initialize().await.unwrap();
+
+        for key in ["default", "dashboard", "dashboard", "health_check"] {
+            state.metrics_cache.get_metrics(Some(key.to_string())).await;
+        }
+
+        state
+    }
+
+    fn server(state: ServerStatusState) -> TestServer {
+        let api_key = ApiKeyConfig::new(Some("secret".to_string()));
+        let mut server = TestServer::new(create_router(api_key).with_state(state)).unwrap();
+        server.add_header("x-api-key".parse().unwrap(), "secret".parse().unwrap());
+        server
+    }
+
+    #[tokio::test]
+    async fn test_cache_listing() {
+        let server = server(populated_state().await);
+
+        let response = server.get("/cache").await;
+        response.assert_status_ok();
+        let listing: CacheListing = response.json();
+
+        let keys: Vec<&str> = listing.entries.iter().map(|entry| entry.key.as_str()).collect();
+        assert_eq!(keys, vec!["dashboard", "default", "health_check"]);
+        assert_eq!(listing.entries[0].access_count, 2);
+        assert_eq!(listing.stats.current_entries, 3);
+        assert_eq!(listing.stats.cache_hits, 1);
+        assert_eq!(listing.stats.cache_misses, 3);
+    }
+
+    #[tokio::test]
+    async fn test_evict_single_entry() {
+        let state = populated_state().await;
+        let server = server(state.clone());
+
+        let response = server.delete("/cache/dashboard").await;
+        response.assert_status_ok();
+        assert_eq!(response.json::<Value>()["evicted"], "dashboard");
+
+        let response = server.delete("/cache/dashboard").await;
+        response.assert_status(StatusCode::NOT_FOUND);
+
+        let mut keys = state.metrics_cache.get_cache_keys().await;
+        keys.sort();
+        assert_eq!(keys, vec!["default".to_string(), "health_check".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_legacy_invalidate_alias() {
+        let state = populated_state().await;
+        let server = server(state.clone());
+
+        let response = server.delete("/server-status/cache/dashboard").await;
+        response.assert_status_ok();
+        let body: Value = response.json();
+        assert_eq!(body["key"], "dashboard");
+        assert_eq!(body["existed"], true);
+
+        let response = server.delete("/server-status/cache/dashboard").await;
+        response.assert_status_ok();
+        assert_eq!(response.json::<Value>()["existed"], false);
+
+        assert_eq!(state.metrics_cache.get_cache_keys().await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_clear_cache() {
+        let state = populated_state().await;
+        let server = server(state.clone());
+
+        let response = server.delete("/cache").await;
+        response.assert_status_ok();
+        assert_eq!(response.json::<Value>()["evicted_entries"], 3);
+        assert!(state.metrics_cache.get_cache_keys().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_eviction_requires_api_key() {
+        let state = populated_state().await;
+
+        // No key configured: evictions are refused outright, listing stays open
+        let server = TestServer::new(create_router(ApiKeyConfig::default()).with_state(state.clone())).unwrap();
+        server.delete("/cache/dashboard").await.assert_status(StatusCode::FORBIDDEN);
+        server.delete("/cache").await.assert_status(StatusCode::FORBIDDEN);
+        server.delete("/server-status/cache/dashboard").await.assert_status(StatusCode::FORBIDDEN);
+        server.get("/cache").await.assert_status_ok();
+
+        // Key configured but not presented
+        let api_key = ApiKeyConfig::new(Some("secret".to_string()));
+        let server = TestServer::new(create_router(api_key).with_state(state.clone())).unwrap();
+        server.delete("/cache/dashboard").await.assert_status(StatusCode::UNAUTHORIZED);
+        server.delete("/server-status/cache/dashboard").await.assert_status(StatusCode::UNAUTHORIZED);
+
+        assert_eq!(state.metrics_cache.get_cache_keys().await.len(), 3);
+    }
+}
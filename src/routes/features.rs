@@ -0,0 +1,126 @@
+// Feature toggle management endpoints
+// Lets operators switch expensive routes off during an incident without redeploying
+
+use crate::middleware::features::{log_toggle_change, FeatureToggles};
+use crate::middleware::{require_admin_key, ApiKeyConfig};
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Request body for POST /api/features
+#[derive(Debug, Deserialize)]
+pub struct FeatureToggleRequest {
+    /// Full request path of the route, e.g. `/api/server-status`
+    pub route: String,
+    /// Whether the route should be served
+    pub enabled: bool,
+}
+
+/// Current toggle state
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FeatureTogglesResponse {
+    pub disabled_routes: Vec<String>,
+}
+
+/// GET /api/features - List disabled routes
+pub async fn get_features(State(toggles): State<FeatureToggles>) -> Json<FeatureTogglesResponse> {
+    Json(FeatureTogglesResponse {
+        disabled_routes: toggles.disabled_routes(),
+    })
+}
+
+/// POST /api/features - Enable or disable a route; requires the API key
+pub async fn update_feature(
+    State(toggles): State<FeatureToggles>,
+    Json(request): Json<FeatureToggleRequest>,
+) -> Result<Json<FeatureTogglesResponse>, (StatusCode, Json<Value>)> {
+    if !toggles.set_enabled(&request.route, request.enabled) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_route",
+                "message": format!("Route '{}' cannot be toggled", request.route),
+            })),
+        ));
+    }
+
+    log_toggle_change(&request.route, request.enabled);
+
+    Ok(Json(FeatureTogglesResponse {
+        disabled_routes: toggles.disabled_routes(),
+    }))
+}
+
+/// Create router for feature toggle endpoints; `api_key` guards changes
+pub fn create_router(api_key: ApiKeyConfig) -> Router<FeatureToggles> {
+    let update = post(update_feature)
+        .route_layer(axum::middleware::from_fn_with_state(api_key, require_admin_key));
+    Router::new().route("/features", get(get_features).merge(update))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum_test::TestServer;
+
+    fn server(toggles: FeatureToggles) -> TestServer {
+        let api_key = ApiKeyConfig::new(Some("secret".to_string()));
+        let mut server = TestServer::new(create_router(api_key).with_state(toggles)).unwrap();
+        server.add_header("x-api-key".parse().unwrap(), "secret".parse().unwrap());
+        server
+    }
+
+    #[tokio::test]
+    async fn test_toggle_route_via_api() {
+        let toggles = FeatureToggles::new();
+        let server = server(toggles.clone());
+
+        let response = server
+            .post("/features")
+            .json(&json!({ "route": "/api/server-status", "enabled": false }))
+            .await;
+        response.assert_status_ok();
+        let body: FeatureTogglesResponse = response.json();
+        assert_eq!(body.disabled_routes, vec!["/api/server-status".to_string()]);
+        assert!(!toggles.is_enabled("/api/server-status"));
+
+        let response = server.get("/features").await;
+        let body: FeatureTogglesResponse = response.json();
+        assert_eq!(body.disabled_routes.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cannot_disable_features_route() {
+        let server = server(FeatureToggles::new());
+
+        let response = server
+            .post("/features")
+            .json(&json!({ "route": "/api/features", "enabled": false }))
+            .await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_update_requires_api_key() {
+        let toggles = FeatureToggles::new();
+        let request = json!({ "route": "/api/server-status", "enabled": false });
+
+        // No key configured: changes are refused outright
+        let server = TestServer::new(create_router(ApiKeyConfig::default()).with_state(toggles.clone())).unwrap();
+        server.post("/features").json(&request).await.assert_status(StatusCode::FORBIDDEN);
+        server.get("/features").await.assert_status_ok();
+
+        // Key configured but not presented
+        let api_key = ApiKeyConfig::new(Some("secret".to_string()));
+        let server = TestServer::new(create_router(api_key).with_state(toggles.clone())).unwrap();
+        server.post("/features").json(&request).await.assert_status(StatusCode::UNAUTHORIZED);
+
+        assert!(toggles.is_enabled("/api/server-status"));
+    }
+}
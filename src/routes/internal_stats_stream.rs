@@ -0,0 +1,157 @@
+// Internal stats SSE endpoint
+// Streams the metrics service and cache statistics for self-monitoring dashboards
+
+use crate::routes::server_status::{ServerStatusState, ServerStatusError};
+use crate::routes::server_status_stream::{stream_head_not_allowed, validate_client_id};
+use crate::services::metrics_cache::CacheStats;
+use crate::services::metrics_service::CollectionStats;
+use axum::{
+    extract::{Query, State},
+    response::sse::{Event, Sse},
+    routing::get,
+    Router,
+};
+use chrono::{DateTime, Utc};
+use futures_util::stream::{self, Stream};
+use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
+use std::time::Duration;
+use tokio::time::{interval, MissedTickBehavior};
+use tracing::{debug, info, warn};
+
+/// Query parameters for the internal stats stream
+#[derive(Debug, Deserialize)]
+pub struct InternalStatsQuery {
+    /// Update interval in seconds (default: 5, same limits as the metrics stream)
+    pub interval: Option<u32>,
+    /// Client identifier for logging
+    pub client_id: Option<String>,
+}
+
+/// Payload of each `internal_stats` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InternalStatsEvent {
+    pub timestamp: DateTime<Utc>,
+    pub sequence: u64,
+    /// Metrics service collection counters and timings
+    pub collection: CollectionStats,
+    /// Metrics cache hit ratio, evictions and refresh counters
+    pub cache: CacheStats,
+}
+
+/// Snapshot the service and cache statistics
+async fn collect_internal_stats(state: &ServerStatusState, sequence: u64) -> InternalStatsEvent {
+    InternalStatsEvent {
+        timestamp: Utc::now(),
+        sequence,
+        collection: state.metrics_service.get_stats().await,
+        cache: state.metrics_cache.get_stats(),
+    }
+}
+
+/// Stream of `internal_stats` events, the first sent immediately
+fn internal_stats_events(
+    state: ServerStatusState,
+    interval_seconds: u32,
+) -> impl Stream<Item = Result<Event, Infallible>> {
+    let mut ticker = interval(Duration::from_secs(interval_seconds as u64));
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+    stream::unfold((state, ticker, 0u64), |(state, mut ticker, sequence)| async move {
+        ticker.tick().await;
+
+        let stats = collect_internal_stats(&state, sequence).await;
+        let event = Event::default()
+            .event("internal_stats")
+            .id(sequence.to_string())
+            .json_data(&stats)
+            .unwrap_or_else(|e| {
+                warn!("Failed to serialize internal stats: {}", e);
+                Event::default().event("error").data("internal stats unavailable")
+            });
+
+        Some((Ok(event), (state, ticker, sequence + 1)))
+    })
+}
+
+/// GET /api/internal-stats-stream - Live collection and cache statistics over SSE
+pub async fn internal_stats_stream(
+    Query(params): Query<InternalStatsQuery>,
+    State(state): State<ServerStatusState>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ServerStatusError> {
+    if let Some(client_id) = &params.client_id {
+        validate_client_id(client_id).map_err(ServerStatusError::BadRequest)?;
+    }
+
+    let interval_seconds = state.sse_config.effective_interval_seconds(
+        params.interval.unwrap_or(5),
+        state.metrics_service.get_config().collection_interval_seconds,
+    );
+
+    info!(
+        "New internal stats stream: client_id={:?}, interval={}s",
+        params.client_id, interval_seconds
+    );
+
+    let keep_alive = state.sse_config.keep_alive();
+    let stream = internal_stats_events(state, interval_seconds);
+    debug!("Internal stats stream initialized");
+
+    Ok(Sse::new(stream).keep_alive(keep_alive))
+}
+
+/// Create the internal stats router
+pub fn create_router() -> Router<ServerStatusState> {
+    Router::new().route(
+        "/internal-stats-stream",
+        get(internal_stats_stream).head(stream_head_not_allowed),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::test_support::create_test_state;
+    use axum::response::IntoResponse;
+    use futures_util::StreamExt;
+
+    #[tokio::test]
+    async fn test_stats_event_contains_hit_ratio() {
+        let state = create_test_state();
+        state.metrics_service.initialize().await.unwrap();
+        state.metrics_cache.get_metrics(None).await;
+        state.metrics_cache.get_metrics(None).await;
+
+        let sse = internal_stats_stream(
+            Query(InternalStatsQuery { interval: None, client_id: None }),
+            State(state),
+        )
+        .await
+        .unwrap();
+        let mut body = sse.into_response().into_body().into_data_stream();
+        let frame = String::from_utf8(body.next().await.unwrap().unwrap().to_vec()).unwrap();
+
+        assert!(frame.lines().any(|line| line == "event: internal_stats"));
+        let data = frame
+            .lines()
+            .find_map(|line| line.strip_prefix("data:"))
+            .expect("Expected a data line");
+        let event: InternalStatsEvent = serde_json::from_str(data.trim()).unwrap();
+
+        let json: serde_json::Value = serde_json::from_str(data.trim()).unwrap();
+        assert!(json["cache"]["hit_ratio"].is_number());
+        assert_eq!(event.cache.hit_ratio, 0.5);
+        assert_eq!(event.sequence, 0);
+        assert!(event.collection.total_collections >= 1);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_invalid_client_id() {
+        let result = internal_stats_stream(
+            Query(InternalStatsQuery { interval: None, client_id: Some("a\nb".to_string()) }),
+            State(create_test_state()),
+        )
+        .await;
+        assert!(matches!(result, Err(ServerStatusError::BadRequest(_))));
+    }
+}
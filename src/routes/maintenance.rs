@@ -0,0 +1,42 @@
+// Maintenance mode switch
+// Lets operators take the app offline and back without redeploying
+
+use crate::middleware::{require_admin_key, ApiKeyConfig, MaintenanceMode};
+use axum::{
+    extract::State,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use serde::{Deserialize, Serialize};
+
+/// Maintenance state, as read or set
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MaintenanceState {
+    pub enabled: bool,
+}
+
+/// GET /api/maintenance - Whether maintenance mode is on
+pub async fn get_maintenance(State(maintenance): State<MaintenanceMode>) -> Json<MaintenanceState> {
+    Json(MaintenanceState {
+        enabled: maintenance.is_enabled(),
+    })
+}
+
+/// POST /api/maintenance - Switch maintenance mode on or off; requires the API key
+pub async fn set_maintenance(
+    State(maintenance): State<MaintenanceMode>,
+    Json(request): Json<MaintenanceState>,
+) -> Json<MaintenanceState> {
+    maintenance.set_enabled(request.enabled);
+    Json(MaintenanceState {
+        enabled: maintenance.is_enabled(),
+    })
+}
+
+/// Create router for the maintenance switch; `api_key` guards changes
+pub fn create_router(api_key: ApiKeyConfig) -> Router<MaintenanceMode> {
+    let update = post(set_maintenance)
+        .route_layer(axum::middleware::from_fn_with_state(api_key, require_admin_key));
+    Router::new().route("/maintenance", get(get_maintenance).merge(update))
+}
@@ -0,0 +1,147 @@
+// Runtime metrics configuration endpoint
+// Applies a new MetricsServiceConfig without a restart
+
+use crate::middleware::{require_admin_key, ApiKeyConfig};
+use crate::routes::server_status::ServerStatusState;
+use crate::services::metrics_service::MetricsServiceConfig;
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::Json,
+    routing::put,
+    Router,
+};
+use chrono::Utc;
+use serde_json::{json, Value};
+use tracing::{info, warn};
+
+/// PUT /api/config/metrics - Replace the metrics service configuration
+///
+/// Returns the effective config, which can differ from the request when the
+/// collection interval is raised to the CPU refresh floor. Out-of-range values
+/// and collector path overrides are rejected with 400 and leave the current
+/// config in place. Requires the API key.
+pub async fn update_metrics_config(
+    State(state): State<ServerStatusState>,
+    Json(config): Json<MetricsServiceConfig>,
+) -> Result<Json<MetricsServiceConfig>, (StatusCode, Json<Value>)> {
+    match state.metrics_service.update_config(config).await {
+        Ok(effective) => {
+            info!(
+                "Metrics config updated: interval {}s, timeout {}ms",
+                effective.collection_interval_seconds, effective.collection_timeout_ms
+            );
+            Ok(Json(effective))
+        }
+        Err(error) => {
+            warn!("Rejected metrics config update: {}", error);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "invalid_config",
+                    "message": error.to_string(),
+                    "field": error.field(),
+                    "timestamp": Utc::now(),
+                })),
+            ))
+        }
+    }
+}
+
+/// Create the runtime configuration router; `api_key` guards updates
+pub fn create_router(api_key: ApiKeyConfig) -> Router<ServerStatusState> {
+    Router::new().route(
+        "/config/metrics",
+        put(update_metrics_config).route_layer(axum::middleware::from_fn_with_state(api_key, require_admin_key)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::test_support::create_test_state;
+    use axum_test::TestServer;
+
+    fn server(state: ServerStatusState) -> TestServer {
+        let api_key = ApiKeyConfig::new(Some("secret".to_string()));
+        let mut server = TestServer::new(create_router(api_key).with_state(state)).unwrap();
+        server.add_header("x-api-key".parse().unwrap(), "secret".parse().unwrap());
+        server
+    }
+
+    #[tokio::test]
+    async fn test_update_metrics_config() {
+        let state = create_test_state();
+        let server = server(state.clone());
+
+        let config = MetricsServiceConfig {
+            collection_interval_seconds: 30,
+            collection_timeout_ms: 5000,
+            collect_network_metrics: false,
+            ..Default::default()
+        };
+        let response = server.put("/config/metrics").json(&config).await;
+        response.assert_status_ok();
+
+        let effective: MetricsServiceConfig = response.json();
+        assert_eq!(effective.collection_interval_seconds, 30);
+        assert_eq!(effective.collection_timeout_ms, 5000);
+
+        let live = state.metrics_service.get_config();
+        assert_eq!(live.collection_interval_seconds, 30);
+        assert!(!live.collect_network_metrics);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_out_of_range_interval() {
+        let state = create_test_state();
+        let server = server(state.clone());
+
+        let config = MetricsServiceConfig {
+            collection_interval_seconds: 0,
+            ..Default::default()
+        };
+        let response = server.put("/config/metrics").json(&config).await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+
+        let body: Value = response.json();
+        assert_eq!(body["error"], "invalid_config");
+        assert_eq!(body["field"], "collection_interval_seconds");
+        assert_eq!(state.metrics_service.get_config().collection_interval_seconds, 5);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_proc_root_override() {
+        let state = create_test_state();
+        let server = server(state.clone());
+
+        let config = MetricsServiceConfig {
+            proc_root: "/etc".to_string(),
+            ..Default::default()
+        };
+        let response = server.put("/config/metrics").json(&config).await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+        assert_eq!(response.json::<Value>()["field"], "proc_root");
+        assert_eq!(state.metrics_service.get_config().proc_root, "/proc");
+    }
+
+    #[tokio::test]
+    async fn test_update_requires_api_key() {
+        let state = create_test_state();
+        let config = MetricsServiceConfig {
+            collection_interval_seconds: 30,
+            ..Default::default()
+        };
+
+        // No key configured: updates are refused outright
+        let server = TestServer::new(create_router(ApiKeyConfig::default()).with_state(state.clone())).unwrap();
+        server.put("/config/metrics").json(&config).await.assert_status(StatusCode::FORBIDDEN);
+
+        // Key configured but not presented
+        let api_key = ApiKeyConfig::new(Some("secret".to_string()));
+        let server = TestServer::new(create_router(api_key).with_state(state.clone())).unwrap();
+        server.put("/config/metrics").json(&config).await.assert_status(StatusCode::UNAUTHORIZED);
+
+        assert_eq!(state.metrics_service.get_config().collection_interval_seconds, 5);
+    }
+}
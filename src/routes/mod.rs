@@ -1,5 +1,17 @@
 // Route handlers for the application
 pub mod api;
+pub mod cache_admin;
+pub mod features;
+pub mod internal_stats_stream;
+pub mod maintenance;
+pub mod metrics_config;
+pub mod openapi;
 pub mod pages;
+pub mod prometheus;
+pub mod readiness;
+pub mod request_metrics;
 pub mod server_status;
-pub mod server_status_stream;
\ No newline at end of file
+pub mod server_status_stream;
+pub mod server_status_ws;
+#[cfg(test)]
+pub(crate) mod test_support;
\ No newline at end of file
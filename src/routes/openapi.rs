@@ -0,0 +1,214 @@
+// OpenAPI document for the metrics API
+// Schemas are derived from the response structs, so the document follows the code
+
+use crate::routes::server_status::{
+    ErrorResponse, ServerHealthResponse, ServerStatusResponse, ServerStatusSummary, StatusQuery,
+};
+use crate::routes::server_status_stream::{MetricsEvent, SseQuery};
+use axum::{response::Json, routing::get, Router};
+use schemars::{generate::SchemaSettings, JsonSchema, SchemaGenerator};
+use serde_json::{json, Value};
+use std::sync::OnceLock;
+
+/// Where component schemas live in the document
+const COMPONENTS_PATH: &str = "/components/schemas";
+
+/// GET /api/openapi.json - OpenAPI 3.1 description of the status endpoints
+pub async fn openapi_document() -> Json<Value> {
+    static DOCUMENT: OnceLock<Value> = OnceLock::new();
+    Json(DOCUMENT.get_or_init(build_document).clone())
+}
+
+/// Assemble the document, collecting every referenced schema under `components`
+pub fn build_document() -> Value {
+    let mut generator = SchemaSettings::draft2020_12()
+        .with(|settings| {
+            settings.definitions_path = COMPONENTS_PATH.into();
+            // The document declares the dialect once, at the top
+            settings.meta_schema = None;
+        })
+        .into_generator();
+
+    let status_response = generator.subschema_for::<ServerStatusResponse>();
+    let health_response = generator.subschema_for::<ServerHealthResponse>();
+    let summary_response = generator.subschema_for::<ServerStatusSummary>();
+    let metrics_event = generator.subschema_for::<MetricsEvent>();
+    let error_response = generator.subschema_for::<ErrorResponse>();
+    let status_parameters = query_parameters::<StatusQuery>(&mut generator);
+    let stream_parameters = query_parameters::<SseQuery>(&mut generator);
+
+    let error = |description: &str| {
+        json!({
+            "description": description,
+            "content": { "application/json": { "schema": error_response } },
+        })
+    };
+
+    json!({
+        "openapi": "3.1.0",
+        "jsonSchemaDialect": "https://json-schema.org/draft/2020-12/schema",
+        "info": {
+            "title": "axum-sse server status API",
+            "version": env!("CARGO_PKG_VERSION"),
+            "description": "Real-time server metrics over REST and Server-Sent Events",
+        },
+        "servers": [{ "url": "/api" }],
+        "paths": {
+            "/server-status": {
+                "get": {
+                    "operationId": "getServerStatus",
+                    "summary": "Current server metrics and server information",
+                    "parameters": status_parameters,
+                    "responses": {
+                        "200": {
+                            "description": "Server status; with envelope=false the body is the bare status data and metadata moves to headers",
+                            "content": { "application/json": { "schema": status_response } },
+                        },
+                        "500": error("Metrics could not be collected"),
+                        "503": error("Metrics are not available yet"),
+                    },
+                },
+            },
+            "/server-status/health": {
+                "get": {
+                    "operationId": "getServerHealth",
+                    "summary": "Health derived from cached metrics",
+                    "responses": {
+                        "200": {
+                            "description": "Health summary",
+                            "content": { "application/json": { "schema": health_response } },
+                        },
+                    },
+                },
+            },
+            "/server-status/summary": {
+                "get": {
+                    "operationId": "getServerSummary",
+                    "summary": "Health light and headline numbers from cached metrics",
+                    "responses": {
+                        "200": {
+                            "description": "Status summary",
+                            "content": { "application/json": { "schema": summary_response } },
+                        },
+                        "500": error("Metrics could not be collected"),
+                    },
+                },
+            },
+            "/server-status-stream": {
+                "get": {
+                    "operationId": "streamServerStatus",
+                    "summary": "Server-Sent Events stream of metrics updates",
+                    "parameters": stream_parameters,
+                    "responses": {
+                        "200": {
+                            "description": "Event stream; the data of each status-update or stale_data event is a MetricsEvent",
+                            "content": {
+                                "text/event-stream": {
+                                    "schema": { "type": "string" },
+                                    "x-event-data": metrics_event,
+                                },
+                            },
+                        },
+                        "400": error("Invalid client_id"),
+                        "429": error("Too many open streams"),
+                    },
+                },
+            },
+        },
+        "components": { "schemas": generator.take_definitions(true) },
+    })
+}
+
+/// OpenAPI query parameters for the fields of `T`, all optional
+fn query_parameters<T: JsonSchema>(generator: &mut SchemaGenerator) -> Vec<Value> {
+    generator.subschema_for::<T>();
+    // Query structs are described by their parameters, not as a component
+    let Some(Value::Object(mut schema)) = generator.definitions_mut().remove(T::schema_name().as_ref()) else {
+        return Vec::new();
+    };
+    let Some(Value::Object(properties)) = schema.remove("properties") else {
+        return Vec::new();
+    };
+
+    properties
+        .into_iter()
+        .map(|(name, mut property)| {
+            let description = property.as_object_mut().and_then(|property| property.remove("description"));
+            json!({
+                "name": name,
+                "in": "query",
+                "required": false,
+                "description": description,
+                "schema": property,
+            })
+        })
+        .collect()
+}
+
+/// Create the OpenAPI router
+pub fn create_router() -> Router {
+    Router::new().route("/openapi.json", get(openapi_document))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum_test::TestServer;
+
+    /// Every `$ref` in `value`
+    fn refs(value: &Value, found: &mut Vec<String>) {
+        match value {
+            Value::Object(map) => {
+                if let Some(Value::String(reference)) = map.get("$ref") {
+                    found.push(reference.clone());
+                }
+                map.values().for_each(|value| refs(value, found));
+            }
+            Value::Array(items) => items.iter().for_each(|value| refs(value, found)),
+            _ => {}
+        }
+    }
+
+    #[tokio::test]
+    async fn test_openapi_document_served() {
+        let server = TestServer::new(create_router()).unwrap();
+        let response = server.get("/openapi.json").await;
+        response.assert_status_ok();
+
+        let document: Value = serde_json::from_str(&response.text()).unwrap();
+        assert_eq!(document["openapi"], "3.1.0");
+        assert!(document["paths"]["/server-status"]["get"].is_object());
+        assert!(document["paths"]["/server-status/health"]["get"].is_object());
+        assert!(document["paths"]["/server-status-stream"]["get"].is_object());
+
+        let schemas = &document["components"]["schemas"];
+        for name in ["ServerStatusResponse", "StatusData", "ServerMetrics", "MetricsEvent", "ServerHealthResponse"] {
+            assert!(schemas[name].is_object(), "missing schema {}", name);
+        }
+    }
+
+    #[test]
+    fn test_openapi_refs_resolve() {
+        let document = build_document();
+        let mut found = Vec::new();
+        refs(&document, &mut found);
+        assert!(!found.is_empty());
+
+        for reference in found {
+            let name = reference
+                .strip_prefix("#/components/schemas/")
+                .unwrap_or_else(|| panic!("unexpected reference {}", reference));
+            assert!(document["components"]["schemas"][name].is_object(), "dangling reference {}", reference);
+        }
+    }
+
+    #[test]
+    fn test_query_parameters_documented() {
+        let document = build_document();
+        let parameters = document["paths"]["/server-status-stream"]["get"]["parameters"].as_array().unwrap();
+        let interval = parameters.iter().find(|parameter| parameter["name"] == "interval").unwrap();
+        assert_eq!(interval["in"], "query");
+        assert!(interval["description"].as_str().unwrap().contains("Update interval"));
+        assert!(document["components"]["schemas"].get("SseQuery").is_none());
+    }
+}
@@ -1,17 +1,17 @@
 // Page route handlers using the static service
 use axum::{
-    response::Html,
-    http::StatusCode,
+    response::{Html, IntoResponse, Response},
+    http::{HeaderMap, StatusCode},
     Extension,
 };
 use std::sync::Arc;
-use crate::services::StaticService;
-use tracing::{info, error, debug};
+use crate::services::{StaticService, StaticServiceError};
+use tracing::{info, error, debug, warn};
 
 /// Handler for the main page (/)
 pub async fn serve_main_page(
     Extension(static_service): Extension<Arc<StaticService>>,
-) -> Result<Html<String>, StatusCode> {
+) -> Result<Html<String>, StaticServiceError> {
     info!("Serving main page");
     
     match static_service.serve_index().await {
@@ -19,32 +19,40 @@ pub async fn serve_main_page(
             info!("Successfully served main page");
             Ok(html)
         }
-        Err(status) => {
-            error!("Failed to serve main page: {}", status);
-            Err(status)
+        Err(error) => {
+            error!("Failed to serve main page: {}", error);
+            Err(error)
         }
     }
 }
 
 /// SPA fallback handler - serves index.html for all unmatched routes
 /// This enables client-side routing for the SPA
+///
+/// When the frontend was not embedded, a diagnostic page is served with 503
+/// instead of the generic fallback index.
 pub async fn serve_spa_fallback(
     uri: axum::http::Uri,
     Extension(static_service): Extension<Arc<StaticService>>,
-) -> Result<Html<String>, StatusCode> {
+) -> Result<Response, StaticServiceError> {
     let path = uri.path();
     info!("SPA fallback for route: {}", path);
+
+    if !static_service.is_healthy() {
+        warn!("SPA fallback for {} with no embedded frontend assets", path);
+        return Ok((StatusCode::SERVICE_UNAVAILABLE, Html(static_service.missing_assets_page())).into_response());
+    }
     
     // For SPA routing, always serve index.html for unmatched routes
     // Let the client-side router handle the actual routing
     match static_service.serve_index().await {
         Ok(html) => {
             info!("Successfully served SPA fallback for: {}", path);
-            Ok(html)
+            Ok(html.into_response())
         }
-        Err(status) => {
-            error!("Failed to serve SPA fallback for {}: {}", path, status);
-            Err(status)
+        Err(error) => {
+            error!("Failed to serve SPA fallback for {}: {}", path, error);
+            Err(error)
         }
     }
 }
@@ -53,17 +61,18 @@ pub async fn serve_spa_fallback(
 pub async fn serve_static_asset(
     axum::extract::Path(path): axum::extract::Path<String>,
     Extension(static_service): Extension<Arc<StaticService>>,
-) -> Result<axum::response::Response, StatusCode> {
+    headers: HeaderMap,
+) -> Result<axum::response::Response, StaticServiceError> {
     info!("Serving static asset: {}", path);
     
-    match static_service.serve_asset(&path).await {
+    match static_service.serve_asset(&path, &headers).await {
         Ok(response) => {
             info!("Successfully served static asset: {}", path);
             Ok(response)
         }
-        Err(status) => {
-            error!("Failed to serve static asset {}: {}", path, status);
-            Err(status)
+        Err(error) => {
+            error!("Failed to serve static asset {}: {}", path, error);
+            Err(error)
         }
     }
 }
@@ -72,7 +81,8 @@ pub async fn serve_static_asset(
 pub async fn serve_app_asset(
     axum::extract::Path(path): axum::extract::Path<String>,
     Extension(static_service): Extension<Arc<StaticService>>,
-) -> Result<axum::response::Response, StatusCode> {
+    headers: HeaderMap,
+) -> Result<axum::response::Response, StaticServiceError> {
     // Reconstruct the full _app path
     let full_path = format!("_app/{}", path);
     info!("Serving static asset: {}", path);
@@ -83,14 +93,14 @@ pub async fn serve_app_asset(
     debug!("Available _app assets (first 5): {:?}", app_assets);
     debug!("Looking for asset: {}", full_path);
     
-    match static_service.serve_asset(&full_path).await {
+    match static_service.serve_asset(&full_path, &headers).await {
         Ok(response) => {
             info!("Successfully served static asset: {}", path);
             Ok(response)
         }
-        Err(status) => {
-            error!("Failed to serve static asset {}: {}", path, status);
-            Err(status)
+        Err(error) => {
+            error!("Failed to serve static asset {}: {}", path, error);
+            Err(error)
         }
     }
 }
@@ -99,18 +109,19 @@ pub async fn serve_app_asset(
 pub async fn serve_fallback_asset(
     uri: axum::http::Uri,
     Extension(static_service): Extension<Arc<StaticService>>,
-) -> Result<axum::response::Response, StatusCode> {
+    headers: HeaderMap,
+) -> Result<axum::response::Response, StaticServiceError> {
     let path = uri.path();
     info!("Serving fallback static asset: {}", path);
     
-    match static_service.serve_asset(path).await {
+    match static_service.serve_asset(path, &headers).await {
         Ok(response) => {
             info!("Successfully served fallback static asset: {}", path);
             Ok(response)
         }
-        Err(status) => {
-            error!("Failed to serve fallback static asset {}: {}", path, status);
-            Err(status)
+        Err(error) => {
+            error!("Failed to serve fallback static asset {}: {}", path, error);
+            Err(error)
         }
     }
 }
@@ -155,4 +166,26 @@ mod tests {
         // This should return 404 since the asset doesn't exist
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_spa_fallback_without_assets_serves_diagnostic() {
+        // What `include_dir!` embeds when the frontend was never built
+        static EMPTY_DIR: include_dir::Dir<'_> = include_dir::Dir::new("", &[]);
+
+        let static_service = Arc::new(StaticService::new().with_assets(&EMPTY_DIR));
+        let app = Router::new()
+            .fallback(serve_spa_fallback)
+            .layer(Extension(static_service));
+
+        let response = app
+            .oneshot(Request::builder().uri("/dashboard").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains("Frontend not built"));
+        assert!(body.contains("npm run build"));
+    }
+}
@@ -0,0 +1,212 @@
+// Prometheus metrics endpoint
+// Renders the current server metrics and service statistics in the text exposition format
+
+use crate::models::{MetricKind, MetricsResponse, ServerMetrics};
+use crate::routes::server_status::ServerStatusState;
+use crate::services::metrics_cache::CacheStats;
+use crate::services::metrics_service::CollectionStats;
+use axum::{
+    extract::State,
+    http::{header, HeaderValue},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use std::fmt::Write;
+use tracing::{debug, warn};
+
+/// Content type of the Prometheus text exposition format
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// Builder for a Prometheus text exposition document
+#[derive(Debug, Default)]
+struct Exposition {
+    out: String,
+}
+
+impl Exposition {
+    /// Append one metric with its HELP and TYPE lines
+    ///
+    /// `field` is the source struct field, used to look up whether the value
+    /// is a counter; counters get the `_total` suffix Prometheus expects.
+    fn metric(&mut self, name: &str, field: &str, help: &str, value: f64) {
+        let kind = MetricKind::of(field);
+        let name = match kind {
+            MetricKind::Counter => format!("{}_total", name),
+            MetricKind::Gauge => name.to_string(),
+        };
+
+        // Writing to a String cannot fail
+        let _ = writeln!(self.out, "# HELP {} {}", name, help);
+        let _ = writeln!(self.out, "# TYPE {} {}", name, kind.as_str());
+        let _ = writeln!(self.out, "{} {}", name, value);
+    }
+
+    fn server_metrics(&mut self, metrics: &ServerMetrics) {
+        let cpu = &metrics.cpu_usage;
+        self.metric("server_cpu_usage_percent", "usage_percentage", "CPU usage in percent", cpu.usage_percentage as f64);
+        self.metric("server_cpu_cores", "core_count", "Number of logical CPU cores", cpu.core_count as f64);
+        self.metric("server_load_average_1m", "one_minute", "One-minute load average", cpu.load_average.one_minute as f64);
+        self.metric("server_load_average_5m", "five_minute", "Five-minute load average", cpu.load_average.five_minute as f64);
+        self.metric("server_load_average_15m", "fifteen_minute", "Fifteen-minute load average", cpu.load_average.fifteen_minute as f64);
+
+        let memory = &metrics.memory_usage;
+        self.metric("server_memory_total_bytes", "total_bytes", "Total system memory in bytes", memory.total_bytes as f64);
+        self.metric("server_memory_used_bytes", "used_bytes", "Used system memory in bytes", memory.used_bytes as f64);
+        self.metric("server_memory_available_bytes", "available_bytes", "Available system memory in bytes", memory.available_bytes as f64);
+        self.metric("server_memory_usage_percent", "usage_percentage", "Memory usage in percent", memory.usage_percentage as f64);
+        self.metric("server_swap_total_bytes", "swap_total_bytes", "Total swap space in bytes", memory.swap_total_bytes as f64);
+        self.metric("server_swap_used_bytes", "swap_used_bytes", "Used swap space in bytes", memory.swap_used_bytes as f64);
+
+        let network = &metrics.network_metrics;
+        self.metric("server_network_sent_bytes", "bytes_sent", "Bytes transmitted on counted interfaces", network.bytes_sent as f64);
+        self.metric("server_network_received_bytes", "bytes_received", "Bytes received on counted interfaces", network.bytes_received as f64);
+        self.metric("server_network_sent_packets", "packets_sent", "Packets transmitted on counted interfaces", network.packets_sent as f64);
+        self.metric("server_network_received_packets", "packets_received", "Packets received on counted interfaces", network.packets_received as f64);
+        self.metric("server_network_active_connections", "active_connections", "Established TCP connections", network.active_connections as f64);
+
+        if let Some(disk) = &metrics.disk_metrics {
+            self.metric("server_disk_total_bytes", "total_bytes", "Total capacity of mounted filesystems in bytes", disk.total_bytes as f64);
+            self.metric("server_disk_used_bytes", "used_bytes", "Used space on mounted filesystems in bytes", disk.used_bytes as f64);
+            self.metric("server_disk_available_bytes", "available_bytes", "Available space on mounted filesystems in bytes", disk.available_bytes as f64);
+        }
+
+        self.metric("server_uptime_seconds", "uptime", "System uptime in seconds", metrics.uptime.as_secs_f64());
+    }
+
+    fn cache_stats(&mut self, stats: &CacheStats) {
+        self.metric("cache_requests", "total_requests", "Metrics cache lookups", stats.total_requests as f64);
+        self.metric("cache_hits", "cache_hits", "Metrics cache hits", stats.cache_hits as f64);
+        self.metric("cache_misses", "cache_misses", "Metrics cache misses", stats.cache_misses as f64);
+        self.metric("cache_evictions", "evictions", "Metrics cache evictions", stats.evictions as f64);
+        self.metric("cache_background_refreshes", "background_refreshes", "Background cache refreshes", stats.background_refreshes as f64);
+        self.metric("cache_failed_refreshes", "failed_refreshes", "Failed background cache refreshes", stats.failed_refreshes as f64);
+        self.metric("cache_entries", "current_entries", "Entries currently cached", stats.current_entries as f64);
+        self.metric("cache_hit_ratio", "hit_ratio", "Share of cache lookups served from cache (0-1)", stats.hit_ratio);
+    }
+
+    fn collection_stats(&mut self, stats: &CollectionStats) {
+        self.metric("collections", "total_collections", "Metrics collections attempted", stats.total_collections as f64);
+        self.metric("collections_successful", "successful_collections", "Metrics collections that produced data", stats.successful_collections as f64);
+        self.metric("collections_failed", "failed_collections", "Metrics collections that failed", stats.failed_collections as f64);
+        self.metric("collection_average_duration_ms", "average_collection_time_ms", "Average collection time in milliseconds", stats.average_collection_time_ms);
+    }
+}
+
+/// GET /api/metrics - Current metrics in the Prometheus text exposition format
+///
+/// Cache and collection statistics are always exported; server metrics are
+/// left out when collection fails, with `server_metrics_available` set to 0.
+pub async fn prometheus_metrics(State(state): State<ServerStatusState>) -> Response {
+    debug!("GET /api/metrics");
+
+    let metrics = match state.metrics_cache.get_metrics(None).await {
+        MetricsResponse::Ok(metrics) | MetricsResponse::PartialData { data: metrics, .. } => Some(metrics),
+        MetricsResponse::Error(error) => {
+            warn!("Exporting Prometheus metrics without server metrics: {}", error);
+            None
+        }
+    };
+
+    let mut exposition = Exposition::default();
+    exposition.metric(
+        "server_metrics_available",
+        "server_metrics_available",
+        "Whether server metrics could be collected (1) or not (0)",
+        if metrics.is_some() { 1.0 } else { 0.0 },
+    );
+    if let Some(metrics) = &metrics {
+        exposition.server_metrics(metrics);
+    }
+    exposition.cache_stats(&state.metrics_cache.get_stats());
+    exposition.collection_stats(&state.metrics_service.get_stats().await);
+
+    (
+        [(header::CONTENT_TYPE, HeaderValue::from_static(PROMETHEUS_CONTENT_TYPE))],
+        exposition.out,
+    )
+        .into_response()
+}
+
+/// Create the Prometheus metrics router
+pub fn create_router() -> Router<ServerStatusState> {
+    Router::new().route("/metrics", get(prometheus_metrics))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::test_support::create_test_state;
+    use axum_test::TestServer;
+    use std::collections::HashMap;
+
+    /// Parse an exposition into sample values and declared types, checking HELP/TYPE precede each sample
+    fn parse_exposition(text: &str) -> (HashMap<String, f64>, HashMap<String, String>) {
+        let mut samples = HashMap::new();
+        let mut types = HashMap::new();
+        let mut helped = Vec::new();
+
+        for line in text.lines() {
+            if let Some(rest) = line.strip_prefix("# HELP ") {
+                helped.push(rest.split_whitespace().next().unwrap().to_string());
+            } else if let Some(rest) = line.strip_prefix("# TYPE ") {
+                let (name, kind) = rest.split_once(' ').expect("TYPE line needs a name and type");
+                types.insert(name.to_string(), kind.to_string());
+            } else {
+                let (name, value) = line.split_once(' ').expect("Sample line needs a name and value");
+                assert!(helped.contains(&name.to_string()), "{} has no HELP line", name);
+                assert!(types.contains_key(name), "{} has no TYPE line", name);
+                samples.insert(name.to_string(), value.parse::<f64>().expect("Sample value must be numeric"));
+            }
+        }
+
+        (samples, types)
+    }
+
+    #[tokio::test]
+    async fn test_prometheus_exposition() {
+        let state = create_test_state();
+        state.metrics_service.initialize().await.unwrap();
+        let server = TestServer::new(create_router().with_state(state)).unwrap();
+
+        let response = server.get("/metrics").await;
+        response.assert_status_ok();
+        assert_eq!(response.header("content-type"), PROMETHEUS_CONTENT_TYPE);
+
+        let (samples, types) = parse_exposition(&response.text());
+        for name in [
+            "server_metrics_available",
+            "server_cpu_usage_percent",
+            "server_memory_used_bytes",
+            "server_memory_usage_percent",
+            "server_network_sent_bytes_total",
+            "server_uptime_seconds",
+            "cache_hits_total",
+            "cache_hit_ratio",
+            "collections_total",
+            "collections_failed_total",
+        ] {
+            assert!(samples.contains_key(name), "missing metric {}", name);
+        }
+
+        assert_eq!(samples["server_metrics_available"], 1.0);
+        assert!(samples["server_memory_used_bytes"] > 0.0);
+        assert_eq!(types["server_network_sent_bytes_total"], "counter");
+        assert_eq!(types["cache_hit_ratio"], "gauge");
+        assert_eq!(types["server_cpu_usage_percent"], "gauge");
+    }
+
+    #[tokio::test]
+    async fn test_exports_stats_when_collection_fails() {
+        let state = create_test_state();
+        state.metrics_service.fail_next_collection(
+            crate::models::MetricsCollectionError::system_unavailable("test failure"),
+        );
+        let server = TestServer::new(create_router().with_state(state)).unwrap();
+
+        let (samples, _) = parse_exposition(&server.get("/metrics").await.text());
+        assert_eq!(samples["server_metrics_available"], 0.0);
+        assert!(!samples.contains_key("server_cpu_usage_percent"));
+        assert_eq!(samples["collections_failed_total"], 1.0);
+    }
+}
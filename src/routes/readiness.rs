@@ -0,0 +1,134 @@
+// Readiness probe
+// Separate from /api/health (liveness): reports whether the server has metrics to serve yet
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::Json,
+    routing::get,
+    Router,
+};
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::info;
+
+/// Shared readiness flag, set once the metrics service is initialized and the
+/// first collection has landed in the cache
+///
+/// An optional startup grace period keeps the probe not ready for a while
+/// after start even once the flag is set: early CPU readings need two
+/// refreshes to be meaningful.
+#[derive(Debug, Clone)]
+pub struct Readiness {
+    ready: Arc<AtomicBool>,
+    not_before: Instant,
+}
+
+impl Default for Readiness {
+    fn default() -> Self {
+        Self {
+            ready: Arc::default(),
+            not_before: Instant::now(),
+        }
+    }
+}
+
+impl Readiness {
+    /// A flag that starts out not ready
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stay not ready until `grace` has passed since the flag was created
+    pub fn with_grace_period(mut self, grace: Duration) -> Self {
+        self.not_before = Instant::now() + grace;
+        self
+    }
+
+    /// A flag that is already ready, for routers built without a warm-up
+    pub fn ready() -> Self {
+        let readiness = Self::new();
+        readiness.mark_ready();
+        readiness
+    }
+
+    pub fn mark_ready(&self) {
+        if !self.ready.swap(true, Ordering::AcqRel) {
+            info!("✅ Server is ready");
+        }
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::Acquire) && Instant::now() >= self.not_before
+    }
+}
+
+/// GET /api/readiness - 200 once metrics can be served, 503 until then
+pub async fn readiness_probe(State(readiness): State<Readiness>) -> (StatusCode, Json<Value>) {
+    let (status, label) = if readiness.is_ready() {
+        (StatusCode::OK, "ready")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "not_ready")
+    };
+
+    (status, Json(json!({ "status": label, "timestamp": Utc::now() })))
+}
+
+/// Create the readiness probe router
+pub fn create_router() -> Router<Readiness> {
+    Router::new().route("/readiness", get(readiness_probe))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum_test::TestServer;
+
+    #[tokio::test]
+    async fn test_not_ready_until_marked() {
+        let readiness = Readiness::new();
+        let server = TestServer::new(create_router().with_state(readiness.clone())).unwrap();
+
+        let response = server.get("/readiness").await;
+        response.assert_status(StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.json::<Value>()["status"], "not_ready");
+
+        readiness.mark_ready();
+
+        let response = server.get("/readiness").await;
+        response.assert_status_ok();
+        assert_eq!(response.json::<Value>()["status"], "ready");
+    }
+
+    #[tokio::test]
+    async fn test_ready_flag() {
+        let server = TestServer::new(create_router().with_state(Readiness::ready())).unwrap();
+        server.get("/readiness").await.assert_status_ok();
+    }
+
+    #[tokio::test]
+    async fn test_not_ready_during_grace_period() {
+        let readiness = Readiness::new().with_grace_period(Duration::from_millis(300));
+        readiness.mark_ready();
+        let server = TestServer::new(create_router().with_state(readiness)).unwrap();
+
+        // Collection has succeeded, but the grace window hasn't passed
+        server.get("/readiness").await.assert_status(StatusCode::SERVICE_UNAVAILABLE);
+
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        server.get("/readiness").await.assert_status_ok();
+    }
+
+    #[tokio::test]
+    async fn test_grace_period_still_needs_collection() {
+        let readiness = Readiness::new().with_grace_period(Duration::ZERO);
+        let server = TestServer::new(create_router().with_state(readiness.clone())).unwrap();
+
+        server.get("/readiness").await.assert_status(StatusCode::SERVICE_UNAVAILABLE);
+        readiness.mark_ready();
+        server.get("/readiness").await.assert_status_ok();
+    }
+}
@@ -0,0 +1,28 @@
+// Request latency endpoint
+// Exposes the per-endpoint durations recorded by the request metrics middleware
+
+use crate::middleware::logging::{EndpointStats, RequestMetrics};
+use axum::{extract::State, response::Json, routing::get, Router};
+use serde::{Deserialize, Serialize};
+
+/// Response body for GET /api/request-metrics
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RequestMetricsResponse {
+    /// Requests recorded across all endpoints
+    pub total_requests: u64,
+    pub endpoints: Vec<EndpointStats>,
+}
+
+/// GET /api/request-metrics - Request count and latency per endpoint
+pub async fn get_request_metrics(State(metrics): State<RequestMetrics>) -> Json<RequestMetricsResponse> {
+    let endpoints = metrics.snapshot();
+    Json(RequestMetricsResponse {
+        total_requests: endpoints.iter().map(|endpoint| endpoint.count).sum(),
+        endpoints,
+    })
+}
+
+/// Create router for the request metrics endpoint
+pub fn create_router() -> Router<RequestMetrics> {
+    Router::new().route("/request-metrics", get(get_request_metrics))
+}
@@ -2,26 +2,33 @@
 // Provides REST endpoints for server metrics and status information
 
 use crate::models::{
-    StatusData, ServerMetrics, MetricsCollectionError, MetricsResponse,
-    ServerInfo, MetricsValidationError
+    StatusData, DataQuality, ServerMetrics, HealthStatus, HealthThresholds, MemoryMetrics, NetworkMetrics, CpuScale, MetricSource,
+    MetricsCollectionError, MetricsResponse, ServerInfo, MetricsValidationError
 };
-#[cfg(test)]
-use crate::models::OsInfo;
+use crate::models::server_metrics::duration_seconds;
+use crate::routes::server_status_stream::SseStreamConfig;
+use crate::services::metrics_service::{BenchmarkStats, RECENT_ERRORS_CAPACITY};
 use crate::services::{MetricsCache, MetricsService};
+use crate::services::tasks::{spawn_named, METRICS_WARMUP_TASK};
 use axum::{
     extract::{Query, State},
-    http::StatusCode,
+    http::{header, HeaderValue, StatusCode},
     response::{IntoResponse, Json, Response},
     routing::get,
     Router,
 };
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use std::collections::BTreeMap;
+use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
 use tracing::{debug, warn, error, instrument};
 
 /// Query parameters for server status endpoint
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct StatusQuery {
     /// Include detailed metrics (default: true)
     pub detailed: Option<bool>,
@@ -29,10 +36,14 @@ pub struct StatusQuery {
     pub cache_key: Option<String>,
     /// Force fresh collection bypassing cache
     pub force_refresh: Option<bool>,
+    /// CPU usage scale: average (0-100) or total (0-N×100)
+    pub cpu_scale: Option<CpuScale>,
+    /// Wrap data in the response envelope (default: true)
+    pub envelope: Option<bool>,
 }
 
 /// Response format for server status endpoint
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ServerStatusResponse {
     /// Indicates if the request was successful
     pub success: bool,
@@ -40,10 +51,42 @@ pub struct ServerStatusResponse {
     pub data: StatusData,
     /// Additional metadata about the response
     pub metadata: ResponseMetadata,
+    /// Network figures derived from the metrics, so clients need not recompute them
+    #[serde(default)]
+    pub network_summary: NetworkSummary,
+    /// Server uptime as a plain number of seconds, alongside the `{secs, nanos}` form in `data`
+    #[serde(default, with = "duration_seconds")]
+    #[schemars(with = "u64")]
+    pub uptime_seconds: Duration,
+}
+
+/// Derived view of the network metrics in a status response
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct NetworkSummary {
+    /// Activity level from the active connection count (Idle, Low, Normal, High, Very High, Critical)
+    pub activity_level: String,
+    /// Bytes sent per byte received; null without traffic or when only sending
+    pub send_receive_ratio: Option<f64>,
+    /// Bytes sent plus bytes received
+    pub total_bytes: u64,
+    /// Human-readable total, e.g. "1.50 MB (1200 packets)"
+    pub format_total: String,
+}
+
+impl From<&NetworkMetrics> for NetworkSummary {
+    fn from(network: &NetworkMetrics) -> Self {
+        Self {
+            activity_level: network.activity_level().to_string(),
+            // JSON has no infinity, so a send-only interface reports no ratio
+            send_receive_ratio: network.send_receive_ratio().filter(|ratio| ratio.is_finite()),
+            total_bytes: network.total_bytes(),
+            format_total: network.format_total(),
+        }
+    }
 }
 
 /// Metadata included with API responses
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ResponseMetadata {
     /// Timestamp when response was generated
     pub response_timestamp: DateTime<Utc>,
@@ -55,6 +98,12 @@ pub struct ResponseMetadata {
     pub api_version: String,
     /// Any warnings or partial data indicators
     pub warnings: Vec<String>,
+    /// Where each metric category came from (sysinfo, proc, cache or default)
+    #[serde(default)]
+    pub sources: BTreeMap<String, MetricSource>,
+    /// Data is a cached sample served because a fresh collection failed
+    #[serde(default)]
+    pub stale: bool,
 }
 
 impl Default for ResponseMetadata {
@@ -65,35 +114,88 @@ impl Default for ResponseMetadata {
             collection_time_ms: None,
             api_version: "1.0".to_string(),
             warnings: vec![],
+            sources: BTreeMap::new(),
+            stale: false,
         }
     }
 }
 
+/// Response of the health check endpoint
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ServerHealthResponse {
+    /// Overall health: healthy, warning or critical
+    pub status: String,
+    /// When the check ran
+    pub timestamp: DateTime<Utc>,
+    /// Metrics cache effectiveness
+    pub cache: HealthCacheSummary,
+    /// Collection counters of the metrics service
+    pub metrics_service: HealthServiceSummary,
+    /// API version
+    pub api_version: String,
+}
+
+/// Response of the summary endpoint: a health light and headline numbers
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ServerStatusSummary {
+    /// Overall health: healthy, warning or critical
+    pub health: String,
+    /// Average CPU usage across cores (0-100)
+    pub cpu_percent: f32,
+    /// Memory usage (0-100)
+    pub memory_percent: f32,
+    /// Time since system start in seconds
+    pub uptime_seconds: u64,
+    /// Server hostname
+    pub hostname: String,
+}
+
+/// Cache figures reported by the health check
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct HealthCacheSummary {
+    /// Fraction of requests served from cache (0-1)
+    pub hit_ratio: f64,
+    /// Entries currently cached
+    pub entries: usize,
+}
+
+/// Collection figures reported by the health check
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct HealthServiceSummary {
+    pub successful_collections: u64,
+    pub failed_collections: u64,
+    pub average_collection_time_ms: f64,
+}
+
 /// Error response format
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct ErrorResponse {
     pub error: String,
     pub error_type: String,
+    /// Stable machine-readable error code
+    pub code: String,
     pub timestamp: DateTime<Utc>,
     pub api_version: String,
     pub details: Option<serde_json::Value>,
 }
 
 impl ErrorResponse {
-    fn new(error: &str, error_type: &str) -> Self {
+    pub(crate) fn new(error: &str, error_type: &str, code: &str) -> Self {
         Self {
             error: error.to_string(),
             error_type: error_type.to_string(),
+            code: code.to_string(),
             timestamp: Utc::now(),
             api_version: "1.0".to_string(),
             details: None,
         }
     }
 
-    fn with_details(error: &str, error_type: &str, details: serde_json::Value) -> Self {
+    fn with_details(error: &str, error_type: &str, code: &str, details: serde_json::Value) -> Self {
         Self {
             error: error.to_string(),
             error_type: error_type.to_string(),
+            code: code.to_string(),
             timestamp: Utc::now(),
             api_version: "1.0".to_string(),
             details: Some(details),
@@ -101,12 +203,60 @@ impl ErrorResponse {
     }
 }
 
+/// Configuration for the server status endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusEndpointConfig {
+    /// Answer 503 `warming_up` until the first collection completes instead
+    /// of blocking the request on a cold collection
+    #[serde(default)]
+    pub warming_up_response: bool,
+    /// `Retry-After` seconds sent with the warming-up response
+    pub warming_up_retry_after_seconds: u32,
+}
+
+impl Default for StatusEndpointConfig {
+    fn default() -> Self {
+        Self {
+            warming_up_response: false,
+            warming_up_retry_after_seconds: 2,
+        }
+    }
+}
+
+impl StatusEndpointConfig {
+    /// Load configuration from `STATUS_*` environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(value) = std::env::var("STATUS_WARMING_UP_RESPONSE") {
+            match value.parse() {
+                Ok(enabled) => config.warming_up_response = enabled,
+                Err(e) => warn!("Ignoring STATUS_WARMING_UP_RESPONSE: {}", e),
+            }
+        }
+
+        config
+    }
+}
+
+/// Default cap on concurrent metrics SSE streams
+pub const DEFAULT_MAX_SSE_CONNECTIONS: usize = 1000;
+
 /// Application state shared with route handlers
 #[derive(Clone)]
 pub struct ServerStatusState {
     pub metrics_cache: Arc<MetricsCache>,
     pub metrics_service: Arc<MetricsService>,
     pub server_info: ServerInfo,
+    pub sse_config: SseStreamConfig,
+    pub status_config: StatusEndpointConfig,
+    pub health_thresholds: HealthThresholds,
+    /// Most concurrent metrics SSE streams accepted before answering 503
+    pub max_sse_connections: usize,
+    /// Metrics SSE streams currently open
+    pub sse_connections: Arc<AtomicUsize>,
+    /// Fired once on server shutdown so open streams can say goodbye and end
+    pub shutdown: broadcast::Sender<()>,
 }
 
 impl ServerStatusState {
@@ -119,8 +269,66 @@ impl ServerStatusState {
             metrics_cache,
             metrics_service,
             server_info,
+            sse_config: SseStreamConfig::default(),
+            status_config: StatusEndpointConfig::default(),
+            health_thresholds: HealthThresholds::default(),
+            max_sse_connections: DEFAULT_MAX_SSE_CONNECTIONS,
+            sse_connections: Arc::new(AtomicUsize::new(0)),
+            shutdown: broadcast::channel(1).0,
         }
     }
+
+    /// Set the SSE stream configuration
+    pub fn with_sse_config(mut self, sse_config: SseStreamConfig) -> Self {
+        self.sse_config = sse_config;
+        self
+    }
+
+    /// Set the server status endpoint configuration
+    pub fn with_status_config(mut self, status_config: StatusEndpointConfig) -> Self {
+        self.status_config = status_config;
+        self
+    }
+
+    /// Set the usage levels that mark health Warning or Critical
+    pub fn with_health_thresholds(mut self, health_thresholds: HealthThresholds) -> Self {
+        self.health_thresholds = health_thresholds;
+        self
+    }
+
+    /// Set the cap on concurrent metrics SSE streams
+    pub fn with_max_sse_connections(mut self, max_sse_connections: usize) -> Self {
+        self.max_sse_connections = max_sse_connections;
+        self
+    }
+
+    /// Use a shutdown channel owned by the server's graceful-shutdown path
+    pub fn with_shutdown(mut self, shutdown: broadcast::Sender<()>) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+}
+
+/// 503 `warming_up` response, kicking off the first collection in the background
+fn warming_up_response(state: &ServerStatusState, cache_key: Option<String>) -> Response {
+    let metrics_cache = Arc::clone(&state.metrics_cache);
+    spawn_named(METRICS_WARMUP_TASK, async move {
+        // Single-flight in the cache collapses concurrent warm-ups into one collection
+        metrics_cache.get_metrics(cache_key).await;
+    });
+
+    let retry_after = state.status_config.warming_up_retry_after_seconds;
+    debug!("No metrics collected yet, answering warming_up (retry after {}s)", retry_after);
+
+    let body = ErrorResponse::with_details(
+        "Metrics are warming up; the first collection has not completed",
+        "warming_up",
+        "METRICS_WARMING_UP",
+        serde_json::json!({ "retry_after_seconds": retry_after }),
+    );
+    let mut response = (StatusCode::SERVICE_UNAVAILABLE, Json(body)).into_response();
+    response.headers_mut().insert(header::RETRY_AFTER, HeaderValue::from(retry_after));
+    response
 }
 
     /// GET /api/server-status - Get current server status and metrics
@@ -128,9 +336,13 @@ impl ServerStatusState {
 pub async fn get_server_status(
     Query(params): Query<StatusQuery>,
     State(state): State<ServerStatusState>,
-) -> Result<Json<ServerStatusResponse>, ServerStatusError> {
+) -> Result<Response, ServerStatusError> {
     debug!("GET /api/server-status - params: {:?}", params);
 
+    if state.status_config.warming_up_response && !state.metrics_service.has_collected() {
+        return Ok(warming_up_response(&state, params.cache_key));
+    }
+
     let start_time = std::time::Instant::now();
     let detailed = params.detailed.unwrap_or(true);
     let force_refresh = params.force_refresh.unwrap_or(false);
@@ -145,6 +357,17 @@ pub async fn get_server_status(
 
     let collection_time = start_time.elapsed().as_millis() as u64;
 
+    let stale = matches!(
+        &metrics_result,
+        MetricsResponse::PartialData { errors, .. }
+            if errors.iter().any(|e| matches!(e, MetricsCollectionError::StaleData { .. }))
+    );
+
+    let data_quality = match &metrics_result {
+        MetricsResponse::PartialData { errors, .. } => DataQuality::from_errors(errors),
+        _ => DataQuality::Complete,
+    };
+
     // Process metrics result
     let (server_metrics, mut warnings) = match metrics_result {
         MetricsResponse::Ok(metrics) => (metrics, vec![]),
@@ -164,14 +387,20 @@ pub async fn get_server_status(
     };
 
     // Create simplified metrics if detailed=false
-    let final_metrics = if detailed {
+    let mut final_metrics = if detailed {
         server_metrics
     } else {
         create_simplified_metrics(server_metrics)
     };
 
+    // Apply the requested CPU scale, overriding the configured one
+    if let Some(scale) = params.cpu_scale {
+        final_metrics.cpu_usage = final_metrics.cpu_usage.to_scale(scale);
+    }
+
     // Check for stale timestamps and add warnings
-    if let Some(age_seconds) = final_metrics.is_timestamp_stale() {
+    let collection_interval = state.metrics_service.get_config().collection_interval_seconds;
+    if let Some(age_seconds) = final_metrics.is_timestamp_stale_for_interval(collection_interval) {
         warnings.push(format!("Timestamp is stale: {} seconds old", age_seconds));
     }
 
@@ -181,13 +410,24 @@ pub async fn get_server_status(
         warnings.push(format!("Validation warning: {}", validation_error));
     }
 
+    // Soft check for memory that used + available does not account for
+    let tolerance = state.metrics_service.get_config().memory_discrepancy_tolerance_percent;
+    if let Some(warning) = memory_accounting_warning(&final_metrics.memory_usage, tolerance) {
+        warn!("{}", warning);
+        warnings.push(warning);
+    }
+
+    let mut sources = final_metrics.sources.clone();
+    let network_summary = NetworkSummary::from(&final_metrics.network_metrics);
+    let uptime_seconds = final_metrics.uptime;
+
     // Create status data
     let status_data = match StatusData::new(
         final_metrics,
         state.metrics_service.get_config().collection_interval_seconds,
         state.server_info.clone(),
     ) {
-        Ok(data) => data,
+        Ok(data) => data.with_data_quality(data_quality),
         Err(validation_error) => {
             warn!("StatusData validation failed: {}", validation_error);
             return Err(ServerStatusError::Internal(format!("StatusData validation failed: {}", validation_error)));
@@ -197,6 +437,15 @@ pub async fn get_server_status(
     // Check if data came from cache
     let cached = !force_refresh && collection_time < 50; // Heuristic: < 50ms likely cached
 
+    // Collected values served from cache are reported as such; defaults stay defaults
+    if cached {
+        for source in sources.values_mut() {
+            if *source != MetricSource::Default {
+                *source = MetricSource::Cache;
+            }
+        }
+    }
+
     // Create response metadata
     let metadata = ResponseMetadata {
         response_timestamp: Utc::now(),
@@ -204,12 +453,16 @@ pub async fn get_server_status(
         collection_time_ms: Some(collection_time),
         api_version: "1.0".to_string(),
         warnings,
+        sources,
+        stale,
     };
 
     let response = ServerStatusResponse {
         success: true,
         data: status_data,
         metadata,
+        network_summary,
+        uptime_seconds,
     };
 
     debug!(
@@ -217,7 +470,55 @@ pub async fn get_server_status(
         cached, collection_time
     );
 
-    Ok(Json(response))
+    if params.envelope.unwrap_or(true) {
+        Ok(Json(response).into_response())
+    } else {
+        Ok(unwrapped_status_response(response))
+    }
+}
+
+/// Return bare `StatusData`, carrying the metadata in response headers instead
+fn unwrapped_status_response(response: ServerStatusResponse) -> Response {
+    let metadata = response.metadata;
+    let mut http_response = Json(response.data).into_response();
+    let headers = http_response.headers_mut();
+
+    let mut insert = |name: &'static str, value: String| {
+        match HeaderValue::from_str(&value) {
+            Ok(value) => {
+                headers.insert(name, value);
+            }
+            Err(_) => warn!("Skipping non-ASCII metadata header {}", name),
+        }
+    };
+
+    insert("X-Cached", metadata.cached.to_string());
+    if metadata.stale {
+        insert("X-Stale", "true".to_string());
+    }
+    if let Some(collection_time_ms) = metadata.collection_time_ms {
+        insert("X-Collection-Time-Ms", collection_time_ms.to_string());
+    }
+    insert("X-Api-Version", metadata.api_version);
+    insert("X-Response-Timestamp", metadata.response_timestamp.to_rfc3339());
+    if !metadata.warnings.is_empty() {
+        insert("X-Warnings", metadata.warnings.join("; "));
+    }
+
+    http_response
+}
+
+/// Build a warning when used + available falls short of total by more than the tolerance
+fn memory_accounting_warning(memory: &MemoryMetrics, tolerance_percent: f32) -> Option<String> {
+    if !memory.has_accounting_discrepancy(tolerance_percent) {
+        return None;
+    }
+
+    Some(format!(
+        "Memory accounting warning: {:.1}% of total memory is not reported as used or available (tolerance {:.1}%)",
+        memory.unaccounted_percentage(),
+        tolerance_percent
+    ))
 }
 
 /// Create simplified metrics for non-detailed requests
@@ -228,11 +529,14 @@ fn create_simplified_metrics(full_metrics: ServerMetrics) -> ServerMetrics {
         cpu_usage: crate::models::CpuMetrics {
             usage_percentage: full_metrics.cpu_usage.usage_percentage,
             core_count: full_metrics.cpu_usage.core_count,
+            scale: full_metrics.cpu_usage.scale,
             load_average: crate::models::cpu_metrics::LoadAverage {
                 one_minute: full_metrics.cpu_usage.load_average.one_minute,
                 five_minute: 0.0, // Remove 5min load for simplified view
                 fifteen_minute: 0.0, // Remove 15min load for simplified view
             },
+            core_usage_percentages: Vec::new(), // Remove per-core detail for simplified view
+            temperature_celsius: full_metrics.cpu_usage.temperature_celsius,
         },
         uptime: full_metrics.uptime,
         network_metrics: crate::models::NetworkMetrics {
@@ -241,7 +545,17 @@ fn create_simplified_metrics(full_metrics: ServerMetrics) -> ServerMetrics {
             packets_sent: 0, // Remove packet details for simplified view
             packets_received: 0, // Remove packet details for simplified view
             active_connections: full_metrics.network_metrics.active_connections,
+            counter_reset: full_metrics.network_metrics.counter_reset,
+            rate_bytes_sent_per_sec: full_metrics.network_metrics.rate_bytes_sent_per_sec,
+            rate_bytes_received_per_sec: full_metrics.network_metrics.rate_bytes_received_per_sec,
         },
+        sources: full_metrics.sources,
+        gpu_metrics: full_metrics.gpu_metrics,
+        disk_metrics: full_metrics.disk_metrics.map(|disk| crate::models::DiskMetrics {
+            mounts: Vec::new(), // Remove per-mount detail for simplified view
+            ..disk
+        }),
+        process_metrics: full_metrics.process_metrics,
     }
 }
 
@@ -249,25 +563,19 @@ fn create_simplified_metrics(full_metrics: ServerMetrics) -> ServerMetrics {
 #[instrument(skip(state))]
 pub async fn get_server_health(
     State(state): State<ServerStatusState>,
-) -> Result<Json<serde_json::Value>, ServerStatusError> {
+) -> Result<Json<ServerHealthResponse>, ServerStatusError> {
     debug!("GET /api/server-status/health");
 
     // Quick health check - try to get cached metrics
     let metrics_result = state.metrics_cache.get_metrics(Some("health_check".to_string())).await;
     
+    // Fast path: derive health straight from the metrics without building StatusData
     let health_status = match metrics_result {
         MetricsResponse::Ok(metrics) => {
-            match StatusData::new(
-                metrics,
-                state.metrics_service.get_config().collection_interval_seconds,
-                state.server_info.clone(),
-            ) {
-                Ok(status_data) => match status_data.get_health_status() {
-                    crate::models::HealthStatus::Healthy => "healthy",
-                    crate::models::HealthStatus::Warning => "warning", 
-                    crate::models::HealthStatus::Critical => "critical",
-                },
-                Err(_) => "warning", // Validation failed, but we have metrics
+            if metrics.validate().is_ok() {
+                state.health_thresholds.evaluate_metrics(&metrics).as_str()
+            } else {
+                "warning" // Validation failed, but we have metrics
             }
         }
         MetricsResponse::PartialData { .. } => "warning",
@@ -277,38 +585,224 @@ pub async fn get_server_health(
     let cache_stats = state.metrics_cache.get_stats();
     let service_stats = state.metrics_service.get_stats().await;
 
-    let health_response = serde_json::json!({
-        "status": health_status,
-        "timestamp": Utc::now(),
-        "cache": {
-            "hit_ratio": cache_stats.hit_ratio,
-            "entries": cache_stats.current_entries,
+    let health_response = ServerHealthResponse {
+        status: health_status.to_string(),
+        timestamp: Utc::now(),
+        cache: HealthCacheSummary {
+            hit_ratio: cache_stats.hit_ratio,
+            entries: cache_stats.current_entries,
         },
-        "metrics_service": {
-            "successful_collections": service_stats.successful_collections,
-            "failed_collections": service_stats.failed_collections,
-            "average_collection_time_ms": service_stats.average_collection_time_ms,
+        metrics_service: HealthServiceSummary {
+            successful_collections: service_stats.successful_collections,
+            failed_collections: service_stats.failed_collections,
+            average_collection_time_ms: service_stats.average_collection_time_ms,
         },
-        "api_version": "1.0"
-    });
+        api_version: "1.0".to_string(),
+    };
 
     debug!("Health check returned status: {}", health_status);
     Ok(Json(health_response))
 }
 
+/// GET /api/server-status/summary - Health and headline numbers for simple dashboards
+///
+/// Reads the cached metrics directly, without building `StatusData`.
+#[instrument(skip(state))]
+pub async fn get_server_summary(
+    State(state): State<ServerStatusState>,
+) -> Result<Json<ServerStatusSummary>, ServerStatusError> {
+    debug!("GET /api/server-status/summary");
+
+    let (metrics, health) = match state.metrics_cache.get_metrics(None).await {
+        MetricsResponse::Ok(metrics) => {
+            let health = state.health_thresholds.evaluate_metrics(&metrics);
+            (metrics, health)
+        }
+        // Missing categories are at least a warning, as on the health endpoint
+        MetricsResponse::PartialData { data, .. } => {
+            let health = state.health_thresholds.evaluate_metrics(&data).max(HealthStatus::Warning);
+            (data, health)
+        }
+        MetricsResponse::Error(error) => {
+            error!("Failed to collect metrics for summary: {}", error);
+            return Err(ServerStatusError::MetricsCollection(error));
+        }
+    };
+
+    Ok(Json(ServerStatusSummary {
+        health: health.as_str().to_string(),
+        cpu_percent: metrics.cpu_usage.per_core_usage(),
+        memory_percent: metrics.memory_usage.usage_percentage,
+        uptime_seconds: metrics.uptime.as_secs(),
+        hostname: state.server_info.hostname.clone(),
+    }))
+}
+
+/// GET /api/server-status/gpu - Per-device GPU metrics
+///
+/// Returns 503 unless the server was built with the `gpu` feature and GPU
+/// collection is enabled in the metrics service config.
+#[instrument(skip(state))]
+pub async fn get_gpu_metrics(
+    State(state): State<ServerStatusState>,
+) -> Result<Json<serde_json::Value>, ServerStatusError> {
+    debug!("GET /api/server-status/gpu");
+
+    let gpus = state.metrics_service.collect_gpu_metrics().await
+        .map_err(ServerStatusError::MetricsCollection)?;
+
+    Ok(Json(serde_json::json!({
+        "device_count": gpus.len(),
+        "gpus": gpus,
+        "timestamp": Utc::now(),
+        "api_version": "1.0"
+    })))
+}
+
+/// GET /api/server-status/sensors - Temperature sensors and fan speeds
+///
+/// Returns 503 unless sensor collection is enabled; hosts without sensors
+/// get empty lists.
+#[instrument(skip(state))]
+pub async fn get_sensor_metrics(
+    State(state): State<ServerStatusState>,
+) -> Result<Json<serde_json::Value>, ServerStatusError> {
+    debug!("GET /api/server-status/sensors");
+
+    let sensors = state.metrics_service.collect_sensor_metrics().await
+        .map_err(ServerStatusError::MetricsCollection)?;
+
+    Ok(Json(serde_json::json!({
+        "sensors": sensors,
+        "timestamp": Utc::now(),
+        "api_version": "1.0"
+    })))
+}
+
+/// Query parameters for GET /api/server-status/history
+#[derive(Debug, Deserialize)]
+pub struct HistoryQuery {
+    /// Only return samples collected after this RFC 3339 timestamp
+    pub since: Option<DateTime<Utc>>,
+    /// Return at most this many of the newest matching samples
+    pub limit: Option<usize>,
+}
+
+/// GET /api/server-status/history - Recently collected metrics, oldest first
+pub async fn get_metrics_history(
+    Query(params): Query<HistoryQuery>,
+    State(state): State<ServerStatusState>,
+) -> Json<Vec<ServerMetrics>> {
+    debug!("GET /api/server-status/history - since: {:?}, limit: {:?}", params.since, params.limit);
+
+    Json(state.metrics_service.history().query(params.since, params.limit))
+}
+
+/// One entry in the collection error history
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CollectionErrorEntry {
+    pub timestamp: DateTime<Utc>,
+    /// Stable machine-readable code of the error
+    pub code: String,
+    pub message: String,
+    pub error: MetricsCollectionError,
+}
+
+/// Response body for the collection error history endpoint
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CollectionErrorsResponse {
+    /// Recent errors from failed and partial collections, newest first
+    pub errors: Vec<CollectionErrorEntry>,
+    /// Most errors kept; older ones are dropped
+    pub capacity: usize,
+    pub failed_collections: u64,
+}
+
+/// GET /api/server-status/errors - Recent collection errors, newest first
+pub async fn get_collection_errors(State(state): State<ServerStatusState>) -> Json<CollectionErrorsResponse> {
+    debug!("GET /api/server-status/errors");
+
+    let stats = state.metrics_service.get_stats().await;
+    let errors = stats
+        .recent_errors
+        .into_iter()
+        .map(|(timestamp, error)| CollectionErrorEntry {
+            timestamp,
+            code: error.code().to_string(),
+            message: error.to_string(),
+            error,
+        })
+        .collect();
+
+    Json(CollectionErrorsResponse {
+        errors,
+        capacity: RECENT_ERRORS_CAPACITY,
+        failed_collections: stats.failed_collections,
+    })
+}
+
+/// Query parameters for the benchmark endpoint
+#[derive(Debug, Deserialize)]
+pub struct BenchmarkQuery {
+    /// Number of fresh collections to run (default: 10, max: 100)
+    pub n: Option<u32>,
+}
+
+/// GET /api/benchmark - Time fresh metrics collection on this host
+///
+/// Only served outside production, since each call runs `n` uncached collections.
+#[instrument(skip(state))]
+pub async fn get_benchmark(
+    Query(params): Query<BenchmarkQuery>,
+    State(state): State<ServerStatusState>,
+) -> Result<Json<BenchmarkStats>, (StatusCode, Json<ErrorResponse>)> {
+    if state.server_info.environment == "production" {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::new(
+                "Benchmarking is disabled in production",
+                "not_found",
+                "BENCHMARK_DISABLED",
+            )),
+        ));
+    }
+
+    let iterations = params.n.unwrap_or(10).clamp(1, 100);
+    debug!("GET /api/benchmark - iterations: {}", iterations);
+
+    Ok(Json(state.metrics_service.benchmark(iterations).await))
+}
+
 /// Custom error type for server status endpoints
 #[derive(Debug)]
 pub enum ServerStatusError {
     MetricsCollection(MetricsCollectionError),
     Validation(MetricsValidationError),
+    BadRequest(String),
+    TooManyConnections { limit: usize },
     Internal(String),
 }
 
+impl ServerStatusError {
+    /// Stable machine-readable code included in the error JSON
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::MetricsCollection(e) => e.code(),
+            Self::Validation(_) => "METRICS_VALIDATION_FAILED",
+            Self::BadRequest(_) => "INVALID_REQUEST",
+            Self::TooManyConnections { .. } => "SSE_CONNECTION_LIMIT",
+            Self::Internal(_) => "INTERNAL_ERROR",
+        }
+    }
+}
+
 impl std::fmt::Display for ServerStatusError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::MetricsCollection(e) => write!(f, "Metrics collection error: {}", e),
             Self::Validation(e) => write!(f, "Validation error: {}", e),
+            Self::BadRequest(e) => write!(f, "Bad request: {}", e),
+            Self::TooManyConnections { limit } => write!(f, "Too many stream connections (limit {})", limit),
             Self::Internal(e) => write!(f, "Internal error: {}", e),
         }
     }
@@ -319,7 +813,7 @@ impl std::error::Error for ServerStatusError {
         match self {
             Self::MetricsCollection(e) => Some(e),
             Self::Validation(e) => Some(e),
-            Self::Internal(_) => None,
+            Self::BadRequest(_) | Self::TooManyConnections { .. } | Self::Internal(_) => None,
         }
     }
 }
@@ -353,14 +847,21 @@ impl IntoResponse for ServerStatusError {
                 });
                 (StatusCode::UNPROCESSABLE_ENTITY, "validation_error", self.to_string(), Some(details))
             }
+            Self::BadRequest(_) => {
+                (StatusCode::BAD_REQUEST, "bad_request", self.to_string(), None)
+            }
+            Self::TooManyConnections { limit } => {
+                let details = serde_json::json!({ "max_connections": limit });
+                (StatusCode::SERVICE_UNAVAILABLE, "too_many_connections", self.to_string(), Some(details))
+            }
             Self::Internal(_) => {
                 (StatusCode::INTERNAL_SERVER_ERROR, "internal_error", self.to_string(), None)
             }
         };
 
         let error_response = match details {
-            Some(details) => ErrorResponse::with_details(&error_message, error_type, details),
-            None => ErrorResponse::new(&error_message, error_type),
+            Some(details) => ErrorResponse::with_details(&error_message, error_type, self.code(), details),
+            None => ErrorResponse::new(&error_message, error_type, self.code()),
         };
 
         (status_code, Json(error_response)).into_response()
@@ -384,28 +885,37 @@ pub fn create_router() -> Router<ServerStatusState> {
     Router::new()
         .route("/server-status", get(get_server_status))
         .route("/server-status/health", get(get_server_health))
+        .route("/server-status/summary", get(get_server_summary))
+        .route("/server-status/gpu", get(get_gpu_metrics))
+        .route("/server-status/sensors", get(get_sensor_metrics))
+        .route("/server-status/history", get(get_metrics_history))
+        .route("/server-status/errors", get(get_collection_errors))
+        .route("/benchmark", get(get_benchmark))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::routes::test_support::{create_state_without_network, create_test_state};
     use crate::services::{MetricsService, MetricsCache};
+    use crate::services::metrics_service::MetricsServiceConfig;
     use axum::http::StatusCode;
     use axum_test::TestServer;
     use std::sync::Arc;
 
-    fn create_test_state() -> ServerStatusState {
-        let metrics_service = Arc::new(MetricsService::new());
-        let metrics_cache = Arc::new(MetricsCache::new(Arc::clone(&metrics_service)));
-        let server_info = ServerInfo::new(
-            "test-server".to_string(),
-            "1.0.0".to_string(),
-            Utc::now(),
-            "development".to_string(),
-            OsInfo::fallback(),
-        ).expect("Failed to create test ServerInfo");
+    #[tokio::test]
+    async fn test_partial_data_quality() {
+        let (state, _proc_root) = create_state_without_network();
+        state.metrics_service.initialize().await.unwrap();
+        let server = TestServer::new(create_router().with_state(state)).unwrap();
 
-        ServerStatusState::new(metrics_cache, metrics_service, server_info)
+        let response = server.get("/server-status").await;
+        response.assert_status_ok();
+        let body: ServerStatusResponse = response.json();
+        assert_eq!(
+            body.data.data_quality,
+            DataQuality::Partial { missing: vec!["network".to_string()] }
+        );
     }
 
     #[tokio::test]
@@ -428,6 +938,7 @@ mod tests {
             let body: ServerStatusResponse = response.json();
             assert!(!body.data.server_info.hostname.is_empty());
             assert!(body.metadata.api_version == "1.0");
+            assert_eq!(body.network_summary.total_bytes, body.data.server_metrics.network_metrics.total_bytes());
         }
     }
 
@@ -482,6 +993,47 @@ mod tests {
         assert!(body["api_version"] == "1.0");
     }
 
+    #[tokio::test]
+    async fn test_uptime_seconds_matches_uptime() {
+        let state = create_test_state();
+        state.metrics_service.initialize().await.unwrap();
+        let server = TestServer::new(create_router().with_state(state)).unwrap();
+
+        let response = server.get("/server-status").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = serde_json::from_str(&response.text()).unwrap();
+        assert!(json["uptime_seconds"].is_u64());
+
+        let body: ServerStatusResponse = response.json();
+        assert_eq!(body.uptime_seconds.as_secs(), body.data.server_metrics.uptime.as_secs());
+        assert_eq!(json["uptime_seconds"], body.data.server_metrics.uptime.as_secs());
+    }
+
+    #[tokio::test]
+    async fn test_summary_endpoint() {
+        let state = create_test_state();
+        state.metrics_service.initialize().await.unwrap();
+        let server = TestServer::new(create_router().with_state(state.clone())).unwrap();
+
+        let response = server.get("/server-status/summary").await;
+        response.assert_status_ok();
+        let body: serde_json::Value = response.json();
+        let mut keys: Vec<&str> = body.as_object().unwrap().keys().map(String::as_str).collect();
+        keys.sort();
+        assert_eq!(keys, ["cpu_percent", "health", "hostname", "memory_percent", "uptime_seconds"]);
+        assert_eq!(body["hostname"], "test-server");
+
+        // Health follows the configured thresholds
+        let state = state.with_health_thresholds(HealthThresholds {
+            cpu_warning: -1.0,
+            cpu_critical: -1.0,
+            ..HealthThresholds::default()
+        });
+        let server = TestServer::new(create_router().with_state(state)).unwrap();
+        let summary: ServerStatusSummary = server.get("/server-status/summary").await.json();
+        assert_eq!(summary.health, "critical");
+    }
+
     #[tokio::test]
     async fn test_detailed_query_parameter() {
         let state = create_test_state();
@@ -515,14 +1067,181 @@ mod tests {
             // Force refresh should indicate not cached
             assert!(!body.metadata.cached);
             assert!(body.metadata.collection_time_ms.unwrap() > 0);
+            // Fresh data reports its real source rather than the cache
+            assert_eq!(body.metadata.sources.get("memory"), Some(&MetricSource::Sysinfo));
         }
     }
 
+    #[tokio::test]
+    async fn test_benchmark_endpoint() {
+        let state = create_test_state();
+        state.metrics_service.initialize().await.unwrap();
+
+        let server = TestServer::new(create_router().with_state(state)).unwrap();
+        let response = server.get("/benchmark").add_query_param("n", 3).await;
+        response.assert_status_ok();
+
+        let stats: BenchmarkStats = response.json();
+        assert_eq!(stats.iterations, 3);
+        assert!(stats.min_ms <= stats.p95_ms && stats.p95_ms <= stats.max_ms);
+    }
+
+    #[tokio::test]
+    async fn test_collection_errors_endpoint() {
+        let state = create_test_state();
+        state.metrics_service.fail_next_collection(MetricsCollectionError::system_unavailable("first"));
+        state.metrics_service.collect_fresh_metrics().await;
+        state.metrics_service.fail_next_collection(MetricsCollectionError::timeout(250));
+        state.metrics_service.collect_fresh_metrics().await;
+
+        let server = TestServer::new(create_router().with_state(state)).unwrap();
+        let response = server.get("/server-status/errors").await;
+        response.assert_status_ok();
+
+        let body: CollectionErrorsResponse = response.json();
+        assert_eq!(body.capacity, RECENT_ERRORS_CAPACITY);
+        assert_eq!(body.failed_collections, 2);
+        assert_eq!(body.errors.len(), 2);
+        assert_eq!(body.errors[0].error, MetricsCollectionError::timeout(250));
+        assert_eq!(body.errors[0].code, MetricsCollectionError::timeout(250).code());
+        assert_eq!(body.errors[1].error, MetricsCollectionError::system_unavailable("first"));
+    }
+
+    #[tokio::test]
+    async fn test_history_since_filter() {
+        let state = create_test_state();
+        state.metrics_service.initialize().await.unwrap();
+
+        let mut timestamps = Vec::new();
+        for _ in 0..4 {
+            let metrics = state.metrics_service.collect_fresh_metrics().await.data().unwrap();
+            timestamps.push(metrics.timestamp);
+        }
+
+        let server = TestServer::new(create_router().with_state(state)).unwrap();
+        let all: Vec<ServerMetrics> = server.get("/server-status/history").await.json();
+        assert_eq!(all.iter().map(|m| m.timestamp).collect::<Vec<_>>(), timestamps);
+
+        let since = timestamps[1].to_rfc3339_opts(chrono::SecondsFormat::Nanos, true);
+        let response = server.get("/server-status/history").add_query_param("since", &since).await;
+        response.assert_status_ok();
+        let recent: Vec<ServerMetrics> = response.json();
+        assert_eq!(recent.iter().map(|m| m.timestamp).collect::<Vec<_>>(), timestamps[2..]);
+
+        let newest: Vec<ServerMetrics> = server
+            .get("/server-status/history")
+            .add_query_param("since", &since)
+            .add_query_param("limit", 1)
+            .await
+            .json();
+        assert_eq!(newest.len(), 1);
+        assert_eq!(newest[0].timestamp, timestamps[3]);
+
+        server
+            .get("/server-status/history")
+            .add_query_param("since", "yesterday")
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_benchmark_disabled_in_production() {
+        let mut state = create_test_state();
+        state.server_info.environment = "production".to_string();
+
+        let server = TestServer::new(create_router().with_state(state)).unwrap();
+        server.get("/benchmark").await.assert_status(StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_gpu_endpoint_unavailable_when_disabled() {
+        let state = create_test_state();
+
+        let server = TestServer::new(create_router().with_state(state)).unwrap();
+        let response = server.get("/server-status/gpu").await;
+        response.assert_status(StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.json::<serde_json::Value>()["code"], "METRICS_SYSTEM_UNAVAILABLE");
+    }
+
+    #[tokio::test]
+    async fn test_sensors_endpoint() {
+        let server = TestServer::new(create_router().with_state(create_test_state())).unwrap();
+        server.get("/server-status/sensors").await.assert_status(StatusCode::SERVICE_UNAVAILABLE);
+
+        let config = MetricsServiceConfig {
+            collect_sensor_metrics: true,
+            hwmon_root: "/nonexistent-hwmon".to_string(),
+            ..MetricsServiceConfig::default()
+        };
+        let mut state = create_test_state();
+        state.metrics_service = Arc::new(MetricsService::with_config(config));
+
+        let server = TestServer::new(create_router().with_state(state)).unwrap();
+        let response = server.get("/server-status/sensors").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert!(json["sensors"]["temperatures"].is_array());
+        assert_eq!(json["sensors"]["fans"], serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_warming_up_until_first_collection() {
+        let state = create_test_state().with_status_config(StatusEndpointConfig {
+            warming_up_response: true,
+            warming_up_retry_after_seconds: 3,
+        });
+        let metrics_service = Arc::clone(&state.metrics_service);
+        let server = TestServer::new(create_router().with_state(state)).unwrap();
+
+        let response = server.get("/server-status").await;
+        response.assert_status(StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.header("retry-after"), "3");
+        let body: serde_json::Value = response.json();
+        assert_eq!(body["error_type"], "warming_up");
+
+        // The 503 started the first collection in the background
+        tokio::time::timeout(std::time::Duration::from_secs(10), async {
+            while !metrics_service.has_collected() {
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .expect("warm-up collection did not complete");
+
+        server.get("/server-status").await.assert_status_ok();
+    }
+
+    #[tokio::test]
+    async fn test_stale_entry_flagged_in_metadata() {
+        use crate::services::metrics_cache::MetricsCacheConfig;
+
+        let mut state = create_test_state();
+        state.metrics_service.initialize().await.unwrap();
+        state.metrics_cache = Arc::new(MetricsCache::with_config(
+            MetricsCacheConfig { ttl_seconds: 0, collection_attempts: 1, ..MetricsCacheConfig::default() },
+            Arc::clone(&state.metrics_service),
+        ));
+        let metrics_service = Arc::clone(&state.metrics_service);
+        let server = TestServer::new(create_router().with_state(state)).unwrap();
+
+        let response: ServerStatusResponse = server.get("/server-status").await.json();
+        assert!(!response.metadata.stale);
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+
+        metrics_service.fail_next_collection(MetricsCollectionError::timeout(2000));
+        let response = server.get("/server-status").await;
+        response.assert_status_ok();
+        let response: ServerStatusResponse = response.json();
+        assert!(response.metadata.stale);
+        assert!(response.metadata.warnings.iter().any(|w| w.contains("Serving cached metrics")));
+    }
+
     #[test]
     fn test_error_response_creation() {
-        let error = ErrorResponse::new("Test error", "test_error");
+        let error = ErrorResponse::new("Test error", "test_error", "TEST_ERROR");
         assert_eq!(error.error, "Test error");
         assert_eq!(error.error_type, "test_error");
+        assert_eq!(error.code, "TEST_ERROR");
         assert_eq!(error.api_version, "1.0");
     }
 
@@ -534,11 +1253,14 @@ mod tests {
             cpu_usage: crate::models::CpuMetrics {
                 usage_percentage: 50.0,
                 core_count: 4,
+                scale: crate::models::CpuScale::Average,
                 load_average: crate::models::cpu_metrics::LoadAverage {
                     one_minute: 1.5,
                     five_minute: 1.2,
                     fifteen_minute: 1.0,
                 },
+                core_usage_percentages: vec![40.0, 60.0, 45.0, 55.0],
+                temperature_celsius: None,
             },
             uptime: std::time::Duration::from_secs(86400), // 24 hours
             network_metrics: crate::models::NetworkMetrics {
@@ -547,15 +1269,158 @@ mod tests {
                 packets_sent: 1000,
                 packets_received: 2000,
                 active_connections: 10,
+                counter_reset: false,
+                rate_bytes_sent_per_sec: 0.0,
+                rate_bytes_received_per_sec: 0.0,
             },
+            sources: Default::default(),
+            gpu_metrics: Vec::new(),
+            disk_metrics: None,
+            process_metrics: None,
         };
 
         let simplified = create_simplified_metrics(full_metrics);
         
         // Should keep overall CPU usage but remove per-core
         assert_eq!(simplified.cpu_usage.usage_percentage, 50.0);
+        assert!(simplified.cpu_usage.core_usage_percentages.is_empty());
         
         // Should keep total network stats but remove interface details
         assert_eq!(simplified.network_metrics.bytes_received, 1000000);
     }
+
+    #[tokio::test]
+    async fn test_health_path_builds_no_status_data() {
+        let state = create_test_state();
+        state.metrics_service.initialize().await.unwrap();
+
+        // Warm the cache so the probe measures only the handler's own work
+        state.metrics_cache.get_metrics(Some("health_check".to_string())).await;
+
+        let before = crate::models::status_data::status_data_constructions();
+        for _ in 0..10 {
+            let Json(body) = get_server_health(State(state.clone())).await.unwrap();
+            assert!(!body.status.is_empty());
+        }
+        let after = crate::models::status_data::status_data_constructions();
+
+        assert_eq!(after, before, "health path must not construct StatusData");
+    }
+
+    #[tokio::test]
+    async fn test_envelope_disabled_moves_metadata_to_headers() {
+        let state = create_test_state();
+        state.metrics_service.initialize().await.unwrap();
+
+        let app = create_router().with_state(state);
+        let server = TestServer::new(app).unwrap();
+
+        let response = server
+            .get("/server-status")
+            .add_query_param("envelope", "false")
+            .await;
+        response.assert_status_ok();
+
+        assert!(response.header("X-Cached").to_str().unwrap().parse::<bool>().is_ok());
+        assert!(response.header("X-Collection-Time-Ms").to_str().unwrap().parse::<u64>().is_ok());
+        assert_eq!(response.header("X-Api-Version"), "1.0");
+
+        // Body is the bare StatusData
+        let body: StatusData = response.json();
+        assert!(!body.server_info.hostname.is_empty());
+        let raw: serde_json::Value = response.json();
+        assert!(raw.get("metadata").is_none());
+        assert!(raw.get("success").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cpu_scale_query() {
+        let state = create_test_state();
+        state.metrics_service.initialize().await.unwrap();
+
+        let app = create_router().with_state(state);
+        let server = TestServer::new(app).unwrap();
+
+        // Same cache key so both responses describe the same sample
+        let average: ServerStatusResponse = server
+            .get("/server-status")
+            .add_query_param("cache_key", "scale")
+            .await
+            .json();
+        let total: ServerStatusResponse = server
+            .get("/server-status")
+            .add_query_param("cache_key", "scale")
+            .add_query_param("cpu_scale", "total")
+            .await
+            .json();
+
+        let average_cpu = &average.data.server_metrics.cpu_usage;
+        let total_cpu = &total.data.server_metrics.cpu_usage;
+        assert_eq!(total_cpu.scale, CpuScale::Total);
+        assert_eq!(total_cpu.core_count, average_cpu.core_count);
+        let expected = average_cpu.usage_percentage * average_cpu.core_count as f32;
+        assert!((total_cpu.usage_percentage - expected).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn test_error_response_includes_code() {
+        let error = ServerStatusError::MetricsCollection(MetricsCollectionError::permission_denied("/proc"));
+        let response = error.into_response();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["code"], "METRICS_PERMISSION_DENIED");
+        assert_eq!(json["error_type"], "metrics_error");
+    }
+
+    #[test]
+    fn test_network_summary_matches_helpers() {
+        let network = NetworkMetrics {
+            bytes_sent: 1_048_576,
+            bytes_received: 524_288,
+            packets_sent: 800,
+            packets_received: 400,
+            active_connections: 75,
+            ..Default::default()
+        };
+
+        let summary = NetworkSummary::from(&network);
+        assert_eq!(summary.activity_level, network.activity_level());
+        assert_eq!(summary.activity_level, "High");
+        assert_eq!(summary.send_receive_ratio, network.send_receive_ratio());
+        assert_eq!(summary.send_receive_ratio, Some(2.0));
+        assert_eq!(summary.total_bytes, network.total_bytes());
+        assert_eq!(summary.format_total, network.format_total());
+
+        // Send-only traffic has an infinite ratio, which JSON cannot carry
+        let send_only = NetworkMetrics { bytes_sent: 10, ..Default::default() };
+        assert_eq!(NetworkSummary::from(&send_only).send_receive_ratio, None);
+    }
+
+    #[test]
+    fn test_memory_accounting_warning() {
+        // 8GB total, but only 2GB used + 4GB available reported
+        let inconsistent = MemoryMetrics::new(
+            8 * 1024 * 1024 * 1024,
+            2 * 1024 * 1024 * 1024,
+            4 * 1024 * 1024 * 1024,
+        ).unwrap();
+        assert!(inconsistent.validate().is_ok());
+
+        let warning = memory_accounting_warning(&inconsistent, 10.0)
+            .expect("Expected a memory accounting warning");
+        assert!(warning.contains("Memory accounting warning"));
+        assert!(warning.contains("25.0%"));
+
+        // Within tolerance no warning is produced
+        assert!(memory_accounting_warning(&inconsistent, 30.0).is_none());
+
+        let consistent = MemoryMetrics::new(
+            8 * 1024 * 1024 * 1024,
+            4 * 1024 * 1024 * 1024,
+            4 * 1024 * 1024 * 1024,
+        ).unwrap();
+        assert!(memory_accounting_warning(&consistent, 10.0).is_none());
+    }
 }
\ No newline at end of file
@@ -2,14 +2,18 @@
 // Provides continuous updates of server status to connected clients
 
 use crate::models::{
-    StatusData, ServerMetrics, MetricsCollectionError, MetricsResponse
+    StatusData, DataQuality, ServerMetrics, MetricsCollectionError, MetricsResponse
 };
+use crate::middleware::rate_limit::client_address;
 use crate::routes::server_status::{ServerStatusState, ServerStatusError};
+use crate::services::metrics_service::clamp_interval_seconds;
+use crate::services::sse_service::{DEFAULT_TIME_STREAM_RETRY_MS, TIME_STREAM_RETRY_RANGE_MS};
 use axum::{
-    extract::{Query, State},
+    extract::{ConnectInfo, Query, State},
+    http::{header, HeaderMap},
     response::{
-        sse::{Event, Sse},
-        IntoResponse,
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
     },
     routing::get,
     Router,
@@ -17,15 +21,39 @@ use axum::{
 use chrono::{DateTime, Utc};
 use futures_util::stream::Stream;
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use std::collections::BTreeMap;
 use std::convert::Infallible;
+use std::future::Future;
+use std::net::SocketAddr;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::task::{Context, Poll};
 use std::time::{Duration, Instant};
 use tokio::time::{interval, MissedTickBehavior};
 use tracing::{debug, error, instrument, info, warn};
 
+/// Stand-in for client addresses when `REDACT_IPS` is set
+const REDACTED_ADDR: &str = "redacted";
+
+/// Longest user agent recorded per connection; the rest is dropped
+const MAX_USER_AGENT_CHARS: usize = 256;
+
+/// Accepted `metrics` filter values; `load` is the CPU load averages on their own
+const METRIC_FILTER_TYPES: [&str; 7] = ["memory", "cpu", "load", "network", "gpu", "disk", "process"];
+
+/// Split a comma-separated `metrics` value, dropping unknown types
+fn parse_metrics_filter(metrics: &str) -> Vec<String> {
+    metrics
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| METRIC_FILTER_TYPES.contains(&s.as_str()))
+        .collect()
+}
+
 /// Query parameters for SSE stream endpoint
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct SseQuery {
     /// Update interval in seconds (default: 5)
     pub interval: Option<u32>,
@@ -34,11 +62,312 @@ pub struct SseQuery {
     /// Client identifier for connection tracking
     pub client_id: Option<String>,
     /// Include only specific metric types
-    pub metrics: Option<String>, // comma-separated: memory,cpu,network
+    pub metrics: Option<String>, // comma-separated: memory,cpu,load,network,gpu,disk,process
+    /// After the first snapshot, send only ticks where a metric changed (default: false)
+    pub changes_only: Option<bool>,
+    /// Longest gap between events in `changes_only` mode, in seconds (default: server config, 60)
+    pub max_idle: Option<u64>,
+    /// After the first full snapshot, send only the fields that changed (default: false)
+    pub delta: Option<bool>,
+    /// Payload encoding of metrics updates (default: json)
+    pub format: Option<EventFormat>,
+    /// Use the short keys of `COMPACT_KEYS` in JSON payloads (default: false)
+    pub compact: Option<bool>,
+    /// Seconds between keep-alives, clamped to 5-120 (default: server config, 30)
+    pub keepalive: Option<u64>,
+    /// Keep-alive comment or event text (default: server config, "ping")
+    pub keepalive_text: Option<String>,
+}
+
+/// Encoding of the `data` field of metrics update events
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum EventFormat {
+    /// JSON text
+    #[default]
+    Json,
+    /// Base64-encoded MessagePack, sent as `metrics_update_msgpack` events
+    Msgpack,
+}
+
+/// SSE event type of MessagePack-encoded metrics updates
+pub const MSGPACK_EVENT_TYPE: &str = "metrics_update_msgpack";
+
+/// Encode an event as base64 MessagePack with named fields
+fn encode_msgpack<T: Serialize>(event_data: &T) -> Result<String, rmp_serde::encode::Error> {
+    use base64::Engine;
+
+    let bytes = rmp_serde::to_vec_named(event_data)?;
+    Ok(base64::engine::general_purpose::STANDARD.encode(bytes))
+}
+
+/// How SSE event IDs are generated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventIdScheme {
+    /// Per-connection sequence number (resumes after `Last-Event-ID` on reconnect)
+    #[default]
+    Sequence,
+    /// Globally unique UUID per event
+    Uuid,
+    /// Monotonic `<epoch_ms>-<seq>`, robust across reconnects
+    TimestampSeq,
+}
+
+impl EventIdScheme {
+    /// Generate the event ID for the given sequence number
+    pub fn event_id(&self, sequence: u64) -> String {
+        match self {
+            Self::Sequence => sequence.to_string(),
+            Self::Uuid => uuid::Uuid::new_v4().to_string(),
+            Self::TimestampSeq => format!("{}-{}", Utc::now().timestamp_millis(), sequence),
+        }
+    }
+}
+
+/// Sequence to resume from after the `Last-Event-ID` a reconnecting client sent
+///
+/// Missing or unparseable ids start a fresh sequence at 0.
+fn resume_sequence(headers: &HeaderMap) -> u64 {
+    headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|id| id.trim().parse::<u64>().ok())
+        .map_or(0, |last| last.saturating_add(1))
+}
+
+impl std::str::FromStr for EventIdScheme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "sequence" => Ok(Self::Sequence),
+            "uuid" => Ok(Self::Uuid),
+            "timestamp_seq" => Ok(Self::TimestampSeq),
+            other => Err(format!("Unknown event id scheme: {}", other)),
+        }
+    }
+}
+
+/// How keep-alive frames are written to the stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeepAliveMode {
+    /// SSE comment line (`:ping`), ignored by EventSource clients
+    #[default]
+    Comment,
+    /// Named `keepalive` event carrying the keep-alive text as data
+    Event,
+}
+
+impl std::str::FromStr for KeepAliveMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "comment" => Ok(Self::Comment),
+            "event" => Ok(Self::Event),
+            other => Err(format!("Unknown keep-alive mode: {}", other)),
+        }
+    }
+}
+
+/// Configuration for the server status SSE stream
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SseStreamConfig {
+    /// Scheme used for the `id:` field of each event
+    pub id_scheme: EventIdScheme,
+    /// Whether keep-alives are comments or named events
+    pub keep_alive_mode: KeepAliveMode,
+    /// Keep-alive payload text
+    pub keep_alive_text: String,
+    /// Seconds between keep-alive frames
+    pub keep_alive_interval_seconds: u64,
+    /// Let clients request intervals shorter than the metrics collection interval
+    ///
+    /// Off by default: sub-interval requests only re-serve cached data.
+    #[serde(default)]
+    pub allow_sub_collection_interval: bool,
+    /// Smallest change in a percentage that counts as a change for `changes_only` streams
+    #[serde(default = "default_change_epsilon")]
+    pub change_epsilon: f32,
+    /// Longest gap between events on `changes_only` streams, in seconds
+    #[serde(default = "default_max_idle_seconds")]
+    pub max_idle_seconds: u64,
+    /// Replace client IPs with `redacted` in connection info
+    #[serde(default)]
+    pub redact_ips: bool,
+}
+
+fn default_change_epsilon() -> f32 {
+    0.5
+}
+
+fn default_max_idle_seconds() -> u64 {
+    60
+}
+
+impl Default for SseStreamConfig {
+    fn default() -> Self {
+        Self {
+            id_scheme: EventIdScheme::default(),
+            keep_alive_mode: KeepAliveMode::default(),
+            keep_alive_text: "ping".to_string(),
+            keep_alive_interval_seconds: 30,
+            allow_sub_collection_interval: false,
+            change_epsilon: default_change_epsilon(),
+            max_idle_seconds: default_max_idle_seconds(),
+            redact_ips: false,
+        }
+    }
+}
+
+/// Range clients may set the keep-alive interval in, in seconds
+pub const KEEP_ALIVE_RANGE_SECONDS: std::ops::RangeInclusive<u64> = 5..=120;
+
+/// Longest client-supplied keep-alive text
+pub const MAX_KEEP_ALIVE_TEXT_LENGTH: usize = 64;
+
+/// Range clients may set `max_idle` in, in seconds
+pub const MAX_IDLE_RANGE_SECONDS: std::ops::RangeInclusive<u64> = 1..=3600;
+
+impl SseStreamConfig {
+    /// Apply a client's keep-alive overrides, clamping the interval and
+    /// stripping line breaks from the text rather than rejecting either
+    pub fn with_keep_alive_overrides(&self, interval_seconds: Option<u64>, text: Option<&str>) -> Self {
+        let mut config = self.clone();
+
+        if let Some(requested) = interval_seconds {
+            let clamped = requested.clamp(*KEEP_ALIVE_RANGE_SECONDS.start(), *KEEP_ALIVE_RANGE_SECONDS.end());
+            if clamped != requested {
+                debug!("Clamped keepalive {}s to {}s", requested, clamped);
+            }
+            config.keep_alive_interval_seconds = clamped;
+        }
+
+        // Line breaks would end the SSE comment early, which axum refuses
+        let text: Option<String> = text.map(|text| {
+            text.chars()
+                .filter(|c| !c.is_control())
+                .take(MAX_KEEP_ALIVE_TEXT_LENGTH)
+                .collect()
+        });
+        if let Some(text) = text.filter(|text| !text.is_empty()) {
+            config.keep_alive_text = text;
+        }
+
+        config
+    }
+
+    /// Build the keep-alive for this configuration
+    pub fn keep_alive(&self) -> KeepAlive {
+        let keep_alive = KeepAlive::new().interval(Duration::from_secs(self.keep_alive_interval_seconds));
+
+        match self.keep_alive_mode {
+            KeepAliveMode::Comment => keep_alive.text(self.keep_alive_text.as_str()),
+            KeepAliveMode::Event => keep_alive.event(
+                Event::default()
+                    .event("keepalive")
+                    .data(self.keep_alive_text.as_str()),
+            ),
+        }
+    }
+
+    /// Load configuration from `SSE_*` environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(value) = std::env::var("SSE_ID_SCHEME") {
+            match value.parse() {
+                Ok(scheme) => config.id_scheme = scheme,
+                Err(e) => warn!("Ignoring SSE_ID_SCHEME: {}", e),
+            }
+        }
+
+        if let Ok(value) = std::env::var("SSE_KEEPALIVE_MODE") {
+            match value.parse() {
+                Ok(mode) => config.keep_alive_mode = mode,
+                Err(e) => warn!("Ignoring SSE_KEEPALIVE_MODE: {}", e),
+            }
+        }
+
+        if let Ok(value) = std::env::var("SSE_ALLOW_SUB_COLLECTION_INTERVAL") {
+            match value.parse() {
+                Ok(allow) => config.allow_sub_collection_interval = allow,
+                Err(e) => warn!("Ignoring SSE_ALLOW_SUB_COLLECTION_INTERVAL: {}", e),
+            }
+        }
+
+        if let Ok(value) = std::env::var("SSE_CHANGE_EPSILON") {
+            match value.parse::<f32>() {
+                Ok(epsilon) if epsilon >= 0.0 => config.change_epsilon = epsilon,
+                Ok(epsilon) => warn!("Ignoring negative SSE_CHANGE_EPSILON: {}", epsilon),
+                Err(e) => warn!("Ignoring SSE_CHANGE_EPSILON: {}", e),
+            }
+        }
+
+        if let Ok(value) = std::env::var("SSE_MAX_IDLE_SECONDS") {
+            match value.parse::<u64>() {
+                Ok(seconds) if seconds > 0 => config.max_idle_seconds = seconds,
+                Ok(_) => warn!("Ignoring zero SSE_MAX_IDLE_SECONDS"),
+                Err(e) => warn!("Ignoring SSE_MAX_IDLE_SECONDS: {}", e),
+            }
+        }
+
+        if let Ok(value) = std::env::var("REDACT_IPS") {
+            match value.trim().to_ascii_lowercase().as_str() {
+                "1" | "true" => config.redact_ips = true,
+                "0" | "false" => config.redact_ips = false,
+                _ => warn!("Ignoring REDACT_IPS: expected 1/0 or true/false, got {:?}", value),
+            }
+        }
+
+        config
+    }
+
+    /// Client address and user agent to record for a connection
+    ///
+    /// The address is the first `X-Forwarded-For` entry, else the peer's IP;
+    /// with `redact_ips` it is reported as `redacted`.
+    pub fn client_details(&self, headers: &HeaderMap, peer: Option<SocketAddr>) -> (Option<String>, Option<String>) {
+        let remote_addr = client_address(headers, peer)
+            .map(|addr| if self.redact_ips { REDACTED_ADDR.to_string() } else { addr });
+        let user_agent = headers
+            .get(header::USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(|agent| agent.chars().take(MAX_USER_AGENT_CHARS).collect());
+        (remote_addr, user_agent)
+    }
+
+    /// Longest gap between `changes_only` events for a client's requested `max_idle`
+    pub fn effective_max_idle(&self, requested: Option<u64>) -> Duration {
+        let seconds = requested
+            .map(|seconds| seconds.clamp(*MAX_IDLE_RANGE_SECONDS.start(), *MAX_IDLE_RANGE_SECONDS.end()))
+            .unwrap_or(self.max_idle_seconds);
+        Duration::from_secs(seconds)
+    }
+
+    /// Effective update interval for a client's requested interval
+    ///
+    /// Clamped between the CPU refresh floor and 60 seconds, and unless
+    /// overridden, never faster than the server's own collection interval.
+    pub fn effective_interval_seconds(&self, requested: u32, collection_interval_seconds: u32) -> u32 {
+        let interval = clamp_interval_seconds("interval", requested).min(60);
+
+        if self.allow_sub_collection_interval || interval >= collection_interval_seconds {
+            interval
+        } else {
+            debug!(
+                "Raising SSE interval {}s to the collection interval {}s",
+                interval, collection_interval_seconds
+            );
+            collection_interval_seconds
+        }
+    }
 }
 
 /// SSE event data for server metrics
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct MetricsEvent {
     /// Event type identifier
     pub event_type: String,
@@ -52,8 +381,180 @@ pub struct MetricsEvent {
     pub connection_info: ConnectionInfo,
 }
 
+/// Final SSE event sent before the stream ends on server shutdown
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShutdownEvent {
+    /// Always `server_shutdown`
+    pub event_type: String,
+    /// Human-readable reason
+    pub message: String,
+    /// Event sequence number
+    pub sequence: u64,
+    /// Event timestamp
+    pub timestamp: DateTime<Utc>,
+    /// Connection metadata
+    pub connection_info: ConnectionInfo,
+}
+
+/// SSE event carrying only the metrics fields that changed since the last event
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MetricsDeltaEvent {
+    /// Always `metrics_delta`
+    pub event_type: String,
+    /// Changed `ServerMetrics` fields, nested as in the full snapshot (empty if nothing changed);
+    /// removed fields are `null`
+    pub changes: serde_json::Value,
+    /// Event sequence number
+    pub sequence: u64,
+    /// Event timestamp
+    pub timestamp: DateTime<Utc>,
+    /// Connection metadata
+    pub connection_info: ConnectionInfo,
+}
+
+/// Short keys sent by `compact=true` streams, as `(full, short)` pairs
+///
+/// Keys missing from the table, such as `name`, keep their full form.
+pub const COMPACT_KEYS: &[(&str, &str)] = &[
+    // Event envelope
+    ("event_type", "t"),
+    ("data", "d"),
+    ("sequence", "s"),
+    ("timestamp", "ts"),
+    ("connection_info", "ci"),
+    ("changes", "ch"),
+    ("message", "msg"),
+    // StatusData
+    ("server_metrics", "sm"),
+    ("collection_interval_seconds", "cis"),
+    ("server_info", "si"),
+    ("data_quality", "dq"),
+    // ServerMetrics
+    ("memory_usage", "mem"),
+    ("cpu_usage", "cpu"),
+    ("uptime", "up"),
+    ("network_metrics", "net"),
+    ("gpu_metrics", "gpu"),
+    ("disk_metrics", "dsk"),
+    ("process_metrics", "prc"),
+    // Memory and disk
+    ("total_bytes", "tb"),
+    ("used_bytes", "ub"),
+    ("available_bytes", "ab"),
+    ("usage_percentage", "pct"),
+    ("swap_total_bytes", "stb"),
+    ("swap_used_bytes", "sub"),
+    ("swap_usage_percentage", "spct"),
+    ("mounts", "mnt"),
+    ("mount_point", "mp"),
+    ("file_system", "fs"),
+    // CPU
+    ("core_count", "cc"),
+    ("scale", "sc"),
+    ("load_average", "la"),
+    ("one_minute", "l1"),
+    ("five_minute", "l5"),
+    ("fifteen_minute", "l15"),
+    ("per_core_usage", "pcu"),
+    ("temperature_celsius", "tc"),
+    // Network
+    ("bytes_sent", "bs"),
+    ("bytes_received", "br"),
+    ("packets_sent", "ps"),
+    ("packets_received", "pr"),
+    ("active_connections", "ac"),
+    ("counter_reset", "cr"),
+    ("rate_bytes_sent_per_sec", "rbs"),
+    ("rate_bytes_received_per_sec", "rbr"),
+    // GPU
+    ("utilization_percentage", "upct"),
+    ("memory_used_bytes", "mub"),
+    ("memory_total_bytes", "mtb"),
+    ("power_draw_watts", "pw"),
+    // Processes
+    ("total_processes", "tp"),
+    ("running_processes", "rp"),
+    ("cpu_percentage", "cpct"),
+    ("memory_bytes", "mb"),
+    // ServerInfo and OsInfo
+    ("hostname", "h"),
+    ("version", "v"),
+    ("start_time", "st"),
+    ("environment", "env"),
+    ("os_info", "os"),
+    ("architecture", "arch"),
+    ("kernel_version", "kv"),
+    ("distribution", "dist"),
+    ("long_description", "ld"),
+    ("build_timestamp", "bt"),
+    ("git_commit", "gc"),
+    // ConnectionInfo
+    ("client_id", "cid"),
+    ("connection_duration_seconds", "cds"),
+    ("events_sent", "es"),
+    ("update_interval_seconds", "uis"),
+    ("data_age_seconds", "age"),
+    ("remote_addr", "ra"),
+    ("user_agent", "ua"),
+];
+
+/// Rename object keys in `value` to their `COMPACT_KEYS` short form, at every depth
+fn compact_keys(value: serde_json::Value) -> serde_json::Value {
+    use serde_json::Value;
+    use std::collections::HashMap;
+    use std::sync::OnceLock;
+
+    static SHORT_KEYS: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+    let short_keys = SHORT_KEYS.get_or_init(|| COMPACT_KEYS.iter().copied().collect());
+
+    match value {
+        Value::Object(fields) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| {
+                    let key = short_keys.get(key.as_str()).map_or(key, |short| short.to_string());
+                    (key, compact_keys(value))
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(compact_keys).collect()),
+        other => other,
+    }
+}
+
+/// Fields of `current` that differ from `previous`, recursing into objects
+///
+/// Arrays and scalars are replaced wholesale when they differ. Keys present in
+/// `previous` but gone from `current` are sent as `null`, as in a JSON merge patch.
+fn json_delta(previous: &serde_json::Value, current: &serde_json::Value) -> serde_json::Value {
+    use serde_json::{Map, Value};
+
+    match (previous, current) {
+        (Value::Object(previous), Value::Object(current)) => {
+            let mut changes: Map<String, Value> = current
+                .iter()
+                .filter_map(|(key, value)| match previous.get(key) {
+                    Some(before) if before == value => None,
+                    Some(before @ Value::Object(_)) if value.is_object() => {
+                        Some((key.clone(), json_delta(before, value)))
+                    }
+                    _ => Some((key.clone(), value.clone())),
+                })
+                .collect();
+            changes.extend(
+                previous
+                    .keys()
+                    .filter(|key| !current.contains_key(*key))
+                    .map(|key| (key.clone(), Value::Null)),
+            );
+            Value::Object(changes)
+        }
+        _ => current.clone(),
+    }
+}
+
 /// Connection tracking information
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct ConnectionInfo {
     /// Client identifier
     pub client_id: String,
@@ -63,6 +564,15 @@ pub struct ConnectionInfo {
     pub events_sent: u64,
     /// Update interval for this client
     pub update_interval_seconds: u32,
+    /// Age of the metrics in seconds, present on `stale_data` events
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_age_seconds: Option<i64>,
+    /// Client address (`X-Forwarded-For` or peer IP), `redacted` under `REDACT_IPS`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_addr: Option<String>,
+    /// Client `User-Agent` header
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
 }
 
 /// SSE stream state for individual connections
@@ -77,6 +587,28 @@ struct SseConnectionState {
     detailed: bool,
     #[allow(dead_code)]
     metrics_filter: Option<Vec<String>>,
+    /// Change threshold when only changed samples are sent
+    changes_only: Option<f32>,
+    /// Longest gap between `changes_only` events, whatever the metrics
+    max_idle: Option<Duration>,
+    /// Last sample sent, compared against in `changes_only` mode
+    last_sent: Option<ServerMetrics>,
+    /// When the last sample was sent
+    last_sent_at: Option<Instant>,
+    /// Open-stream counter this connection holds a slot in, released on drop
+    connection_slot: Option<Arc<AtomicUsize>>,
+    /// Send `metrics_delta` events after the first snapshot
+    delta: bool,
+    /// Last metrics sent in delta mode, serialized, as the baseline for the next delta
+    delta_base: Option<serde_json::Value>,
+    /// Encoding of metrics update payloads
+    format: EventFormat,
+    /// Send JSON payloads with `COMPACT_KEYS` short keys
+    compact: bool,
+    /// Where the client connected from, for spotting abusive clients
+    remote_addr: Option<String>,
+    /// Client `User-Agent` header
+    user_agent: Option<String>,
 }
 
 impl SseConnectionState {
@@ -88,7 +620,39 @@ impl SseConnectionState {
             interval_seconds,
             detailed,
             metrics_filter,
+            changes_only: None,
+            max_idle: None,
+            last_sent: None,
+            last_sent_at: None,
+            connection_slot: None,
+            delta: false,
+            delta_base: None,
+            format: EventFormat::Json,
+            compact: false,
+            remote_addr: None,
+            user_agent: None,
+        }
+    }
+
+    /// Whether a new sample should be sent, remembering it if so
+    ///
+    /// Without `changes_only` every sample is sent; with it, only the first,
+    /// those differing from the last one sent, and one at least every `max_idle`.
+    fn should_send(&mut self, metrics: &ServerMetrics) -> bool {
+        let Some(epsilon) = self.changes_only else {
+            return true;
+        };
+
+        let idle = self
+            .max_idle
+            .zip(self.last_sent_at)
+            .is_some_and(|(max_idle, sent_at)| sent_at.elapsed() >= max_idle);
+        if !idle && self.last_sent.as_ref().is_some_and(|last| !metrics.differs_from(last, epsilon)) {
+            return false;
         }
+        self.last_sent = Some(metrics.clone());
+        self.last_sent_at = Some(Instant::now());
+        true
     }
 
     #[allow(dead_code)]
@@ -98,25 +662,77 @@ impl SseConnectionState {
             connection_duration_seconds: self.connected_at.elapsed().as_secs(),
             events_sent: self.events_sent,
             update_interval_seconds: self.interval_seconds,
+            data_age_seconds: None,
+            remote_addr: self.remote_addr.clone(),
+            user_agent: self.user_agent.clone(),
         }
     }
 
     fn increment_events(&mut self) {
         self.events_sent += 1;
     }
+
+    /// Cache key shared by every connection with the same stream parameters
+    ///
+    /// Keying by client would let a client cycling ids create unbounded
+    /// entries; connections with identical parameters can share a slice.
+    fn shared_cache_key(&self) -> String {
+        let filter = match &self.metrics_filter {
+            Some(filter) => {
+                let mut filter = filter.clone();
+                filter.sort();
+                filter.dedup();
+                filter.join("+")
+            }
+            None => "all".to_string(),
+        };
+
+        format!(
+            "sse_{}s_{}_{}",
+            self.interval_seconds,
+            if self.detailed { "detailed" } else { "summary" },
+            filter
+        )
+    }
+}
+
+impl Drop for SseConnectionState {
+    fn drop(&mut self) {
+        if let Some(open) = self.connection_slot.take() {
+            let remaining = open.fetch_sub(1, Ordering::SeqCst) - 1;
+            debug!("SSE client {:?} disconnected, {} streams open", self.client_id, remaining);
+        }
+    }
+}
+
+/// Claim a slot in the open-stream counter, failing once `limit` streams are open
+pub(crate) fn acquire_connection_slot(open: &Arc<AtomicUsize>, limit: usize) -> Result<Arc<AtomicUsize>, ServerStatusError> {
+    open.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| (count < limit).then_some(count + 1))
+        .map(|_| Arc::clone(open))
+        .map_err(|_| ServerStatusError::TooManyConnections { limit })
 }
 
 /// Custom stream implementation for metrics SSE
-struct MetricsStream {
+pub(crate) struct MetricsStream {
     state: SseConnectionState,
     #[allow(dead_code)]
     app_state: ServerStatusState,
     sequence: u64,
     interval_timer: tokio::time::Interval,
+    /// Metrics fetch started by the last tick, polled until it resolves
+    pending_metrics: Option<MetricsFuture>,
+    /// Resolves when the server starts shutting down; `None` once the shutdown event was sent
+    shutdown: Option<ShutdownFuture>,
 }
 
+/// In-flight metrics fetch owned by a stream between polls
+type MetricsFuture = Pin<Box<dyn Future<Output = MetricsResponse<ServerMetrics>> + Send>>;
+
+/// Wait for the server shutdown broadcast
+type ShutdownFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
 impl MetricsStream {
-    fn new(
+    pub(crate) fn new(
         client_id: String,
         interval_seconds: u32,
         detailed: bool,
@@ -126,25 +742,77 @@ impl MetricsStream {
         let mut timer = interval(Duration::from_secs(interval_seconds as u64));
         timer.set_missed_tick_behavior(MissedTickBehavior::Skip);
 
+        let mut shutdown_rx = app_state.shutdown.subscribe();
+        let shutdown = Box::pin(async move {
+            // A closed or lagged channel also means the server is going away
+            let _ = shutdown_rx.recv().await;
+        });
+
         Self {
             state: SseConnectionState::new(client_id, interval_seconds, detailed, metrics_filter),
             app_state,
             sequence: 0,
             interval_timer: timer,
+            pending_metrics: None,
+            shutdown: Some(shutdown),
         }
     }
 
+    /// Hold a slot in the open-stream counter until the stream is dropped
+    pub(crate) fn holding_slot(mut self, open: Arc<AtomicUsize>) -> Self {
+        self.state.connection_slot = Some(open);
+        self
+    }
+
+    /// Continue the event id sequence from `sequence` instead of 0
+    fn starting_at(mut self, sequence: u64) -> Self {
+        self.sequence = sequence;
+        self
+    }
+
+    /// Encode metrics update payloads as `format`
+    fn format(mut self, format: EventFormat) -> Self {
+        self.state.format = format;
+        self
+    }
+
+    /// Send JSON payloads with short keys
+    fn compact(mut self) -> Self {
+        self.state.compact = true;
+        self
+    }
+
+    /// Record where the client connected from and its user agent
+    fn client_details(mut self, remote_addr: Option<String>, user_agent: Option<String>) -> Self {
+        self.state.remote_addr = remote_addr;
+        self.state.user_agent = user_agent;
+        self
+    }
+
+    /// Send only changed fields after the first full snapshot
+    fn delta(mut self) -> Self {
+        self.state.delta = true;
+        self
+    }
+
+    /// Suppress ticks where no metric moved by more than `epsilon`, sending one at least every `max_idle`
+    fn changes_only(mut self, epsilon: f32, max_idle: Duration) -> Self {
+        self.state.changes_only = Some(epsilon);
+        self.state.max_idle = Some(max_idle);
+        self
+    }
+
     #[allow(dead_code)]
     async fn collect_metrics(&self) -> Result<ServerMetrics, MetricsCollectionError> {
         // Use cache for regular updates to reduce system load
-        let cache_key = format!("sse_{}", self.state.client_id);
+        let cache_key = self.state.shared_cache_key();
         
         match self.app_state.metrics_cache.get_metrics(Some(cache_key)).await {
             MetricsResponse::Ok(metrics) => Ok(metrics),
             MetricsResponse::PartialData { data, errors } => {
                 // Log warnings but return partial data
                 for error in errors {
-                    warn!("Partial metrics for SSE client {}: {}", self.state.client_id, error);
+                    warn!("Partial metrics for SSE client {:?}: {}", self.state.client_id, error);
                 }
                 Ok(data)
             }
@@ -152,7 +820,6 @@ impl MetricsStream {
         }
     }
 
-    #[allow(dead_code)]
     fn filter_metrics(&self, mut metrics: ServerMetrics) -> ServerMetrics {
         if let Some(ref filter) = self.state.metrics_filter {
             // Apply metrics filtering based on requested types
@@ -160,11 +827,25 @@ impl MetricsStream {
                 metrics.memory_usage = crate::models::MemoryMetrics::default();
             }
             if !filter.contains(&"cpu".to_string()) {
+                let load_average = metrics.cpu_usage.load_average.clone();
                 metrics.cpu_usage = crate::models::CpuMetrics::default();
+                // `load` streams the load averages without the rest of the CPU data
+                if filter.contains(&"load".to_string()) {
+                    metrics.cpu_usage.load_average = load_average;
+                }
             }
             if !filter.contains(&"network".to_string()) {
                 metrics.network_metrics = crate::models::NetworkMetrics::default();
             }
+            if !filter.contains(&"gpu".to_string()) {
+                metrics.gpu_metrics.clear();
+            }
+            if !filter.contains(&"disk".to_string()) {
+                metrics.disk_metrics = None;
+            }
+            if !filter.contains(&"process".to_string()) {
+                metrics.process_metrics = None;
+            }
         }
 
         // Apply detailed flag
@@ -175,6 +856,18 @@ impl MetricsStream {
         metrics
     }
 
+    /// Apply the client's metrics filter and detail level to a fetched response
+    fn filter_response(&self, metrics_result: MetricsResponse<ServerMetrics>) -> MetricsResponse<ServerMetrics> {
+        match metrics_result {
+            MetricsResponse::Ok(metrics) => MetricsResponse::Ok(self.filter_metrics(metrics)),
+            MetricsResponse::PartialData { data, errors } => MetricsResponse::PartialData {
+                data: self.filter_metrics(data),
+                errors,
+            },
+            MetricsResponse::Error(error) => MetricsResponse::Error(error),
+        }
+    }
+
     #[allow(dead_code)]
     async fn create_event(&mut self) -> Result<Event, ServerStatusError> {
         // Collect metrics
@@ -211,14 +904,14 @@ impl MetricsStream {
         // Create SSE event
         let event = Event::default()
             .event("metrics_update")
-            .id(self.sequence.to_string())
+            .id(self.app_state.sse_config.id_scheme.event_id(self.sequence))
             .data(serde_json::to_string(&event_data).map_err(|e| {
                 ServerStatusError::Internal(format!("Failed to serialize event data: {}", e))
             })?)
             .retry(Duration::from_secs(5));
 
         debug!(
-            "Created SSE event {} for client {} (connection: {}s)",
+            "Created SSE event {} for client {:?} (connection: {}s)",
             self.sequence,
             self.state.client_id,
             self.state.connected_at.elapsed().as_secs()
@@ -229,36 +922,7 @@ impl MetricsStream {
 
     /// Create a minimal status data for error cases
     fn create_minimal_status(&self) -> Result<StatusData, String> {
-        use crate::models::{ServerMetrics, MemoryMetrics, CpuMetrics, NetworkMetrics};
-        use crate::models::cpu_metrics::LoadAverage;
-        
-        // Create minimal/default metrics
-        let minimal_metrics = ServerMetrics {
-            timestamp: Utc::now(),
-            memory_usage: MemoryMetrics {
-                total_bytes: 0,
-                used_bytes: 0,
-                available_bytes: 0,
-                usage_percentage: 0.0,
-            },
-            cpu_usage: CpuMetrics {
-                usage_percentage: 0.0,
-                core_count: 1,
-                load_average: LoadAverage {
-                    one_minute: 0.0,
-                    five_minute: 0.0,
-                    fifteen_minute: 0.0,
-                },
-            },
-            uptime: Duration::from_secs(0),
-            network_metrics: NetworkMetrics {
-                bytes_sent: 0,
-                bytes_received: 0,
-                packets_sent: 0,
-                packets_received: 0,
-                active_connections: 0,
-            },
-        };
+        let minimal_metrics = ServerMetrics::empty();
 
         StatusData::new(
             minimal_metrics,
@@ -268,153 +932,181 @@ impl MetricsStream {
     }
 }
 
-impl Stream for MetricsStream {
-    type Item = Result<Event, Infallible>;
+impl MetricsStream {
+    /// Age of the metrics in seconds if they are older than the configured stale threshold
+    fn data_age_seconds(&self, metrics_result: &MetricsResponse<ServerMetrics>) -> Option<i64> {
+        let stale_threshold = self.app_state.metrics_service.get_config().stale_threshold_seconds;
+        match metrics_result {
+            MetricsResponse::Ok(metrics) | MetricsResponse::PartialData { data: metrics, .. } => {
+                metrics.is_timestamp_stale_after(i64::from(stale_threshold))
+            }
+            MetricsResponse::Error(_) => None,
+        }
+    }
 
-    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        // Poll the interval timer
-        match self.interval_timer.poll_tick(cx) {
-            Poll::Ready(_) => {
-                // Timer ticked - collect metrics and create event
-                let sequence = self.sequence;
-                let client_id = self.state.client_id.clone();
-                let connected_at = self.state.connected_at;
-                let events_sent = self.state.events_sent;
-                let interval_seconds = self.state.interval_seconds;
-                
-                // Create connection info
-                let connection_info = ConnectionInfo {
-                    client_id: client_id.clone(),
-                    connection_duration_seconds: connected_at.elapsed().as_secs(),
-                    events_sent,
-                    update_interval_seconds: interval_seconds,
-                };
-                
-                // Get metrics from cache (this is synchronous and safe to call in poll_next)
-                let metrics_result = tokio::task::block_in_place(|| {
-                    tokio::runtime::Handle::current().block_on(async {
-                        self.app_state.metrics_cache.get_metrics(None).await
-                    })
-                });
-                
-                let event_data = match metrics_result {
-                    MetricsResponse::Ok(metrics) => {
-                        // Create status data
-                        match StatusData::new(
-                            metrics,
-                            self.app_state.metrics_service.get_config().collection_interval_seconds,
-                            self.app_state.server_info.clone(),
-                        ) {
-                            Ok(status_data) => {
-                                // Create proper event with full metrics
-                                MetricsEvent {
-                                    event_type: "status-update".to_string(),
-                                    data: status_data,
-                                    sequence,
-                                    timestamp: Utc::now(),
-                                    connection_info: connection_info.clone(),
-                                }
-                            }
-                            Err(e) => {
-                                // Fallback to error event - create minimal StatusData
-                                warn!("Failed to create status data: {}", e);
-                                let minimal_status = self.create_minimal_status().unwrap_or_else(|err| {
-                                    // This shouldn't happen, but if it does, we need to handle it
-                                    error!("Failed to create minimal status: {}", err);
-                                    // We can't return from here, so we'll create the most basic status possible
-                                    StatusData::new(
-                                        ServerMetrics {
-                                            timestamp: Utc::now(),
-                                            memory_usage: crate::models::MemoryMetrics {
-                                                total_bytes: 0, used_bytes: 0, available_bytes: 0, usage_percentage: 0.0
-                                            },
-                                            cpu_usage: crate::models::CpuMetrics {
-                                                usage_percentage: 0.0, core_count: 1,
-                                                load_average: crate::models::cpu_metrics::LoadAverage { one_minute: 0.0, five_minute: 0.0, fifteen_minute: 0.0 }
-                                            },
-                                            uptime: Duration::from_secs(0),
-                                            network_metrics: crate::models::NetworkMetrics {
-                                                bytes_sent: 0, bytes_received: 0, packets_sent: 0, packets_received: 0, active_connections: 0
-                                            },
-                                        },
-                                        5,
-                                        self.app_state.server_info.clone(),
-                                    ).unwrap_or_else(|_| panic!("Critical: Cannot create StatusData"))
-                                });
-                                
-                                MetricsEvent {
-                                    event_type: "error".to_string(),
-                                    data: minimal_status,
-                                    sequence,
-                                    timestamp: Utc::now(),
-                                    connection_info: connection_info.clone(),
-                                }
-                            }
+    /// Connection metadata for the next event
+    fn connection_info(&self, data_age_seconds: Option<i64>) -> ConnectionInfo {
+        ConnectionInfo {
+            client_id: self.state.client_id.clone(),
+            connection_duration_seconds: self.state.connected_at.elapsed().as_secs(),
+            events_sent: self.state.events_sent,
+            update_interval_seconds: self.state.interval_seconds,
+            data_age_seconds,
+            remote_addr: self.state.remote_addr.clone(),
+            user_agent: self.state.user_agent.clone(),
+        }
+    }
+
+    /// Turn a fetched metrics response into the SSE event for this tick
+    fn build_event(&mut self, metrics_result: MetricsResponse<ServerMetrics>) -> Event {
+        let sequence = self.sequence;
+        let metrics_result = self.filter_response(metrics_result);
+
+        // Delta mode: the first snapshot is the baseline, later events carry only changes
+        if self.state.delta {
+            if let MetricsResponse::Ok(metrics) | MetricsResponse::PartialData { data: metrics, .. } = &metrics_result {
+                match serde_json::to_value(metrics) {
+                    Ok(current) => {
+                        let connection_info = self.connection_info(self.data_age_seconds(&metrics_result));
+                        if let Some(previous) = self.state.delta_base.replace(current.clone()) {
+                            return self.delta_event(json_delta(&previous, &current), connection_info);
+                        }
+                    }
+                    Err(e) => warn!("Failed to serialize metrics for delta, sending full snapshot: {}", e),
+                }
+            }
+        }
+
+        let event_data = self.metrics_event(metrics_result);
+
+        // Binary clients get status updates as MessagePack; error events stay JSON
+        let msgpack = (self.state.format == EventFormat::Msgpack && event_data.event_type == "status-update")
+            .then(|| encode_msgpack(&event_data))
+            .and_then(|encoded| {
+                encoded
+                    .map_err(|e| error!("Failed to encode SSE event as MessagePack, sending JSON: {}", e))
+                    .ok()
+            });
+
+        // Serialize event data, falling back to a schema-valid error event
+        let connection_info = event_data.connection_info.clone();
+        let server_info = self.app_state.server_info.clone();
+        let (event_type, event_data_json) = match msgpack {
+            Some(encoded) => (MSGPACK_EVENT_TYPE.to_string(), encoded),
+            None => self.serialize_json(&event_data, &event_data.event_type, || {
+                minimal_error_event(sequence, connection_info, server_info)
+            }),
+        };
+        
+        let event = Event::default()
+            .event(event_type)
+            .id(self.app_state.sse_config.id_scheme.event_id(sequence))
+            .data(event_data_json)
+            .retry(Duration::from_secs(5));
+
+        self.sequence += 1;
+        self.state.increment_events();
+
+        event
+    }
+
+    /// Identifier of the connected client
+    pub(crate) fn client_id(&self) -> &str {
+        &self.state.client_id
+    }
+
+    /// Cache key shared by streams requesting the same metrics
+    pub(crate) fn cache_key(&self) -> String {
+        self.state.shared_cache_key()
+    }
+
+    /// Report a new update interval in later events; the caller owns the timer
+    pub(crate) fn set_interval_seconds(&mut self, interval_seconds: u32) {
+        self.state.interval_seconds = interval_seconds;
+    }
+
+    /// Turn a fetched metrics response into the event for the next tick, advancing the sequence
+    ///
+    /// Used by transports that send `MetricsEvent` JSON as is, such as WebSockets.
+    pub(crate) fn next_metrics_event(&mut self, metrics_result: MetricsResponse<ServerMetrics>) -> MetricsEvent {
+        let event = self.metrics_event(self.filter_response(metrics_result));
+        self.sequence += 1;
+        self.state.increment_events();
+        event
+    }
+
+    /// Build the `MetricsEvent` for a fetched metrics response
+    fn metrics_event(&self, metrics_result: MetricsResponse<ServerMetrics>) -> MetricsEvent {
+        let sequence = self.sequence;
+
+        // Flag metrics older than the configured threshold so dashboards can mark them outdated
+        let data_age_seconds = self.data_age_seconds(&metrics_result);
+        if let Some(age) = data_age_seconds {
+            warn!("Streaming stale metrics to {}: {}s old", self.state.client_id, age);
+        }
+        let update_event_type = if data_age_seconds.is_some() { "stale_data" } else { "status-update" };
+        let connection_info = self.connection_info(data_age_seconds);
+
+        match metrics_result {
+            MetricsResponse::Ok(metrics) => {
+                // Create status data
+                match StatusData::new(
+                    metrics,
+                    self.app_state.metrics_service.get_config().collection_interval_seconds,
+                    self.app_state.server_info.clone(),
+                ) {
+                    Ok(status_data) => {
+                        // Create proper event with full metrics
+                        MetricsEvent {
+                            event_type: update_event_type.to_string(),
+                            data: status_data,
+                            sequence,
+                            timestamp: Utc::now(),
+                            connection_info: connection_info.clone(),
                         }
                     }
-                    MetricsResponse::PartialData { data, errors } => {
-                        warn!("Partial metrics data with {} errors", errors.len());
-                        match StatusData::new(
-                            data,
-                            self.app_state.metrics_service.get_config().collection_interval_seconds,
-                            self.app_state.server_info.clone(),
-                        ) {
-                            Ok(status_data) => MetricsEvent {
-                                event_type: "status-update".to_string(),
-                                data: status_data,
-                                sequence,
-                                timestamp: Utc::now(),
-                                connection_info: connection_info.clone(),
-                            },
-                            Err(_) => {
-                                let minimal_status = self.create_minimal_status().unwrap_or_else(|_| {
-                                    StatusData::new(
-                                        ServerMetrics {
-                                            timestamp: Utc::now(),
-                                            memory_usage: crate::models::MemoryMetrics {
-                                                total_bytes: 0, used_bytes: 0, available_bytes: 0, usage_percentage: 0.0
-                                            },
-                                            cpu_usage: crate::models::CpuMetrics {
-                                                usage_percentage: 0.0, core_count: 1,
-                                                load_average: crate::models::cpu_metrics::LoadAverage { one_minute: 0.0, five_minute: 0.0, fifteen_minute: 0.0 }
-                                            },
-                                            uptime: Duration::from_secs(0),
-                                            network_metrics: crate::models::NetworkMetrics {
-                                                bytes_sent: 0, bytes_received: 0, packets_sent: 0, packets_received: 0, active_connections: 0
-                                            },
-                                        },
-                                        5,
-                                        self.app_state.server_info.clone(),
-                                    ).unwrap()
-                                });
-                                MetricsEvent {
-                                    event_type: "error".to_string(),
-                                    data: minimal_status,
-                                    sequence,
-                                    timestamp: Utc::now(),
-                                    connection_info: connection_info.clone(),
-                                }
-                            }
+                    Err(e) => {
+                        // Fallback to error event - create minimal StatusData
+                        warn!("Failed to create status data: {}", e);
+                        let minimal_status = self.create_minimal_status().unwrap_or_else(|err| {
+                            // This shouldn't happen, but if it does, we need to handle it
+                            error!("Failed to create minimal status: {}", err);
+                            // We can't return from here, so we'll create the most basic status possible
+                            StatusData::new(
+                                ServerMetrics::empty(),
+                                5,
+                                self.app_state.server_info.clone(),
+                            ).unwrap_or_else(|_| panic!("Critical: Cannot create StatusData"))
+                        });
+                        
+                        MetricsEvent {
+                            event_type: "error".to_string(),
+                            data: minimal_status,
+                            sequence,
+                            timestamp: Utc::now(),
+                            connection_info: connection_info.clone(),
                         }
                     }
-                    MetricsResponse::Error(e) => {
-                        error!("Failed to collect metrics for SSE: {}", e);
+                }
+            }
+            MetricsResponse::PartialData { data, errors } => {
+                warn!("Partial metrics data with {} errors", errors.len());
+                match StatusData::new(
+                    data,
+                    self.app_state.metrics_service.get_config().collection_interval_seconds,
+                    self.app_state.server_info.clone(),
+                ).map(|status_data| status_data.with_data_quality(DataQuality::from_errors(&errors))) {
+                    Ok(status_data) => MetricsEvent {
+                        event_type: update_event_type.to_string(),
+                        data: status_data,
+                        sequence,
+                        timestamp: Utc::now(),
+                        connection_info: connection_info.clone(),
+                    },
+                    Err(_) => {
                         let minimal_status = self.create_minimal_status().unwrap_or_else(|_| {
                             StatusData::new(
-                                ServerMetrics {
-                                    timestamp: Utc::now(),
-                                    memory_usage: crate::models::MemoryMetrics {
-                                        total_bytes: 0, used_bytes: 0, available_bytes: 0, usage_percentage: 0.0
-                                    },
-                                    cpu_usage: crate::models::CpuMetrics {
-                                        usage_percentage: 0.0, core_count: 1,
-                                        load_average: crate::models::cpu_metrics::LoadAverage { one_minute: 0.0, five_minute: 0.0, fifteen_minute: 0.0 }
-                                    },
-                                    uptime: Duration::from_secs(0),
-                                    network_metrics: crate::models::NetworkMetrics {
-                                        bytes_sent: 0, bytes_received: 0, packets_sent: 0, packets_received: 0, active_connections: 0
-                                    },
-                                },
+                                ServerMetrics::empty(),
                                 5,
                                 self.app_state.server_info.clone(),
                             ).unwrap()
@@ -427,79 +1119,293 @@ impl Stream for MetricsStream {
                             connection_info: connection_info.clone(),
                         }
                     }
-                };
-                
-                // Serialize event data
-                let event_data_json = match serde_json::to_string(&event_data) {
-                    Ok(json) => json,
-                    Err(e) => {
-                        error!("Failed to serialize event data: {}", e);
-                        format!(r#"{{"event_type":"error","sequence":{},"timestamp":"{}","client_id":"{}","error":"serialization_failed"}}"#,
-                            sequence, Utc::now().to_rfc3339(), client_id)
-                    }
-                };
-                
-                let event = Event::default()
-                    .event(&event_data.event_type)
-                    .id(sequence.to_string())
-                    .data(event_data_json)
-                    .retry(Duration::from_secs(5));
-
-                self.sequence += 1;
-                self.state.increment_events();
-
-                Poll::Ready(Some(Ok(event)))
+                }
+            }
+            MetricsResponse::Error(e) => {
+                error!("Failed to collect metrics for SSE: {}", e);
+                let minimal_status = self.create_minimal_status().unwrap_or_else(|_| {
+                    StatusData::new(
+                        ServerMetrics::empty(),
+                        5,
+                        self.app_state.server_info.clone(),
+                    ).unwrap()
+                });
+                MetricsEvent {
+                    event_type: "error".to_string(),
+                    data: minimal_status,
+                    sequence,
+                    timestamp: Utc::now(),
+                    connection_info: connection_info.clone(),
+                }
             }
-            Poll::Pending => Poll::Pending,
         }
     }
 }
 
-    /// GET /api/server-status-stream - Server-Sent Events stream for real-time metrics
-#[instrument(skip(state))]
-pub async fn server_status_stream(
-    Query(params): Query<SseQuery>,
-    State(state): State<ServerStatusState>,
-) -> impl IntoResponse {
-    let client_id = params.client_id.unwrap_or_else(|| {
-        format!("client_{}", uuid::Uuid::new_v4().to_string()[..8].to_string())
-    });
-    
-    let interval = params.interval.unwrap_or(5).max(1).min(60); // Clamp between 1-60 seconds
-    let detailed = params.detailed.unwrap_or(true);
-    
-    let metrics_filter = params.metrics.map(|m| {
-        m.split(',')
-            .map(|s| s.trim().to_string())
-            .filter(|s| ["memory", "cpu", "network"].contains(&s.as_str()))
-            .collect()
-    });
-
-    info!(
-        "New SSE connection: client_id={}, interval={}s, detailed={}, filter={:?}",
-        client_id, interval, detailed, metrics_filter
-    );
+impl MetricsStream {
+    /// Serialize an event payload as JSON, with short keys for compact streams
+    fn serialize_json<T: Serialize>(
+        &self,
+        event_data: &T,
+        event_type: &str,
+        fallback: impl FnOnce() -> MetricsEvent,
+    ) -> (String, String) {
+        if self.state.compact {
+            if let Ok(value) = serde_json::to_value(event_data) {
+                return (event_type.to_string(), compact_keys(value).to_string());
+            }
+        }
+        serialize_event_data(event_data, event_type, fallback)
+    }
+
+    /// Build the `server_shutdown` event that ends the stream
+    fn shutdown_event(&mut self) -> Event {
+        let sequence = self.sequence;
+        let connection_info = self.connection_info(None);
+        let shutdown = ShutdownEvent {
+            event_type: "server_shutdown".to_string(),
+            message: "Server is shutting down".to_string(),
+            sequence,
+            timestamp: Utc::now(),
+            connection_info: connection_info.clone(),
+        };
+
+        let server_info = self.app_state.server_info.clone();
+        let (event_type, data) = self.serialize_json(&shutdown, &shutdown.event_type, || {
+            minimal_error_event(sequence, connection_info, server_info)
+        });
+
+        self.sequence += 1;
+        self.state.increment_events();
+
+        Event::default()
+            .event(event_type)
+            .id(self.app_state.sse_config.id_scheme.event_id(sequence))
+            .data(data)
+    }
+
+    /// Build a `metrics_delta` event from the changed fields
+    fn delta_event(&mut self, changes: serde_json::Value, connection_info: ConnectionInfo) -> Event {
+        let sequence = self.sequence;
+        let delta = MetricsDeltaEvent {
+            event_type: "metrics_delta".to_string(),
+            changes,
+            sequence,
+            timestamp: Utc::now(),
+            connection_info: connection_info.clone(),
+        };
+
+        let server_info = self.app_state.server_info.clone();
+        let (event_type, data) = self.serialize_json(&delta, &delta.event_type, || {
+            minimal_error_event(sequence, connection_info, server_info)
+        });
+
+        self.sequence += 1;
+        self.state.increment_events();
+
+        Event::default()
+            .event(event_type)
+            .id(self.app_state.sse_config.id_scheme.event_id(sequence))
+            .data(data)
+            .retry(Duration::from_secs(5))
+    }
+}
+
+impl Stream for MetricsStream {
+    type Item = Result<Event, Infallible>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // Shutdown wins over any pending tick: say goodbye once, then end the stream
+        match self.shutdown.as_mut().map(|shutdown| shutdown.as_mut().poll(cx)) {
+            None => return Poll::Ready(None),
+            Some(Poll::Ready(())) => {
+                self.shutdown = None;
+                self.pending_metrics = None;
+                info!("Closing SSE stream for client {:?} on server shutdown", self.state.client_id);
+                return Poll::Ready(Some(Ok(self.shutdown_event())));
+            }
+            Some(Poll::Pending) => {}
+        }
+
+        loop {
+            // Start a fetch when the interval fires; until then nothing is in flight
+            if self.pending_metrics.is_none() {
+                match self.interval_timer.poll_tick(cx) {
+                    Poll::Ready(_) => {
+                        let metrics_cache = self.app_state.metrics_cache.clone();
+                        let cache_key = self.state.shared_cache_key();
+                        self.pending_metrics = Some(Box::pin(async move {
+                            metrics_cache.get_metrics(Some(cache_key)).await
+                        }));
+                    }
+                    Poll::Pending => return Poll::Pending,
+                }
+            }
+
+            // Drive the fetch; it keeps its place across polls until it resolves
+            let metrics_result = match self.pending_metrics.as_mut().map(|fetch| fetch.as_mut().poll(cx)) {
+                Some(Poll::Ready(metrics_result)) => {
+                    self.pending_metrics = None;
+                    metrics_result
+                }
+                _ => return Poll::Pending,
+            };
+
+            // Unchanged samples are skipped; keep-alives hold the connection open
+            let unchanged = match &metrics_result {
+                MetricsResponse::Ok(metrics) | MetricsResponse::PartialData { data: metrics, .. } => {
+                    !self.state.should_send(metrics)
+                }
+                MetricsResponse::Error(_) => false,
+            };
+            if unchanged {
+                debug!("No change for SSE client {:?}, skipping tick", self.state.client_id);
+                // Loop so the timer registers for the next tick
+                continue;
+            }
+
+            return Poll::Ready(Some(Ok(self.build_event(metrics_result))));
+        }
+    }
+}
+
+/// Longest accepted `client_id`, in characters
+pub const MAX_CLIENT_ID_LENGTH: usize = 64;
+
+/// Check a client-supplied `client_id` before it reaches cache keys, logs and events
+pub fn validate_client_id(client_id: &str) -> Result<(), String> {
+    if client_id.is_empty() {
+        return Err("client_id must not be empty".to_string());
+    }
+    if client_id.chars().count() > MAX_CLIENT_ID_LENGTH {
+        return Err(format!("client_id must be at most {} characters", MAX_CLIENT_ID_LENGTH));
+    }
+    if client_id.chars().any(char::is_control) {
+        return Err("client_id must not contain control characters".to_string());
+    }
+    Ok(())
+}
+
+    /// GET /api/server-status-stream - Server-Sent Events stream for real-time metrics
+#[instrument(skip(state))]
+pub async fn server_status_stream(
+    Query(params): Query<SseQuery>,
+    State(state): State<ServerStatusState>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+) -> Result<Response, ServerStatusError> {
+    if let Some(client_id) = &params.client_id {
+        validate_client_id(client_id).map_err(|reason| {
+            warn!("Rejected SSE connection with invalid client_id: {}", reason);
+            ServerStatusError::BadRequest(reason)
+        })?;
+    }
+
+    let client_id = params.client_id.unwrap_or_else(|| {
+        format!("client_{}", uuid::Uuid::new_v4().to_string()[..8].to_string())
+    });
+    
+    let interval = state.sse_config.effective_interval_seconds(
+        params.interval.unwrap_or(5),
+        state.metrics_service.get_config().collection_interval_seconds,
+    );
+    let detailed = params.detailed.unwrap_or(true);
+    
+    let metrics_filter = params.metrics.as_deref().map(parse_metrics_filter);
+
+    let changes_only = params.changes_only.unwrap_or(false);
+    let delta = params.delta.unwrap_or(false);
+    let format = params.format.unwrap_or_default();
+    let compact = params.compact.unwrap_or(false);
+    let (remote_addr, user_agent) = state
+        .sse_config
+        .client_details(&headers, connect_info.map(|ConnectInfo(addr)| addr));
+
+    info!(
+        "New SSE connection: client_id={:?}, remote_addr={:?}, user_agent={:?}, interval={}s, detailed={}, filter={:?}, changes_only={}, delta={}, format={:?}, compact={}",
+        client_id, remote_addr, user_agent, interval, detailed, metrics_filter, changes_only, delta, format, compact
+    );
 
     // Create metrics stream
-    let stream = MetricsStream::new(client_id.clone(), interval, detailed, metrics_filter, state);
+    let keep_alive = state
+        .sse_config
+        .with_keep_alive_overrides(params.keepalive, params.keepalive_text.as_deref())
+        .keep_alive();
+    let change_epsilon = state.sse_config.change_epsilon;
+    let max_idle = state.sse_config.effective_max_idle(params.max_idle);
+    let slot = acquire_connection_slot(&state.sse_connections, state.max_sse_connections).map_err(|error| {
+        warn!("Rejected SSE connection for {:?}: {}", client_id, error);
+        error
+    })?;
+    let sequence = resume_sequence(&headers);
+    let mut stream = MetricsStream::new(client_id.clone(), interval, detailed, metrics_filter, state)
+        .holding_slot(slot)
+        .starting_at(sequence)
+        .client_details(remote_addr, user_agent);
+    if changes_only {
+        stream = stream.changes_only(change_epsilon, max_idle);
+    }
+    if delta {
+        stream = stream.delta();
+    }
+    if compact {
+        stream = stream.compact();
+    }
+    stream = stream.format(format);
 
     // Create SSE response
-    let sse = Sse::new(stream)
-        .keep_alive(
-            axum::response::sse::KeepAlive::new()
-                .interval(Duration::from_secs(30))
-                .text("ping"),
-        );
+    let sse = Sse::new(stream).keep_alive(keep_alive);
 
     // Add CORS headers for SSE
     let response = sse.into_response();
     
-    debug!("SSE stream initialized for client: {}", client_id);
-    response
+    debug!("SSE stream initialized for client: {:?}", client_id);
+    Ok(response)
+}
+
+/// Serialize an event payload, returning the event type and JSON to send
+///
+/// If serialization fails, the fallback event is sent instead so clients
+/// always receive a well-formed `MetricsEvent`.
+fn serialize_event_data<T: Serialize>(
+    event_data: &T,
+    event_type: &str,
+    fallback: impl FnOnce() -> MetricsEvent,
+) -> (String, String) {
+    match serde_json::to_string(event_data) {
+        Ok(json) => (event_type.to_string(), json),
+        Err(e) => {
+            // The metrics models have no fallible fields, so this should never happen
+            error!("Anomaly: failed to serialize SSE event data: {}", e);
+            let fallback = fallback();
+            let json = serde_json::to_string(&fallback)
+                .expect("Minimal error event must always serialize");
+            (fallback.event_type, json)
+        }
+    }
+}
+
+/// Build a minimal error event with zeroed metrics
+fn minimal_error_event(
+    sequence: u64,
+    connection_info: ConnectionInfo,
+    server_info: crate::models::ServerInfo,
+) -> MetricsEvent {
+    MetricsEvent {
+        event_type: "error".to_string(),
+        data: StatusData {
+            server_metrics: ServerMetrics::empty(),
+            collection_interval_seconds: 5,
+            server_info,
+            data_quality: DataQuality::default(),
+        },
+        sequence,
+        timestamp: Utc::now(),
+        connection_info,
+    }
 }
 
 /// Create a simplified version of metrics for non-detailed streams
-#[allow(dead_code)]
 fn create_simplified_metrics(full_metrics: ServerMetrics) -> ServerMetrics {
     ServerMetrics {
         timestamp: full_metrics.timestamp,
@@ -508,15 +1414,21 @@ fn create_simplified_metrics(full_metrics: ServerMetrics) -> ServerMetrics {
             used_bytes: full_metrics.memory_usage.used_bytes,
             available_bytes: full_metrics.memory_usage.available_bytes,
             usage_percentage: full_metrics.memory_usage.usage_percentage,
+            swap_total_bytes: 0,
+            swap_used_bytes: 0,
+            swap_usage_percentage: 0.0,
         },
         cpu_usage: crate::models::CpuMetrics {
             usage_percentage: full_metrics.cpu_usage.usage_percentage,
             core_count: full_metrics.cpu_usage.core_count,
+            scale: full_metrics.cpu_usage.scale,
             load_average: crate::models::cpu_metrics::LoadAverage {
                 one_minute: full_metrics.cpu_usage.load_average.one_minute,
                 five_minute: 0.0, // Remove extended load averages for simplified view
                 fifteen_minute: 0.0,
             },
+            core_usage_percentages: Vec::new(), // Remove per-core detail for simplified view
+            temperature_celsius: full_metrics.cpu_usage.temperature_celsius,
         },
         uptime: full_metrics.uptime,
         network_metrics: crate::models::NetworkMetrics {
@@ -525,68 +1437,212 @@ fn create_simplified_metrics(full_metrics: ServerMetrics) -> ServerMetrics {
             packets_sent: 0, // Remove packet details for simplified view
             packets_received: 0,
             active_connections: full_metrics.network_metrics.active_connections,
+            counter_reset: full_metrics.network_metrics.counter_reset,
+            rate_bytes_sent_per_sec: full_metrics.network_metrics.rate_bytes_sent_per_sec,
+            rate_bytes_received_per_sec: full_metrics.network_metrics.rate_bytes_received_per_sec,
         },
+        sources: full_metrics.sources,
+        gpu_metrics: full_metrics.gpu_metrics,
+        disk_metrics: full_metrics.disk_metrics.map(|disk| crate::models::DiskMetrics {
+            mounts: Vec::new(), // Remove per-mount detail for simplified view
+            ..disk
+        }),
+        process_metrics: full_metrics.process_metrics,
     }
 }
 
+/// Response body for the SSE info endpoint
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SseInfoResponse {
+    pub endpoint: String,
+    pub description: String,
+    pub parameters: SseInfoParameters,
+    pub events: SseInfoEvents,
+    pub headers: SseInfoHeaders,
+    pub example_url: String,
+    pub collection_interval_seconds: u32,
+    pub allow_sub_collection_interval: bool,
+    pub time_stream: SseInfoTimeStream,
+    /// Full key to short key mapping used by `compact=true` streams
+    #[serde(default)]
+    pub compact_keys: BTreeMap<String, String>,
+    pub api_version: String,
+}
+
+/// Reconnect settings of the time stream
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SseInfoTimeStream {
+    pub endpoint: String,
+    /// Description of the `retry_ms` query parameter
+    pub retry_ms: String,
+    /// Reconnect delay sent in the first event when `retry_ms` is not given
+    pub default_retry_ms: u64,
+}
+
+/// Query parameter descriptions for the SSE stream
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SseInfoParameters {
+    pub interval: String,
+    pub detailed: String,
+    pub client_id: String,
+    pub metrics: String,
+    #[serde(default)]
+    pub changes_only: String,
+    #[serde(default)]
+    pub max_idle: String,
+    #[serde(default)]
+    pub delta: String,
+    #[serde(default)]
+    pub format: String,
+    #[serde(default)]
+    pub compact: String,
+    #[serde(default)]
+    pub keepalive: String,
+    #[serde(default)]
+    pub keepalive_text: String,
+}
+
+/// Event type descriptions for the SSE stream
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SseInfoEvents {
+    pub metrics_update: String,
+    #[serde(default)]
+    pub metrics_delta: String,
+    #[serde(default)]
+    pub metrics_update_msgpack: String,
+    #[serde(default)]
+    pub server_shutdown: String,
+    #[serde(default)]
+    pub stale_data: String,
+    pub ping: String,
+}
+
+/// Response headers sent on the SSE stream
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SseInfoHeaders {
+    #[serde(rename = "Cache-Control")]
+    pub cache_control: String,
+    #[serde(rename = "Content-Type")]
+    pub content_type: String,
+    #[serde(rename = "Connection")]
+    pub connection: String,
+}
+
 /// Helper endpoint to get SSE connection info
-#[instrument(skip(_state))]
+#[instrument(skip(state))]
 pub async fn get_sse_info(
-    State(_state): State<ServerStatusState>,
+    State(state): State<ServerStatusState>,
 ) -> impl IntoResponse {
-    let info = serde_json::json!({
-        "endpoint": "/server-status-stream",
-        "description": "Server-Sent Events stream for real-time server metrics",
-        "parameters": {
-            "interval": "Update interval in seconds (1-60, default: 5)",
-            "detailed": "Include detailed metrics (default: true)",
-            "client_id": "Client identifier for connection tracking (optional)",
-            "metrics": "Comma-separated metric types: memory,cpu,network (default: all)"
+    let collection_interval = state.metrics_service.get_config().collection_interval_seconds;
+    let interval_description = if state.sse_config.allow_sub_collection_interval {
+        "Update interval in seconds (1-60, default: 5)".to_string()
+    } else {
+        format!(
+            "Update interval in seconds (1-60, default: 5); raised to the {}s collection interval if shorter",
+            collection_interval
+        )
+    };
+
+    let info = SseInfoResponse {
+        endpoint: "/server-status-stream".to_string(),
+        description: "Server-Sent Events stream for real-time server metrics".to_string(),
+        parameters: SseInfoParameters {
+            interval: interval_description,
+            detailed: "Include detailed metrics (default: true)".to_string(),
+            client_id: format!(
+                "Client identifier for connection tracking (optional, 1-{} characters, no control characters)",
+                MAX_CLIENT_ID_LENGTH
+            ),
+            metrics: "Comma-separated metric types: memory,cpu,load,network,gpu,disk,process; load keeps only the CPU load averages (default: all)".to_string(),
+            changes_only: format!(
+                "After the first event, send only when a percentage moves by more than {} or a counter changes; keep-alives fill the gaps (default: false)",
+                state.sse_config.change_epsilon
+            ),
+            max_idle: format!(
+                "Longest gap between changes_only events in seconds, clamped to {}-{} (default: {})",
+                MAX_IDLE_RANGE_SECONDS.start(),
+                MAX_IDLE_RANGE_SECONDS.end(),
+                state.sse_config.max_idle_seconds
+            ),
+            delta: "After a full first event, send metrics_delta events holding only changed fields, removed fields as null (default: false)".to_string(),
+            format: "Metrics update payload encoding: json or msgpack (base64 MessagePack in metrics_update_msgpack events, default: json)".to_string(),
+            compact: "Send JSON payloads with the short keys listed in compact_keys; other keys are unchanged (default: false)".to_string(),
+            keepalive: format!(
+                "Seconds between keep-alives, clamped to {}-{} (default: {})",
+                KEEP_ALIVE_RANGE_SECONDS.start(),
+                KEEP_ALIVE_RANGE_SECONDS.end(),
+                state.sse_config.keep_alive_interval_seconds
+            ),
+            keepalive_text: format!(
+                "Keep-alive text, up to {} characters without line breaks (default: {})",
+                MAX_KEEP_ALIVE_TEXT_LENGTH, state.sse_config.keep_alive_text
+            ),
         },
-        "events": {
-            "metrics_update": "Regular metrics update event",
-            "ping": "Keep-alive ping event"
+        events: SseInfoEvents {
+            metrics_update: "Regular metrics update event".to_string(),
+            metrics_delta: "Changed fields since the previous event (delta=true)".to_string(),
+            metrics_update_msgpack: "Base64 MessagePack metrics update (format=msgpack)".to_string(),
+            server_shutdown: "Final event before the server closes the stream for shutdown; do not reconnect immediately".to_string(),
+            stale_data: format!(
+                "Metrics update older than {}s; connection_info.data_age_seconds holds the age",
+                state.metrics_service.get_config().stale_threshold_seconds
+            ),
+            ping: "Keep-alive ping event".to_string(),
         },
-        "headers": {
-            "Cache-Control": "no-cache",
-            "Content-Type": "text/event-stream",
-            "Connection": "keep-alive"
+        headers: SseInfoHeaders {
+            cache_control: "no-cache".to_string(),
+            content_type: "text/event-stream".to_string(),
+            connection: "keep-alive".to_string(),
         },
-        "example_url": "/server-status-stream?interval=10&detailed=false&metrics=memory,cpu",
-        "api_version": "1.0"
-    });
+        example_url: "/server-status-stream?interval=10&detailed=false&metrics=memory,cpu".to_string(),
+        collection_interval_seconds: collection_interval,
+        allow_sub_collection_interval: state.sse_config.allow_sub_collection_interval,
+        time_stream: SseInfoTimeStream {
+            endpoint: "/api/time/stream".to_string(),
+            retry_ms: format!(
+                "Reconnect delay in milliseconds sent in the first event, clamped to {}-{} (default: {})",
+                TIME_STREAM_RETRY_RANGE_MS.start(),
+                TIME_STREAM_RETRY_RANGE_MS.end(),
+                DEFAULT_TIME_STREAM_RETRY_MS
+            ),
+            default_retry_ms: DEFAULT_TIME_STREAM_RETRY_MS,
+        },
+        compact_keys: COMPACT_KEYS
+            .iter()
+            .map(|(full, short)| (full.to_string(), short.to_string()))
+            .collect(),
+        api_version: "1.0".to_string(),
+    };
 
     axum::Json(info)
 }
 
+/// HEAD handler for event streams
+///
+/// axum answers HEAD with the GET handler, which would open a stream only to
+/// discard it, so SSE routes reject HEAD instead.
+pub async fn stream_head_not_allowed() -> impl IntoResponse {
+    (
+        axum::http::StatusCode::METHOD_NOT_ALLOWED,
+        [(axum::http::header::ALLOW, "GET")],
+    )
+}
+
 /// Create the SSE router
 pub fn create_sse_router() -> Router<ServerStatusState> {
     Router::new()
-        .route("/server-status-stream", get(server_status_stream))
+        .route("/server-status-stream", get(server_status_stream).head(stream_head_not_allowed))
         .route("/server-status-stream/info", get(get_sse_info))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::routes::test_support::{create_state_without_network, create_test_state};
     use crate::services::{MetricsService, MetricsCache};
     use axum_test::TestServer;
     use std::sync::Arc;
 
-    fn create_test_state() -> ServerStatusState {
-        let metrics_service = Arc::new(MetricsService::new());
-        let metrics_cache = Arc::new(MetricsCache::new(Arc::clone(&metrics_service)));
-        let server_info = crate::models::ServerInfo::new(
-            "test-server".to_string(),
-            "1.0.0".to_string(),
-            chrono::Utc::now(),
-            "development".to_string(),
-            crate::models::OsInfo::fallback(),
-        ).expect("Failed to create test ServerInfo");
-
-        ServerStatusState::new(metrics_cache, metrics_service, server_info)
-    }
-
     #[tokio::test]
     async fn test_sse_info_endpoint() {
         let state = create_test_state();
@@ -600,6 +1656,689 @@ mod tests {
         assert_eq!(body["endpoint"], "/server-status-stream");
         assert_eq!(body["api_version"], "1.0");
         assert!(body["parameters"].is_object());
+        // The payload must match the typed response
+        let info: SseInfoResponse = response.json();
+        assert_eq!(info.endpoint, "/server-status-stream");
+        assert_eq!(info.headers.content_type, "text/event-stream");
+        assert_eq!(info.time_stream.endpoint, "/api/time/stream");
+        assert_eq!(info.time_stream.default_retry_ms, 3000);
+    }
+
+    /// Payload whose serialization always fails
+    struct PoisonedEvent;
+
+    impl Serialize for PoisonedEvent {
+        fn serialize<S: serde::Serializer>(&self, _serializer: S) -> Result<S::Ok, S::Error> {
+            Err(serde::ser::Error::custom("poisoned field"))
+        }
+    }
+
+    #[test]
+    fn test_serialization_fallback_is_schema_valid() {
+        let connection_info = ConnectionInfo {
+            client_id: "client\"quoted".to_string(),
+            connection_duration_seconds: 3,
+            events_sent: 2,
+            update_interval_seconds: 5,
+            data_age_seconds: None,
+            remote_addr: None,
+            user_agent: None,
+        };
+        let server_info = create_test_state().server_info;
+
+        let (event_type, json) = serialize_event_data(&PoisonedEvent, "status-update", || {
+            minimal_error_event(2, connection_info, server_info)
+        });
+
+        assert_eq!(event_type, "error");
+        let event: MetricsEvent = serde_json::from_str(&json).expect("Fallback must match MetricsEvent schema");
+        assert_eq!(event.event_type, "error");
+        assert_eq!(event.sequence, 2);
+        assert_eq!(event.connection_info.client_id, "client\"quoted");
+        assert_eq!(event.data.server_metrics.memory_usage.total_bytes, 0);
+    }
+
+    #[test]
+    fn test_validate_client_id() {
+        assert!(validate_client_id("dashboard-01").is_ok());
+        assert!(validate_client_id(&"a".repeat(MAX_CLIENT_ID_LENGTH)).is_ok());
+        assert!(validate_client_id("").is_err());
+        assert!(validate_client_id(&"a".repeat(MAX_CLIENT_ID_LENGTH + 1)).is_err());
+        assert!(validate_client_id("line\nbreak").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_over_long_client_id_rejected() {
+        let server = TestServer::new(create_sse_router().with_state(create_test_state())).unwrap();
+
+        let response = server
+            .get("/server-status-stream")
+            .add_query_param("client_id", "a".repeat(MAX_CLIENT_ID_LENGTH + 1))
+            .await;
+        response.assert_status(axum::http::StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = response.json();
+        assert_eq!(body["code"], "INVALID_REQUEST");
+    }
+
+    #[tokio::test]
+    async fn test_control_character_client_id_rejected() {
+        let server = TestServer::new(create_sse_router().with_state(create_test_state())).unwrap();
+
+        let response = server
+            .get("/server-status-stream")
+            .add_query_param("client_id", "evil\r\nevent: forged")
+            .await;
+        response.assert_status(axum::http::StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_identical_params_share_cache_entry() {
+        use futures_util::StreamExt;
+
+        let state = create_test_state();
+        state.metrics_service.initialize().await.unwrap();
+
+        for i in 0..10 {
+            let filter = Some(vec!["memory".to_string(), "cpu".to_string()]);
+            let mut stream = MetricsStream::new(format!("client_{}", i), 5, false, filter, state.clone());
+            let _event = stream.next().await.unwrap().unwrap();
+        }
+
+        // Reordered filter, same parameters
+        let filter = Some(vec!["cpu".to_string(), "memory".to_string()]);
+        let mut stream = MetricsStream::new("client_x".to_string(), 5, false, filter, state.clone());
+        let _event = stream.next().await.unwrap().unwrap();
+
+        assert_eq!(state.metrics_cache.get_cache_keys().await, vec!["sse_5s_summary_cpu+memory".to_string()]);
+        assert_eq!(state.metrics_cache.get_stats().cache_misses, 1);
+    }
+
+    #[tokio::test]
+    async fn test_connection_limit() {
+        let state = create_test_state().with_max_sse_connections(2);
+        let connect = || server_status_stream(
+            Query(SseQuery { interval: None, detailed: None, client_id: None, metrics: None, changes_only: None, max_idle: None, delta: None, format: None, compact: None, keepalive: None, keepalive_text: None }),
+            State(state.clone()),
+            None,
+            HeaderMap::new(),
+        );
+
+        let first = connect().await.unwrap();
+        let second = connect().await.unwrap();
+        assert_eq!(state.sse_connections.load(Ordering::SeqCst), 2);
+
+        let rejected = connect().await.unwrap_err();
+        assert!(matches!(rejected, ServerStatusError::TooManyConnections { limit: 2 }));
+        let response = rejected.into_response();
+        assert_eq!(response.status(), axum::http::StatusCode::SERVICE_UNAVAILABLE);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], "SSE_CONNECTION_LIMIT");
+
+        // Closing a stream frees its slot
+        drop(first);
+        assert_eq!(state.sse_connections.load(Ordering::SeqCst), 1);
+        let _third = connect().await.unwrap();
+        drop(second);
+        assert_eq!(state.sse_connections.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_delta_events_after_full_snapshot() {
+        use futures_util::StreamExt;
+
+        let state = create_test_state();
+        state.metrics_service.initialize().await.unwrap();
+        // Seed the shared entry so both ticks read the same cached sample
+        let stream = MetricsStream::new("delta".to_string(), 1, true, None, state.clone()).delta();
+        state.metrics_cache.get_metrics(Some(stream.state.shared_cache_key())).await;
+
+        let mut body = Sse::new(stream.take(2)).into_response().into_body().into_data_stream();
+        let mut events = Vec::new();
+        while let Some(Ok(frame)) = body.next().await {
+            let frame = String::from_utf8(frame.to_vec()).unwrap();
+            let event_type = frame.lines().find_map(|line| line.strip_prefix("event:")).unwrap().trim().to_string();
+            let data = frame.lines().find_map(|line| line.strip_prefix("data:")).unwrap().trim().to_string();
+            events.push((event_type, serde_json::from_str::<serde_json::Value>(&data).unwrap()));
+        }
+
+        assert_eq!(events.len(), 2);
+        let (snapshot_type, snapshot) = &events[0];
+        assert_eq!(snapshot_type, "status-update");
+        assert!(snapshot["data"]["server_metrics"].is_object());
+
+        let (delta_type, delta) = &events[1];
+        assert_eq!(delta_type, "metrics_delta");
+        assert_eq!(delta["event_type"], "metrics_delta");
+        assert_eq!(delta["changes"], serde_json::json!({}));
+        assert_eq!(delta["sequence"], 1);
+    }
+
+    #[test]
+    fn test_json_delta() {
+        let previous = serde_json::json!({"cpu": {"usage": 10.0, "cores": 4}, "uptime": 5, "gpus": [1]});
+        let current = serde_json::json!({"cpu": {"usage": 12.5, "cores": 4}, "uptime": 5, "gpus": [1, 2]});
+        assert_eq!(
+            json_delta(&previous, &current),
+            serde_json::json!({"cpu": {"usage": 12.5}, "gpus": [1, 2]})
+        );
+        assert_eq!(json_delta(&current, &current), serde_json::json!({}));
+    }
+
+    #[test]
+    fn test_json_delta_sends_removed_keys_as_null() {
+        let previous = serde_json::json!({"disk": {"used": 1}, "cpu": {"usage": 10.0, "temperature": 40.0}});
+        let current = serde_json::json!({"cpu": {"usage": 10.0}});
+        assert_eq!(
+            json_delta(&previous, &current),
+            serde_json::json!({"disk": null, "cpu": {"temperature": null}})
+        );
+    }
+
+    #[test]
+    fn test_resume_sequence() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(resume_sequence(&headers), 0);
+
+        headers.insert("last-event-id", "41".parse().unwrap());
+        assert_eq!(resume_sequence(&headers), 42);
+
+        headers.insert("last-event-id", "not-a-number".parse().unwrap());
+        assert_eq!(resume_sequence(&headers), 0);
+
+        headers.insert("last-event-id", u64::MAX.to_string().parse().unwrap());
+        assert_eq!(resume_sequence(&headers), u64::MAX);
+    }
+
+    #[tokio::test]
+    async fn test_last_event_id_resumes_sequence() {
+        let server = TestServer::new_with_config(
+            create_sse_router().with_state(create_test_state()),
+            axum_test::TestServerConfig::builder().http_transport().build(),
+        ).unwrap();
+        let url = server.server_address().unwrap().join("server-status-stream").unwrap();
+
+        let mut response = reqwest::Client::new()
+            .get(url)
+            .header("Last-Event-ID", "42")
+            .send()
+            .await
+            .unwrap();
+        let chunk = tokio::time::timeout(Duration::from_secs(5), response.chunk())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        let frame = String::from_utf8(chunk.to_vec()).unwrap();
+
+        assert!(frame.lines().any(|line| line == "id: 43"), "expected id 43, got {:?}", frame);
+    }
+
+    #[tokio::test]
+    async fn test_connection_info_records_client_details() {
+        let server = TestServer::new_with_config(
+            create_sse_router().with_state(create_test_state()),
+            axum_test::TestServerConfig::builder().http_transport().build(),
+        ).unwrap();
+        let url = server.server_address().unwrap().join("server-status-stream").unwrap();
+
+        let mut response = reqwest::Client::new()
+            .get(url)
+            .header("X-Forwarded-For", "203.0.113.7, 10.0.0.1")
+            .header("User-Agent", "status-scraper/2.1")
+            .send()
+            .await
+            .unwrap();
+        let chunk = tokio::time::timeout(Duration::from_secs(5), response.chunk())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        let frame = String::from_utf8(chunk.to_vec()).unwrap();
+        let data = frame.lines().find_map(|line| line.strip_prefix("data: ")).unwrap();
+        let event: serde_json::Value = serde_json::from_str(data).unwrap();
+
+        assert_eq!(event["connection_info"]["remote_addr"], "203.0.113.7");
+        assert_eq!(event["connection_info"]["user_agent"], "status-scraper/2.1");
+    }
+
+    #[test]
+    fn test_client_details_redacted() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::USER_AGENT, "curl/8.5.0".parse().unwrap());
+        let peer = Some(SocketAddr::from(([198, 51, 100, 4], 51000)));
+
+        let config = SseStreamConfig::default();
+        assert_eq!(
+            config.client_details(&headers, peer),
+            (Some("198.51.100.4".to_string()), Some("curl/8.5.0".to_string()))
+        );
+
+        let config = SseStreamConfig { redact_ips: true, ..SseStreamConfig::default() };
+        assert_eq!(
+            config.client_details(&headers, peer),
+            (Some("redacted".to_string()), Some("curl/8.5.0".to_string()))
+        );
+        assert_eq!(config.client_details(&HeaderMap::new(), None), (None, None));
+    }
+
+    #[tokio::test]
+    async fn test_partial_data_quality_in_events() {
+        use futures_util::StreamExt;
+
+        let (state, _proc_root) = create_state_without_network();
+        state.metrics_service.initialize().await.unwrap();
+        let mut stream = Sse::new(MetricsStream::new("partial".to_string(), 1, true, None, state))
+            .into_response()
+            .into_body()
+            .into_data_stream();
+
+        let chunk = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        let frame = String::from_utf8(chunk.to_vec()).unwrap();
+        let data = frame.lines().find_map(|line| line.strip_prefix("data: ")).unwrap();
+        let event: MetricsEvent = serde_json::from_str(data).unwrap();
+
+        assert_eq!(event.event_type, "status-update");
+        assert_eq!(
+            event.data.data_quality,
+            DataQuality::Partial { missing: vec!["network".to_string()] }
+        );
+    }
+
+    /// Render a single SSE event as it is written to the wire
+    async fn event_frame(event: Event) -> String {
+        use futures_util::StreamExt;
+
+        let mut body = Sse::new(futures_util::stream::iter([Ok::<_, Infallible>(event)]))
+            .into_response()
+            .into_body()
+            .into_data_stream();
+        String::from_utf8(body.next().await.unwrap().unwrap().to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_stale_data_event() {
+        let state = create_test_state();
+        state.metrics_service.initialize().await.unwrap();
+        let metrics = state.metrics_service.get_metrics().await.data().unwrap();
+        let mut stream = MetricsStream::new("stale".to_string(), 5, true, None, state);
+
+        let mut old_metrics = metrics.clone();
+        old_metrics.timestamp = Utc::now() - chrono::Duration::seconds(60);
+        let frame = event_frame(stream.build_event(MetricsResponse::Ok(old_metrics))).await;
+        assert!(frame.lines().any(|line| line == "event: stale_data"), "got {:?}", frame);
+        let data = frame.lines().find_map(|line| line.strip_prefix("data: ")).unwrap();
+        let event: MetricsEvent = serde_json::from_str(data).unwrap();
+        assert_eq!(event.event_type, "stale_data");
+        assert!(event.connection_info.data_age_seconds.unwrap() >= 60);
+
+        let frame = event_frame(stream.build_event(MetricsResponse::Ok(metrics))).await;
+        let data = frame.lines().find_map(|line| line.strip_prefix("data: ")).unwrap();
+        let event: MetricsEvent = serde_json::from_str(data).unwrap();
+        assert_eq!(event.event_type, "status-update");
+        assert_eq!(event.connection_info.data_age_seconds, None);
+        assert!(!data.contains("data_age_seconds"));
+    }
+
+    #[tokio::test]
+    async fn test_compact_event_smaller_than_default() {
+        let state = create_test_state();
+        state.metrics_service.initialize().await.unwrap();
+        let metrics = state.metrics_service.get_metrics().await.data().unwrap();
+        let mut full = MetricsStream::new("compact".to_string(), 5, true, None, state.clone());
+        let mut compact = MetricsStream::new("compact".to_string(), 5, true, None, state).compact();
+
+        let full_frame = event_frame(full.build_event(MetricsResponse::Ok(metrics.clone()))).await;
+        let compact_frame = event_frame(compact.build_event(MetricsResponse::Ok(metrics))).await;
+        let full_data = full_frame.lines().find_map(|line| line.strip_prefix("data: ")).unwrap();
+        let compact_data = compact_frame.lines().find_map(|line| line.strip_prefix("data: ")).unwrap();
+
+        assert!(
+            compact_data.len() * 10 <= full_data.len() * 7,
+            "compact event is {} bytes, full event {} bytes",
+            compact_data.len(),
+            full_data.len()
+        );
+
+        // Expanding the short keys gives back a full event
+        fn expand(value: serde_json::Value) -> serde_json::Value {
+            use serde_json::Value;
+            match value {
+                Value::Object(fields) => Value::Object(
+                    fields
+                        .into_iter()
+                        .map(|(key, value)| {
+                            let key = COMPACT_KEYS
+                                .iter()
+                                .find(|(_, short)| *short == key)
+                                .map_or(key, |(full, _)| full.to_string());
+                            (key, expand(value))
+                        })
+                        .collect(),
+                ),
+                Value::Array(items) => Value::Array(items.into_iter().map(expand).collect()),
+                other => other,
+            }
+        }
+        let expanded = expand(serde_json::from_str(compact_data).unwrap()).to_string();
+        let event: MetricsEvent = serde_json::from_str(&expanded).unwrap();
+        assert_eq!(event.event_type, "status-update");
+        assert_eq!(event.connection_info.client_id, "compact");
+    }
+
+    #[test]
+    fn test_compact_keys_unambiguous() {
+        let shorts: std::collections::HashSet<&str> = COMPACT_KEYS.iter().map(|(_, short)| *short).collect();
+        let fulls: std::collections::HashSet<&str> = COMPACT_KEYS.iter().map(|(full, _)| *full).collect();
+        assert_eq!(shorts.len(), COMPACT_KEYS.len(), "short keys must be unique");
+        assert_eq!(fulls.len(), COMPACT_KEYS.len(), "full keys must be listed once");
+        // Keys left unmapped must not be mistaken for short keys
+        for unmapped in ["name", "index", "pid", "top", "secs", "nanos", "status", "missing"] {
+            assert!(!shorts.contains(unmapped), "{} is both unmapped and a short key", unmapped);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_msgpack_format_round_trip() {
+        use base64::Engine;
+
+        let state = create_test_state();
+        state.metrics_service.initialize().await.unwrap();
+        let server = TestServer::new_with_config(
+            create_sse_router().with_state(state),
+            axum_test::TestServerConfig::builder().http_transport().build(),
+        ).unwrap();
+        let mut url = server.server_address().unwrap().join("server-status-stream").unwrap();
+        url.set_query(Some("format=msgpack"));
+
+        let mut response = reqwest::Client::new()
+            .get(url)
+            .header("Last-Event-ID", "6")
+            .send()
+            .await
+            .unwrap();
+        let chunk = tokio::time::timeout(Duration::from_secs(5), response.chunk())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        let frame = String::from_utf8(chunk.to_vec()).unwrap();
+
+        assert!(frame.lines().any(|line| line == format!("event: {}", MSGPACK_EVENT_TYPE)), "got {:?}", frame);
+        let data = frame.lines().find_map(|line| line.strip_prefix("data: ")).unwrap();
+        let bytes = base64::engine::general_purpose::STANDARD.decode(data).unwrap();
+        let event: MetricsEvent = rmp_serde::from_slice(&bytes).unwrap();
+
+        assert_eq!(event.sequence, 7);
+        assert_eq!(event.event_type, "status-update");
+        assert!(event.data.server_metrics.memory_usage.total_bytes > 0);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_event_ends_stream() {
+        use futures_util::StreamExt;
+
+        let state = create_test_state();
+        state.metrics_service.initialize().await.unwrap();
+        let mut stream = Sse::new(MetricsStream::new("shutdown".to_string(), 1, true, None, state.clone()))
+            .into_response()
+            .into_body()
+            .into_data_stream();
+
+        // First tick fires immediately
+        let first = stream.next().await.unwrap().unwrap();
+        assert!(String::from_utf8(first.to_vec()).unwrap().contains("event: status-update"));
+
+        state.shutdown.send(()).unwrap();
+        let frame = tokio::time::timeout(Duration::from_secs(1), stream.next())
+            .await
+            .expect("shutdown event should be sent without waiting for the next tick")
+            .unwrap()
+            .unwrap();
+        let frame = String::from_utf8(frame.to_vec()).unwrap();
+        assert!(frame.lines().any(|line| line == "event: server_shutdown"), "got {:?}", frame);
+        let data = frame.lines().find_map(|line| line.strip_prefix("data: ")).unwrap();
+        let event: ShutdownEvent = serde_json::from_str(data).unwrap();
+        assert_eq!(event.event_type, "server_shutdown");
+        assert_eq!(event.sequence, 1);
+
+        let end = tokio::time::timeout(Duration::from_secs(1), stream.next()).await.unwrap();
+        assert!(end.is_none());
+    }
+
+    #[test]
+    fn test_stream_yields_on_current_thread_runtime() {
+        use futures_util::StreamExt;
+
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        runtime.block_on(async {
+            let state = create_test_state();
+            state.metrics_service.initialize().await.unwrap();
+
+            let stream = MetricsStream::new("current_thread".to_string(), 1, true, None, state);
+            let events: Vec<_> = tokio::time::timeout(Duration::from_secs(5), stream.take(2).collect())
+                .await
+                .expect("stream should yield without blocking the runtime");
+            assert_eq!(events.len(), 2);
+            assert!(events.iter().all(|event| event.is_ok()));
+        });
+    }
+
+    #[tokio::test]
+    async fn test_hostile_client_id_cannot_forge_events() {
+        use futures_util::StreamExt;
+
+        let client_id = "evil\"\nevent: forged\ndata: {}\n".to_string();
+        let state = create_test_state();
+        state.metrics_service.initialize().await.unwrap();
+
+        let stream = MetricsStream::new(client_id.clone(), 5, true, None, state).take(1);
+        let mut body = Sse::new(stream).into_response().into_body().into_data_stream();
+        let frame = String::from_utf8(body.next().await.unwrap().unwrap().to_vec()).unwrap();
+
+        // Exactly one event and one data line - the newlines stayed inside the JSON string
+        assert_eq!(frame.lines().filter(|line| line.starts_with("event:")).count(), 1);
+        assert!(!frame.lines().any(|line| line == "event: forged"));
+
+        let data_lines: Vec<&str> = frame.lines().filter(|line| line.starts_with("data:")).collect();
+        assert_eq!(data_lines.len(), 1);
+        let event: MetricsEvent = serde_json::from_str(data_lines[0].trim_start_matches("data:").trim()).unwrap();
+        assert_eq!(event.connection_info.client_id, client_id);
+    }
+
+    #[tokio::test]
+    async fn test_changes_only_sends_initial_snapshot_then_keep_alives() {
+        let state = create_test_state();
+        state.metrics_service.initialize().await.unwrap();
+        // Seed the shared entry so every tick re-reads the same cached sample
+        let stream = MetricsStream::new("static".to_string(), 1, true, None, state.clone())
+            .changes_only(0.5, Duration::from_secs(60));
+        state.metrics_cache.get_metrics(Some(stream.state.shared_cache_key())).await;
+
+        let frames = collect_frames(stream, 400, Duration::from_millis(2500)).await;
+
+        let events = frames.iter().filter(|frame| frame.lines().any(|line| line.starts_with("event:"))).count();
+        let keep_alives = frames.iter().filter(|frame| frame.trim() == ": ping").count();
+        assert_eq!(events, 1, "expected only the initial snapshot, got {:?}", frames);
+        assert!(keep_alives >= 2, "expected keep-alives between ticks, got {:?}", frames);
+    }
+
+    #[test]
+    fn test_changes_only_sends_when_only_swap_changes() {
+        let mut state = SseConnectionState::new("swap".to_string(), 1, true, None);
+        state.changes_only = Some(0.5);
+
+        let sample = ServerMetrics::empty();
+        assert!(state.should_send(&sample));
+        assert!(!state.should_send(&sample));
+
+        // CPU and memory usage are flat; swap thrash alone is worth an event
+        let mut swapping = sample.clone();
+        swapping.memory_usage.swap_usage_percentage = 60.0;
+        assert!(state.should_send(&swapping));
+    }
+
+    /// Collect the stream's frames for `window`
+    async fn collect_frames(stream: MetricsStream, keep_alive_ms: u64, window: Duration) -> Vec<String> {
+        use futures_util::StreamExt;
+
+        let keep_alive = KeepAlive::new().interval(Duration::from_millis(keep_alive_ms)).text("ping");
+        let mut body = Sse::new(stream).keep_alive(keep_alive).into_response().into_body().into_data_stream();
+
+        let mut frames = Vec::new();
+        let _ = tokio::time::timeout(window, async {
+            while let Some(Ok(frame)) = body.next().await {
+                frames.push(String::from_utf8(frame.to_vec()).unwrap());
+            }
+        })
+        .await;
+        frames
+    }
+
+    #[tokio::test]
+    async fn test_changes_only_resends_after_max_idle() {
+        let state = create_test_state();
+        state.metrics_service.initialize().await.unwrap();
+        let stream = MetricsStream::new("idle".to_string(), 1, true, None, state.clone())
+            .changes_only(0.5, Duration::from_secs(1));
+        state.metrics_cache.get_metrics(Some(stream.state.shared_cache_key())).await;
+
+        let frames = collect_frames(stream, 400, Duration::from_millis(2500)).await;
+        let events = frames.iter().filter(|frame| frame.lines().any(|line| line.starts_with("event:"))).count();
+        assert!(events >= 2, "expected an event once max_idle passed, got {:?}", frames);
+    }
+
+    #[test]
+    fn test_effective_max_idle() {
+        let config = SseStreamConfig::default();
+        assert_eq!(config.effective_max_idle(None), Duration::from_secs(60));
+        assert_eq!(config.effective_max_idle(Some(10)), Duration::from_secs(10));
+        assert_eq!(config.effective_max_idle(Some(0)), Duration::from_secs(1));
+        assert_eq!(config.effective_max_idle(Some(86_400)), Duration::from_secs(3600));
+    }
+
+    #[test]
+    fn test_keep_alive_overrides() {
+        let config = SseStreamConfig::default();
+
+        let unchanged = config.with_keep_alive_overrides(None, None);
+        assert_eq!(unchanged.keep_alive_interval_seconds, 30);
+        assert_eq!(unchanged.keep_alive_text, "ping");
+
+        let custom = config.with_keep_alive_overrides(Some(10), Some("still here"));
+        assert_eq!(custom.keep_alive_interval_seconds, 10);
+        assert_eq!(custom.keep_alive_text, "still here");
+
+        assert_eq!(config.with_keep_alive_overrides(Some(1), None).keep_alive_interval_seconds, 5);
+        assert_eq!(config.with_keep_alive_overrides(Some(3600), None).keep_alive_interval_seconds, 120);
+        assert_eq!(config.with_keep_alive_overrides(None, Some("a\r\nb")).keep_alive_text, "ab");
+        assert_eq!(config.with_keep_alive_overrides(None, Some("\n")).keep_alive_text, "ping");
+    }
+
+    #[tokio::test]
+    async fn test_keepalive_query_params_accepted() {
+        use tower::ServiceExt;
+
+        let state = create_test_state();
+        state.metrics_service.initialize().await.unwrap();
+        let app = create_sse_router().with_state(state);
+
+        for query in ["keepalive=10", "keepalive=1", "keepalive=100000&keepalive_text=hello%0Aworld"] {
+            let response = app
+                .clone()
+                .oneshot(
+                    axum::http::Request::builder()
+                        .uri(format!("/server-status-stream?{}", query))
+                        .body(axum::body::Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), axum::http::StatusCode::OK, "{} was rejected", query);
+        }
+    }
+
+    #[test]
+    fn test_event_id_schemes() {
+        assert_eq!(EventIdScheme::Sequence.event_id(7), "7");
+
+        let id = EventIdScheme::Uuid.event_id(7);
+        assert!(uuid::Uuid::parse_str(&id).is_ok());
+        assert_ne!(id, EventIdScheme::Uuid.event_id(7));
+
+        let id = EventIdScheme::TimestampSeq.event_id(7);
+        let (millis, seq) = id.split_once('-').expect("Expected <epoch_ms>-<seq>");
+        assert!(millis.parse::<i64>().unwrap() > 0);
+        assert_eq!(seq, "7");
+    }
+
+    #[test]
+    fn test_event_id_scheme_parsing() {
+        assert_eq!("sequence".parse::<EventIdScheme>(), Ok(EventIdScheme::Sequence));
+        assert_eq!("UUID".parse::<EventIdScheme>(), Ok(EventIdScheme::Uuid));
+        assert_eq!("timestamp_seq".parse::<EventIdScheme>(), Ok(EventIdScheme::TimestampSeq));
+        assert!("random".parse::<EventIdScheme>().is_err());
+        assert_eq!(SseStreamConfig::default().id_scheme, EventIdScheme::Sequence);
+    }
+
+    #[test]
+    fn test_interval_raised_to_collection_interval() {
+        let config = SseStreamConfig::default();
+        assert_eq!(config.effective_interval_seconds(1, 5), 5);
+        assert_eq!(config.effective_interval_seconds(10, 5), 10);
+        assert_eq!(config.effective_interval_seconds(120, 5), 60);
+
+        let config = SseStreamConfig {
+            allow_sub_collection_interval: true,
+            ..SseStreamConfig::default()
+        };
+        assert_eq!(config.effective_interval_seconds(1, 5), 1);
+    }
+
+    #[tokio::test]
+    async fn test_sse_info_documents_interval_minimum() {
+        let app = create_sse_router().with_state(create_test_state());
+        let server = TestServer::new(app).unwrap();
+
+        let info: serde_json::Value = server.get("/server-status-stream/info").await.json();
+        assert_eq!(info["collection_interval_seconds"], 5);
+        assert_eq!(info["allow_sub_collection_interval"], false);
+        assert!(info["parameters"]["interval"].as_str().unwrap().contains("5s collection interval"));
+    }
+
+    async fn first_keep_alive_frame(config: SseStreamConfig) -> String {
+        use futures_util::StreamExt;
+
+        let sse = Sse::new(futures_util::stream::pending::<Result<Event, Infallible>>())
+            .keep_alive(config.keep_alive());
+        let mut body = sse.into_response().into_body().into_data_stream();
+        let frame = body.next().await.unwrap().unwrap();
+        String::from_utf8(frame.to_vec()).unwrap()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_keep_alive_is_comment_line() {
+        let frame = first_keep_alive_frame(SseStreamConfig::default()).await;
+        // Comment lines start with ':' and are never dispatched as messages
+        assert!(frame.starts_with(':'));
+        assert_eq!(frame.trim_start_matches(':').trim(), "ping");
+        assert!(!frame.contains("data:"));
+        assert!(!frame.contains("event:"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_keep_alive_as_named_event() {
+        let config = SseStreamConfig {
+            keep_alive_mode: KeepAliveMode::Event,
+            ..SseStreamConfig::default()
+        };
+        let frame = first_keep_alive_frame(config).await;
+        assert!(frame.contains("event: keepalive\n"));
+        assert!(frame.contains("data: ping\n"));
     }
 
     #[tokio::test]
@@ -637,18 +2376,28 @@ mod tests {
                 used_bytes: 500000000,
                 available_bytes: 500000000,
                 usage_percentage: 50.0,
+                swap_total_bytes: 0,
+                swap_used_bytes: 0,
+                swap_usage_percentage: 0.0,
             },
             cpu_usage: crate::models::CpuMetrics {
                 usage_percentage: 25.0,
                 core_count: 2,
+                scale: crate::models::CpuScale::Average,
                 load_average: crate::models::cpu_metrics::LoadAverage {
                     one_minute: 1.5,
                     five_minute: 1.2,
                     fifteen_minute: 1.0,
                 },
+                core_usage_percentages: Vec::new(),
+                temperature_celsius: None,
             },
             uptime: std::time::Duration::from_secs(3600), // 1 hour
             network_metrics: crate::models::NetworkMetrics::default(),
+            sources: Default::default(),
+            gpu_metrics: Vec::new(),
+            disk_metrics: None,
+            process_metrics: None,
         };
 
         let filtered = stream.filter_metrics(full_metrics);
@@ -663,6 +2412,77 @@ mod tests {
         assert_eq!(filtered.network_metrics.bytes_received, 0);
     }
 
+    /// Read the first status event a stream sends
+    async fn first_event(stream: MetricsStream) -> MetricsEvent {
+        use futures_util::StreamExt;
+
+        let mut body = Sse::new(stream).into_response().into_body().into_data_stream();
+        let chunk = tokio::time::timeout(Duration::from_secs(5), body.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        let frame = String::from_utf8(chunk.to_vec()).unwrap();
+        let data = frame.lines().find_map(|line| line.strip_prefix("data: ")).unwrap();
+        serde_json::from_str(data).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_load_only_filter() {
+        let filter = parse_metrics_filter("load, bogus");
+        assert_eq!(filter, vec!["load"]);
+
+        let state = create_test_state();
+        state.metrics_service.initialize().await.unwrap();
+        let stream = MetricsStream::new("test_client".to_string(), 5, true, Some(filter), state.clone());
+        let cache_key = stream.cache_key();
+
+        let event = first_event(stream).await;
+        let (cached, _, _) = state.metrics_cache.get_cache_entry_details(&cache_key).await.unwrap();
+        let streamed = event.data.server_metrics;
+
+        // Load averages survive, the rest of the CPU data doesn't
+        assert_eq!(streamed.cpu_usage.load_average.one_minute, cached.cpu_usage.load_average.one_minute);
+        assert_eq!(streamed.cpu_usage.load_average.five_minute, cached.cpu_usage.load_average.five_minute);
+        assert_eq!(streamed.cpu_usage.load_average.fifteen_minute, cached.cpu_usage.load_average.fifteen_minute);
+        assert_eq!(streamed.cpu_usage.usage_percentage, 0.0);
+        assert!(streamed.cpu_usage.core_usage_percentages.is_empty());
+        assert_eq!(streamed.memory_usage.total_bytes, 0);
+        assert_ne!(cached.memory_usage.total_bytes, 0);
+    }
+
+    #[tokio::test]
+    async fn test_process_filter() {
+        let metrics_service = Arc::new(MetricsService::with_config(
+            crate::services::metrics_service::MetricsServiceConfig {
+                collect_processes: true,
+                ..Default::default()
+            },
+        ));
+        metrics_service.initialize().await.unwrap();
+        let state = ServerStatusState::new(
+            Arc::new(MetricsCache::new(Arc::clone(&metrics_service))),
+            metrics_service,
+            create_test_state().server_info,
+        );
+        let stream_with = |filter: &str| MetricsStream::new(
+            "test_client".to_string(),
+            5,
+            true,
+            Some(parse_metrics_filter(filter)),
+            state.clone(),
+        );
+
+        let event = first_event(stream_with("process")).await;
+        let processes = event.data.server_metrics.process_metrics.expect("process metrics requested");
+        assert!(processes.total_processes > 0);
+        assert_eq!(event.data.server_metrics.memory_usage.total_bytes, 0);
+
+        let event = first_event(stream_with("cpu,memory")).await;
+        assert!(event.data.server_metrics.process_metrics.is_none());
+        assert_ne!(event.data.server_metrics.memory_usage.total_bytes, 0);
+    }
+
     #[test]
     fn test_simplified_metrics() {
         let full_metrics = ServerMetrics {
@@ -672,15 +2492,21 @@ mod tests {
                 used_bytes: 500000000,   // 500MB
                 available_bytes: 500000000, // 500MB
                 usage_percentage: 50.0,
+                swap_total_bytes: 0,
+                swap_used_bytes: 0,
+                swap_usage_percentage: 0.0,
             },
             cpu_usage: crate::models::CpuMetrics {
                 usage_percentage: 25.0,
                 core_count: 4,
+                scale: crate::models::CpuScale::Average,
                 load_average: crate::models::cpu_metrics::LoadAverage {
                     one_minute: 1.5,
                     five_minute: 1.2,
                     fifteen_minute: 1.0,
                 },
+                core_usage_percentages: Vec::new(),
+                temperature_celsius: None,
             },
             uptime: std::time::Duration::from_secs(3600), // 1 hour
             network_metrics: crate::models::NetworkMetrics {
@@ -689,7 +2515,14 @@ mod tests {
                 packets_sent: 1000,
                 packets_received: 2000,
                 active_connections: 10,
+                counter_reset: false,
+                rate_bytes_sent_per_sec: 0.0,
+                rate_bytes_received_per_sec: 0.0,
             },
+            sources: Default::default(),
+            gpu_metrics: Vec::new(),
+            disk_metrics: None,
+            process_metrics: None,
         };
 
         let simplified = create_simplified_metrics(full_metrics);
@@ -713,6 +2546,13 @@ mod tests {
             detailed: Some(false),
             client_id: Some("test_client".to_string()),
             metrics: Some("memory,cpu".to_string()),
+            changes_only: None,
+            max_idle: None,
+            delta: None,
+            format: None,
+            compact: None,
+            keepalive: None,
+            keepalive_text: None,
         };
         
         assert_eq!(query.interval.unwrap(), 10);
@@ -737,6 +2577,10 @@ mod tests {
                     cpu_usage: crate::models::CpuMetrics::default(),
                     uptime: std::time::Duration::from_secs(0),
                     network_metrics: crate::models::NetworkMetrics::default(),
+                    sources: Default::default(),
+                    gpu_metrics: Vec::new(),
+                    disk_metrics: None,
+                    process_metrics: None,
                 },
                 5,
                 crate::models::ServerInfo::new(
@@ -754,6 +2598,9 @@ mod tests {
                 connection_duration_seconds: 10,
                 events_sent: 1,
                 update_interval_seconds: 5,
+                data_age_seconds: None,
+                remote_addr: None,
+                user_agent: None,
             },
         };
 
@@ -0,0 +1,157 @@
+// WebSocket transport for real-time server metrics
+// Pushes the same MetricsEvent JSON as the SSE stream, for networks whose proxies buffer SSE
+
+use crate::routes::server_status::{ServerStatusError, ServerStatusState};
+use crate::routes::server_status_stream::{acquire_connection_slot, validate_client_id, MetricsStream};
+use axum::{
+    extract::{
+        ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade},
+        Query, State,
+    },
+    response::Response,
+    routing::get,
+    Router,
+};
+use serde::Deserialize;
+use std::time::Duration;
+use tokio::time::{interval, interval_at, Instant, MissedTickBehavior};
+use tracing::{debug, info, instrument, warn};
+
+/// WebSocket close code sent when the server shuts down ("going away")
+const CLOSE_GOING_AWAY: u16 = 1001;
+
+/// Query parameters for the WebSocket endpoint
+#[derive(Debug, Deserialize)]
+pub struct WsQuery {
+    /// Update interval in seconds, as for the SSE stream (default: 5)
+    pub interval: Option<u32>,
+    /// Client identifier for connection tracking
+    pub client_id: Option<String>,
+}
+
+/// Message a client may send over the socket
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WsClientMessage {
+    /// New update interval in seconds, clamped like the `interval` parameter
+    pub set_interval: u32,
+}
+
+/// Timer firing every `seconds`, first after `first_tick`
+fn update_timer(first_tick: Instant, seconds: u32) -> tokio::time::Interval {
+    let mut timer = interval_at(first_tick, Duration::from_secs(seconds as u64));
+    timer.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    timer
+}
+
+/// GET /api/server-status-ws - WebSocket stream of `MetricsEvent` JSON
+///
+/// Shares the SSE connection limit. Clients change the update interval by
+/// sending `{"set_interval": <seconds>}`.
+#[instrument(skip(state, ws))]
+pub async fn server_status_ws(
+    ws: WebSocketUpgrade,
+    Query(params): Query<WsQuery>,
+    State(state): State<ServerStatusState>,
+) -> Result<Response, ServerStatusError> {
+    if let Some(client_id) = &params.client_id {
+        validate_client_id(client_id).map_err(|reason| {
+            warn!("Rejected WebSocket connection with invalid client_id: {}", reason);
+            ServerStatusError::BadRequest(reason)
+        })?;
+    }
+
+    let client_id = params.client_id.unwrap_or_else(|| {
+        format!("ws_{}", &uuid::Uuid::new_v4().to_string()[..8])
+    });
+    let interval_seconds = state.sse_config.effective_interval_seconds(
+        params.interval.unwrap_or(5),
+        state.metrics_service.get_config().collection_interval_seconds,
+    );
+
+    let slot = acquire_connection_slot(&state.sse_connections, state.max_sse_connections).map_err(|error| {
+        warn!("Rejected WebSocket connection for {:?}: {}", client_id, error);
+        error
+    })?;
+
+    info!("New WebSocket connection: client_id={:?}, interval={}s", client_id, interval_seconds);
+    let stream = MetricsStream::new(client_id, interval_seconds, true, None, state.clone()).holding_slot(slot);
+    Ok(ws.on_upgrade(move |socket| run_socket(socket, stream, state, interval_seconds)))
+}
+
+/// Push events until the client leaves or the server shuts down
+async fn run_socket(mut socket: WebSocket, mut stream: MetricsStream, state: ServerStatusState, interval_seconds: u32) {
+    let client_id = stream.client_id().to_string();
+    let mut updates = update_timer(Instant::now(), interval_seconds);
+    // Pings detect peers that vanished without closing the socket
+    let ping_every = Duration::from_secs(state.sse_config.keep_alive_interval_seconds);
+    let mut pings = interval(ping_every);
+    pings.reset();
+    let mut shutdown = state.shutdown.subscribe();
+
+    loop {
+        tokio::select! {
+            _ = updates.tick() => {
+                let metrics_result = state.metrics_cache.get_metrics(Some(stream.cache_key())).await;
+                let event = stream.next_metrics_event(metrics_result);
+                let json = match serde_json::to_string(&event) {
+                    Ok(json) => json,
+                    Err(e) => {
+                        warn!("Failed to serialize WebSocket event for {}: {}", client_id, e);
+                        continue;
+                    }
+                };
+                if socket.send(Message::Text(json)).await.is_err() {
+                    debug!("WebSocket client {} went away", client_id);
+                    break;
+                }
+            }
+            _ = pings.tick() => {
+                if socket.send(Message::Ping(Vec::new())).await.is_err() {
+                    debug!("WebSocket client {} went away", client_id);
+                    break;
+                }
+            }
+            message = socket.recv() => match message {
+                Some(Ok(Message::Text(text))) => match serde_json::from_str::<WsClientMessage>(&text) {
+                    Ok(WsClientMessage { set_interval }) => {
+                        let seconds = state.sse_config.effective_interval_seconds(
+                            set_interval,
+                            state.metrics_service.get_config().collection_interval_seconds,
+                        );
+                        info!("WebSocket client {} changed interval to {}s", client_id, seconds);
+                        stream.set_interval_seconds(seconds);
+                        updates = update_timer(Instant::now() + Duration::from_secs(seconds as u64), seconds);
+                    }
+                    Err(e) => warn!("Ignoring WebSocket message from {}: {}", client_id, e),
+                },
+                // Pongs to incoming pings are sent automatically
+                Some(Ok(Message::Ping(_))) | Some(Ok(Message::Pong(_))) => {}
+                Some(Ok(Message::Binary(_))) => warn!("Ignoring binary WebSocket message from {}", client_id),
+                Some(Ok(Message::Close(_))) | None => {
+                    info!("WebSocket client {} disconnected", client_id);
+                    break;
+                }
+                Some(Err(e)) => {
+                    debug!("WebSocket error for {}: {}", client_id, e);
+                    break;
+                }
+            },
+            _ = shutdown.recv() => {
+                info!("Closing WebSocket for {} on server shutdown", client_id);
+                let _ = socket
+                    .send(Message::Close(Some(CloseFrame {
+                        code: CLOSE_GOING_AWAY,
+                        reason: "server shutdown".into(),
+                    })))
+                    .await;
+                break;
+            }
+        }
+    }
+}
+
+/// Create the WebSocket router
+pub fn create_router() -> Router<ServerStatusState> {
+    Router::new().route("/server-status-ws", get(server_status_ws))
+}
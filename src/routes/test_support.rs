@@ -0,0 +1,43 @@
+// Shared fixtures for route handler tests
+
+use crate::models::{OsInfo, ServerInfo};
+use crate::routes::server_status::ServerStatusState;
+use crate::services::metrics_service::MetricsServiceConfig;
+use crate::services::{MetricsCache, MetricsService};
+use chrono::Utc;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+/// State backed by a default metrics service and cache
+pub(crate) fn create_test_state() -> ServerStatusState {
+    state_for(Arc::new(MetricsService::new()))
+}
+
+/// State whose network collector fails: `net/dev` under its proc root is a directory
+///
+/// The proc root is deleted when the returned guard is dropped, so keep it
+/// alive for as long as the state is used.
+pub(crate) fn create_state_without_network() -> (ServerStatusState, TempDir) {
+    let proc_root = tempfile::Builder::new().prefix("axum-sse-proc-").tempdir().unwrap();
+    std::fs::create_dir_all(proc_root.path().join("net").join("dev")).unwrap();
+
+    let metrics_service = Arc::new(MetricsService::with_config(MetricsServiceConfig {
+        proc_root: proc_root.path().to_string_lossy().into_owned(),
+        ..Default::default()
+    }));
+
+    (state_for(metrics_service), proc_root)
+}
+
+fn state_for(metrics_service: Arc<MetricsService>) -> ServerStatusState {
+    let metrics_cache = Arc::new(MetricsCache::new(Arc::clone(&metrics_service)));
+    let server_info = ServerInfo::new(
+        "test-server".to_string(),
+        "1.0.0".to_string(),
+        Utc::now(),
+        "development".to_string(),
+        OsInfo::fallback(),
+    ).expect("Failed to create test ServerInfo");
+
+    ServerStatusState::new(metrics_cache, metrics_service, server_info)
+}
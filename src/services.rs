@@ -1,10 +1,14 @@
 // Business logic services
+pub mod jitter;
 pub mod metrics_cache;
+pub mod metrics_history;
 pub mod metrics_service;
 pub mod sse_service;
 pub mod static_service;
+pub mod tasks;
 
 pub use metrics_cache::MetricsCache;
+pub use metrics_history::MetricsHistory;
 pub use metrics_service::MetricsService;
 pub use sse_service::SseService;
-pub use static_service::StaticService;
\ No newline at end of file
+pub use static_service::{StaticService, StaticServiceError};
\ No newline at end of file
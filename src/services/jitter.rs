@@ -0,0 +1,55 @@
+// Randomized interval jitter
+// Spreads periodic work so replicas started together do not tick in lockstep
+
+use rand::Rng;
+use std::time::Duration;
+
+/// Largest jitter accepted, so a jittered interval never collapses to zero
+pub const MAX_JITTER_PERCENT: f64 = 50.0;
+
+/// `base` moved by a random amount within ±`percent` of itself
+///
+/// A `percent` of 0 (or less) returns `base` unchanged; values above
+/// [`MAX_JITTER_PERCENT`] are capped.
+pub fn jittered(base: Duration, percent: f64) -> Duration {
+    if percent.is_nan() || percent <= 0.0 {
+        return base;
+    }
+    let percent = percent.min(MAX_JITTER_PERCENT);
+
+    let factor = rand::thread_rng().gen_range(-percent..=percent) / 100.0;
+    base.mul_f64(1.0 + factor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jitter_varies_within_band() {
+        let base = Duration::from_secs(10);
+        let samples: Vec<Duration> = (0..200).map(|_| jittered(base, 20.0)).collect();
+
+        for sample in &samples {
+            assert!(*sample >= Duration::from_secs(8) && *sample <= Duration::from_secs(12), "{:?} outside band", sample);
+        }
+        assert!(samples.windows(2).any(|pair| pair[0] != pair[1]));
+    }
+
+    #[test]
+    fn test_zero_jitter_is_exact() {
+        let base = Duration::from_millis(1500);
+        assert_eq!(jittered(base, 0.0), base);
+        assert_eq!(jittered(base, -5.0), base);
+        assert_eq!(jittered(base, f64::NAN), base);
+    }
+
+    #[test]
+    fn test_jitter_capped() {
+        let base = Duration::from_secs(1);
+        for _ in 0..100 {
+            let sample = jittered(base, 400.0);
+            assert!(sample >= Duration::from_millis(500) && sample <= Duration::from_millis(1500));
+        }
+    }
+}
@@ -3,14 +3,40 @@
 
 use crate::models::{ServerMetrics, MetricsCollectionError, MetricsResponse};
 use crate::services::MetricsService;
+use crate::services::jitter::{jittered, MAX_JITTER_PERCENT};
+use crate::services::tasks::{spawn_named, METRICS_CACHE_REFRESH_TASK};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
 use tokio::sync::{Mutex, RwLock as TokioRwLock};
-use tokio::time::{interval, MissedTickBehavior};
+use tokio::time::{sleep_until, Instant as TokioInstant};
 use tracing::{debug, warn, error, instrument};
 
+/// When the cache collects metrics
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CollectionMode {
+    /// Background task keeps entries warm ahead of requests
+    #[default]
+    Scheduled,
+    /// No background task; requests collect fresh behind a short TTL
+    OnDemand,
+}
+
+impl std::str::FromStr for CollectionMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace('-', "_").as_str() {
+            "scheduled" => Ok(Self::Scheduled),
+            "on_demand" => Ok(Self::OnDemand),
+            other => Err(format!("Unknown collection mode: {}", other)),
+        }
+    }
+}
+
 /// Configuration for the metrics cache
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MetricsCacheConfig {
@@ -24,8 +50,54 @@ pub struct MetricsCacheConfig {
     pub enable_background_refresh: bool,
     /// Prefetch threshold - refresh when TTL has this percentage remaining
     pub prefetch_threshold_percent: f64,
-    /// Maximum concurrent background refresh operations
-    pub max_concurrent_refreshes: usize,
+    /// Skip background refreshes while nothing has requested metrics recently
+    #[serde(default)]
+    pub pause_when_idle: bool,
+    /// Seconds without a metrics request (status call or SSE tick) before refresh pauses
+    #[serde(default = "default_idle_window_seconds")]
+    pub idle_window_seconds: u32,
+    /// Scheduled background refresh or collect-on-request
+    #[serde(default)]
+    pub collection_mode: CollectionMode,
+    /// Entry lifetime in `OnDemand` mode, long enough to absorb request bursts
+    #[serde(default = "default_on_demand_ttl_ms")]
+    pub on_demand_ttl_ms: u64,
+    /// Keys LRU eviction never selects (they still expire by TTL)
+    #[serde(default = "default_pinned_keys")]
+    pub pinned_keys: HashSet<String>,
+    /// When a collection fails, serve the last good entry up to this many
+    /// seconds old instead of the error (0 always propagates the error)
+    #[serde(default = "default_max_stale_seconds")]
+    pub max_stale_seconds: u32,
+    /// Collections tried on a cache miss before giving up; recoverable errors
+    /// are retried after their `retry_delay_ms`
+    #[serde(default = "default_collection_attempts")]
+    pub collection_attempts: u32,
+    /// Randomly lengthen or shorten each refresh interval by up to this
+    /// percentage (capped at 50) so replicas drift apart; 0 keeps it fixed
+    #[serde(default)]
+    pub refresh_jitter_percent: f64,
+}
+
+fn default_idle_window_seconds() -> u32 {
+    60
+}
+
+fn default_on_demand_ttl_ms() -> u64 {
+    1000
+}
+
+fn default_max_stale_seconds() -> u32 {
+    60
+}
+
+fn default_collection_attempts() -> u32 {
+    3
+}
+
+/// The health-check and default keys are hot and cheap to keep
+fn default_pinned_keys() -> HashSet<String> {
+    ["health_check", "default"].iter().map(|key| key.to_string()).collect()
 }
 
 impl Default for MetricsCacheConfig {
@@ -36,23 +108,71 @@ impl Default for MetricsCacheConfig {
             background_refresh_interval_seconds: 10,
             enable_background_refresh: true,
             prefetch_threshold_percent: 0.2, // Refresh when 20% of TTL remains
-            max_concurrent_refreshes: 3,
+            pause_when_idle: false,
+            idle_window_seconds: default_idle_window_seconds(),
+            collection_mode: CollectionMode::Scheduled,
+            on_demand_ttl_ms: default_on_demand_ttl_ms(),
+            pinned_keys: default_pinned_keys(),
+            max_stale_seconds: default_max_stale_seconds(),
+            collection_attempts: default_collection_attempts(),
+            refresh_jitter_percent: 0.0,
+        }
+    }
+}
+
+impl MetricsCacheConfig {
+    /// Load configuration from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(value) = std::env::var("METRICS_COLLECTION_MODE") {
+            match value.parse() {
+                Ok(mode) => config.collection_mode = mode,
+                Err(e) => warn!("Ignoring METRICS_COLLECTION_MODE: {}", e),
+            }
+        }
+
+        if let Ok(value) = std::env::var("METRICS_COLLECTION_ATTEMPTS") {
+            match value.parse::<u32>() {
+                Ok(attempts) if attempts > 0 => config.collection_attempts = attempts,
+                _ => warn!("Ignoring METRICS_COLLECTION_ATTEMPTS: expected a positive integer, got {:?}", value),
+            }
+        }
+
+        if let Ok(value) = std::env::var("METRICS_REFRESH_JITTER_PERCENT") {
+            match value.parse::<f64>() {
+                Ok(percent) if (0.0..=MAX_JITTER_PERCENT).contains(&percent) => config.refresh_jitter_percent = percent,
+                _ => warn!(
+                    "Ignoring METRICS_REFRESH_JITTER_PERCENT: expected 0-{}, got {:?}",
+                    MAX_JITTER_PERCENT, value
+                ),
+            }
+        }
+
+        config
+    }
+
+    /// How long an entry stays fresh under the configured collection mode
+    pub fn ttl(&self) -> Duration {
+        match self.collection_mode {
+            CollectionMode::Scheduled => Duration::from_secs(self.ttl_seconds as u64),
+            CollectionMode::OnDemand => Duration::from_millis(self.on_demand_ttl_ms),
         }
     }
 }
 
 /// Cache entry with metadata
-#[derive(Debug, Clone)]
+///
+/// Access statistics are atomics so cache hits can record them under the
+/// read lock.
+#[derive(Debug)]
 pub struct CacheEntry {
     pub data: ServerMetrics,
     pub created_at: Instant,
-    #[allow(dead_code)]
-    pub accessed_at: Instant,
-    #[allow(dead_code)]
-    pub access_count: u64,
-    #[allow(dead_code)]
+    /// Milliseconds after `created_at` of the most recent access
+    last_access_ms: AtomicU64,
+    access_count: AtomicU64,
     pub cache_key: String,
-    #[allow(dead_code)]
     pub collection_time_ms: u64,
 }
 
@@ -62,8 +182,8 @@ impl CacheEntry {
         Self {
             data,
             created_at: now,
-            accessed_at: now,
-            access_count: 1,
+            last_access_ms: AtomicU64::new(0),
+            access_count: AtomicU64::new(1),
             cache_key,
             collection_time_ms,
         }
@@ -79,11 +199,42 @@ impl CacheEntry {
         remaining_ratio <= threshold && remaining_ratio > 0.0
     }
 
+    fn touch(&self) {
+        let since_created = self.created_at.elapsed().as_millis() as u64;
+        self.last_access_ms.store(since_created, Ordering::Relaxed);
+        self.access_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// When the entry was last served
     #[allow(dead_code)]
-    fn touch(&mut self) {
-        self.accessed_at = Instant::now();
-        self.access_count += 1;
+    pub fn accessed_at(&self) -> Instant {
+        self.created_at + Duration::from_millis(self.last_access_ms.load(Ordering::Relaxed))
     }
+
+    /// Times the entry was served, including the collection that created it
+    pub fn access_count(&self) -> u64 {
+        self.access_count.load(Ordering::Relaxed)
+    }
+
+    fn info(&self) -> CacheEntryInfo {
+        CacheEntryInfo {
+            key: self.cache_key.clone(),
+            age_seconds: self.created_at.elapsed().as_secs(),
+            access_count: self.access_count(),
+            collection_time_ms: self.collection_time_ms,
+        }
+    }
+}
+
+/// Cache entry metadata for monitoring, without the cached metrics
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CacheEntryInfo {
+    pub key: String,
+    /// Seconds since the entry was collected
+    pub age_seconds: u64,
+    /// Times the entry was served, including the collection that created it
+    pub access_count: u64,
+    pub collection_time_ms: u64,
 }
 
 /// Cache statistics for monitoring and optimization
@@ -95,6 +246,12 @@ pub struct CacheStats {
     pub evictions: u64,
     pub background_refreshes: u64,
     pub failed_refreshes: u64,
+    /// Background refresh ticks skipped because the cache was idle
+    #[serde(default)]
+    pub idle_skipped_refreshes: u64,
+    /// Collection failures answered with a stale entry
+    #[serde(default)]
+    pub stale_served: u64,
     pub current_entries: usize,
     pub average_collection_time_ms: f64,
     pub hit_ratio: f64,
@@ -108,6 +265,11 @@ impl CacheStats {
     }
 }
 
+/// Check whether the last metrics request is older than the idle window
+fn is_idle(last_activity: &RwLock<Instant>, idle_window_seconds: u32) -> bool {
+    last_activity.read().unwrap().elapsed() > Duration::from_secs(idle_window_seconds as u64)
+}
+
 /// Advanced metrics cache with LRU eviction and background refresh
 pub struct MetricsCache {
     config: MetricsCacheConfig,
@@ -116,6 +278,61 @@ pub struct MetricsCache {
     stats: Arc<RwLock<CacheStats>>,
     metrics_service: Arc<MetricsService>,
     background_refresh_active: Arc<Mutex<bool>>,
+    last_activity: Arc<RwLock<Instant>>,
+    /// Per-key locks so concurrent misses share a single collection
+    in_flight: Mutex<HashMap<String, Arc<Mutex<()>>>>,
+}
+
+/// One background refresh tick: collect once and apply the result to every
+/// entry due for prefetch, returning how many entries were refreshed
+///
+/// Keys share the same machine, so a collection per key would only repeat
+/// the same work and scale system load with the number of keys.
+async fn refresh_eligible_entries(
+    cache: &TokioRwLock<HashMap<String, CacheEntry>>,
+    stats: &RwLock<CacheStats>,
+    metrics_service: &MetricsService,
+    config: &MetricsCacheConfig,
+) -> usize {
+    let has_eligible = {
+        let cache = cache.read().await;
+        let ttl = config.ttl();
+        cache.values().any(|entry| entry.should_prefetch(ttl, config.prefetch_threshold_percent))
+    };
+    if !has_eligible {
+        return 0;
+    }
+
+    let start = Instant::now();
+    let metrics = match metrics_service.collect_fresh_metrics().await {
+        MetricsResponse::Ok(metrics) | MetricsResponse::PartialData { data: metrics, .. } => metrics,
+        MetricsResponse::Error(error) => {
+            warn!("Background refresh failed: {}", error);
+            stats.write().unwrap().failed_refreshes += 1;
+            return 0;
+        }
+    };
+    let collection_time_ms = start.elapsed().as_millis() as u64;
+
+    // Re-check under the write lock; entries may have been refreshed or removed meanwhile
+    let mut cache = cache.write().await;
+    let ttl = config.ttl();
+    let mut refreshed = 0;
+    for (key, entry) in cache.iter_mut() {
+        if !entry.should_prefetch(ttl, config.prefetch_threshold_percent) {
+            continue;
+        }
+        entry.data = metrics.clone();
+        entry.created_at = Instant::now();
+        entry.collection_time_ms = collection_time_ms;
+        refreshed += 1;
+        debug!("Background refreshed cache entry: {}", key);
+    }
+    drop(cache);
+
+    stats.write().unwrap().background_refreshes += refreshed as u64;
+    debug!("Background refreshed {} cache entries from one collection", refreshed);
+    refreshed
 }
 
 impl MetricsCache {
@@ -133,6 +350,8 @@ impl MetricsCache {
             stats: Arc::new(RwLock::new(CacheStats::default())),
             metrics_service,
             background_refresh_active: Arc::new(Mutex::new(false)),
+            last_activity: Arc::new(RwLock::new(Instant::now())),
+            in_flight: Mutex::new(HashMap::new()),
         }
     }
 
@@ -144,6 +363,11 @@ impl MetricsCache {
             return Ok(());
         }
 
+        if self.config.collection_mode == CollectionMode::OnDemand {
+            debug!("Background refresh skipped in on-demand collection mode");
+            return Ok(());
+        }
+
         let mut active = self.background_refresh_active.lock().await;
         if *active {
             debug!("Background refresh already active");
@@ -157,17 +381,18 @@ impl MetricsCache {
         let metrics_service = Arc::clone(&self.metrics_service);
         let config = self.config.clone();
         let background_active = Arc::clone(&self.background_refresh_active);
+        let last_activity = Arc::clone(&self.last_activity);
 
-        tokio::spawn(async move {
-            let mut interval_timer = interval(Duration::from_secs(
-                config.background_refresh_interval_seconds as u64
-            ));
-            interval_timer.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        spawn_named(METRICS_CACHE_REFRESH_TASK, async move {
+            let refresh_interval = Duration::from_secs(config.background_refresh_interval_seconds as u64);
+            // The first refresh runs straight away, like an interval's first tick
+            let mut next_refresh = TokioInstant::now();
 
             debug!("Background refresh task started");
 
             loop {
-                interval_timer.tick().await;
+                sleep_until(next_refresh).await;
+                next_refresh = TokioInstant::now() + jittered(refresh_interval, config.refresh_jitter_percent);
 
                 // Check if background refresh is still active
                 {
@@ -178,60 +403,14 @@ impl MetricsCache {
                     }
                 }
 
-                // Find entries that need refresh
-                let entries_to_refresh = {
-                    let cache = cache.read().await;
-                    let ttl = Duration::from_secs(config.ttl_seconds as u64);
-                    
-                    cache.iter()
-                        .filter(|(_, entry)| {
-                            entry.should_prefetch(ttl, config.prefetch_threshold_percent)
-                        })
-                        .take(config.max_concurrent_refreshes)
-                        .map(|(key, _)| key.clone())
-                        .collect::<Vec<_>>()
-                };
-
-                if !entries_to_refresh.is_empty() {
-                    debug!("Background refreshing {} cache entries", entries_to_refresh.len());
-                    
-                    // Refresh entries in parallel
-                    let refresh_tasks = entries_to_refresh.into_iter().map(|key| {
-                        let cache_clone = Arc::clone(&cache);
-                        let stats_clone = Arc::clone(&stats);
-                        let service_clone = Arc::clone(&metrics_service);
-                        let key_clone = key.clone();
-                        
-                        tokio::spawn(async move {
-                            match service_clone.collect_fresh_metrics().await {
-                                MetricsResponse::Ok(metrics) | MetricsResponse::PartialData { data: metrics, .. } => {
-                                    let mut cache = cache_clone.write().await;
-                                    if let Some(entry) = cache.get_mut(&key_clone) {
-                                        entry.data = metrics;
-                                        entry.created_at = Instant::now();
-                                        
-                                        let mut stats = stats_clone.write().unwrap();
-                                        stats.background_refreshes += 1;
-                                        
-                                        debug!("Background refreshed cache entry: {}", key_clone);
-                                    }
-                                }
-                                MetricsResponse::Error(error) => {
-                                    warn!("Background refresh failed for {}: {}", key_clone, error);
-                                    let mut stats = stats_clone.write().unwrap();
-                                    stats.failed_refreshes += 1;
-                                }
-                            }
-                        })
-                    });
-
-                    // Wait for all refresh tasks to complete
-                    for task in refresh_tasks {
-                        if let Err(e) = task.await {
-                            error!("Background refresh task failed: {}", e);
-                        }
-                    }
+                // Nobody is asking for metrics - don't collect until they do
+                if config.pause_when_idle && is_idle(&last_activity, config.idle_window_seconds) {
+                    debug!("Background refresh paused: no metrics requests in {}s", config.idle_window_seconds);
+                    stats.write().unwrap().idle_skipped_refreshes += 1;
+                    continue;
                 }
+
+                refresh_eligible_entries(&cache, &stats, &metrics_service, &config).await;
             }
         });
 
@@ -247,20 +426,42 @@ impl MetricsCache {
         debug!("Background refresh task stopped");
     }
 
+    /// Whether background refresh is currently paused for lack of requests
+    pub fn is_idle(&self) -> bool {
+        self.config.pause_when_idle && is_idle(&self.last_activity, self.config.idle_window_seconds)
+    }
+
     /// Get metrics from cache or collect fresh if not available
     #[instrument(skip(self))]
     pub async fn get_metrics(&self, cache_key: Option<String>) -> MetricsResponse<ServerMetrics> {
         let key = cache_key.unwrap_or_else(|| "default".to_string());
         
+        *self.last_activity.write().unwrap() = Instant::now();
         self.update_stats(|stats| stats.total_requests += 1);
 
         // Try to get from cache first
         if let Some(metrics) = self.get_from_cache(&key).await {
-            self.update_stats(|stats| {
-                stats.cache_hits += 1;
-                stats.calculate_hit_ratio();
-            });
-            debug!("Cache hit for key: {}", key);
+            self.record_hit(&key);
+            return MetricsResponse::Ok(metrics);
+        }
+
+        // Single-flight: wait for any collection already running for this key
+        let flight = Arc::clone(self.in_flight.lock().await.entry(key.clone()).or_default());
+        let result = {
+            let _flight_guard = flight.lock().await;
+            self.collect_into_cache(&key).await
+        };
+
+        self.release_flight(&key, flight).await;
+        result
+    }
+
+    /// Collect fresh metrics for `key` and store them, unless the collection we
+    /// waited on already did; callers hold the key's single-flight slot
+    async fn collect_into_cache(&self, key: &str) -> MetricsResponse<ServerMetrics> {
+        // The collection we waited on may have filled the cache
+        if let Some(metrics) = self.get_from_cache(key).await {
+            self.record_hit(key);
             return MetricsResponse::Ok(metrics);
         }
 
@@ -273,13 +474,13 @@ impl MetricsCache {
         debug!("Cache miss for key: {}", key);
         let start_time = Instant::now();
         
-        let result = self.metrics_service.collect_fresh_metrics().await;
+        let result = self.metrics_service.collect_with_retry(self.config.collection_attempts).await;
         let collection_time = start_time.elapsed().as_millis() as u64;
 
         // Cache the result if successful
         match &result {
             MetricsResponse::Ok(metrics) | MetricsResponse::PartialData { data: metrics, .. } => {
-                self.put_in_cache(key.clone(), metrics.clone(), collection_time).await;
+                self.put_in_cache(key.to_string(), metrics.clone(), collection_time).await;
                 self.update_stats(|stats| {
                     stats.average_collection_time_ms = if stats.cache_misses == 1 {
                         collection_time as f64
@@ -294,28 +495,83 @@ impl MetricsCache {
             }
         }
 
-        result
+        match result {
+            MetricsResponse::Error(error) => self.stale_fallback(key, error).await,
+            result => result,
+        }
+    }
+
+    /// Drop the single-flight slot for `key` once nobody else holds it
+    ///
+    /// Removing it while waiters still hold a handle would let a new request
+    /// start a second slot, and so a second collection, alongside theirs.
+    async fn release_flight(&self, key: &str, flight: Arc<Mutex<()>>) {
+        let mut in_flight = self.in_flight.lock().await;
+        // One handle in the map, one ours; ours goes while the map is locked so
+        // the last caller out always sees the count drop
+        let last = Arc::strong_count(&flight) <= 2;
+        drop(flight);
+        if last {
+            in_flight.remove(key);
+        }
+    }
+
+    /// Serve the last good entry for `key` after a failed collection, if recent enough
+    ///
+    /// The data comes back as `PartialData` carrying a `StaleData` error so
+    /// callers can flag it; anything older than `max_stale_seconds` gets the
+    /// original error.
+    async fn stale_fallback(&self, key: &str, error: MetricsCollectionError) -> MetricsResponse<ServerMetrics> {
+        let max_stale = Duration::from_secs(self.config.max_stale_seconds as u64);
+        let stale = {
+            let cache = self.cache.read().await;
+            cache
+                .get(key)
+                .filter(|entry| entry.created_at.elapsed() <= max_stale)
+                .map(|entry| (entry.data.clone(), entry.created_at.elapsed().as_secs()))
+        };
+
+        match stale {
+            Some((data, age_seconds)) => {
+                warn!("Serving {}s old metrics for {} after collection failed: {}", age_seconds, key, error);
+                self.update_stats(|stats| stats.stale_served += 1);
+                MetricsResponse::PartialData {
+                    data,
+                    errors: vec![MetricsCollectionError::stale_data(age_seconds, error.to_string())],
+                }
+            }
+            None => MetricsResponse::Error(error),
+        }
+    }
+
+    /// Count a cache hit
+    fn record_hit(&self, key: &str) {
+        self.update_stats(|stats| {
+            stats.cache_hits += 1;
+            stats.calculate_hit_ratio();
+        });
+        debug!("Cache hit for key: {}", key);
     }
 
     /// Get metrics from cache if available and not expired
     async fn get_from_cache(&self, key: &str) -> Option<ServerMetrics> {
-        let cache = self.cache.read().await;
-        
-        if let Some(entry) = cache.get(key) {
-            let ttl = Duration::from_secs(self.config.ttl_seconds as u64);
-            
-            if !entry.is_expired(ttl) {
-                // Update access order
-                self.update_access_order(key.to_string()).await;
-                
-                // Return cloned data
-                return Some(entry.data.clone());
-            } else {
+        let data = {
+            let cache = self.cache.read().await;
+            let entry = cache.get(key)?;
+
+            if entry.is_expired(self.config.ttl()) {
                 debug!("Cache entry expired for key: {}", key);
+                return None;
             }
-        }
-        
-        None
+
+            entry.touch();
+            entry.data.clone()
+        };
+
+        // Update access order once the cache lock is released
+        self.update_access_order(key.to_string()).await;
+
+        Some(data)
     }
 
     /// Put metrics in cache
@@ -340,15 +596,21 @@ impl MetricsCache {
         });
     }
 
-    /// Evict least recently used entries
+    /// Evict least recently used entries, skipping pinned keys
     async fn evict_lru_entries(&self, cache: &mut HashMap<String, CacheEntry>) {
         let mut access_order = self.access_order.lock().await;
         
         // Calculate how many entries to evict (25% of max)
         let evict_count = (self.config.max_entries / 4).max(1);
         
+        let mut position = 0;
         for _ in 0..evict_count {
-            if let Some(lru_key) = access_order.pop_front() {
+            // Oldest key that isn't pinned
+            while access_order.get(position).is_some_and(|key| self.config.pinned_keys.contains(key)) {
+                position += 1;
+            }
+
+            if let Some(lru_key) = access_order.remove(position) {
                 if cache.remove(&lru_key).is_some() {
                     self.update_stats(|stats| stats.evictions += 1);
                     debug!("Evicted LRU cache entry: {}", lru_key);
@@ -395,7 +657,6 @@ impl MetricsCache {
 
     /// Clear all cache entries
     #[instrument(skip(self))]
-    #[allow(dead_code)]
     pub async fn clear(&self) {
         let mut cache = self.cache.write().await;
         let mut access_order = self.access_order.lock().await;
@@ -410,13 +671,29 @@ impl MetricsCache {
         debug!("Cache cleared");
     }
 
+    /// Remove a single cache entry, returning whether the key existed
+    pub async fn invalidate(&self, key: &str) -> bool {
+        let mut cache = self.cache.write().await;
+        let mut access_order = self.access_order.lock().await;
+
+        let existed = cache.remove(key).is_some();
+        access_order.retain(|k| k != key);
+
+        self.update_stats(|stats| {
+            stats.current_entries = cache.len();
+        });
+
+        debug!("Cache key '{}' invalidated (existed: {})", key, existed);
+        existed
+    }
+
     /// Remove expired entries from cache
     #[instrument(skip(self))]
     #[allow(dead_code)]
     pub async fn cleanup_expired(&self) -> usize {
         let mut cache = self.cache.write().await;
         let mut access_order = self.access_order.lock().await;
-        let ttl = Duration::from_secs(self.config.ttl_seconds as u64);
+        let ttl = self.config.ttl();
         
         let expired_keys: Vec<String> = cache
             .iter()
@@ -482,9 +759,17 @@ impl MetricsCache {
     pub async fn get_cache_entry_details(&self, key: &str) -> Option<(ServerMetrics, Instant, u64)> {
         let cache = self.cache.read().await;
         cache.get(key).map(|entry| {
-            (entry.data.clone(), entry.created_at, entry.access_count)
+            (entry.data.clone(), entry.created_at, entry.access_count())
         })
     }
+
+    /// Metadata for every cache entry, sorted by key
+    pub async fn get_cache_entries(&self) -> Vec<CacheEntryInfo> {
+        let cache = self.cache.read().await;
+        let mut entries: Vec<CacheEntryInfo> = cache.values().map(CacheEntry::info).collect();
+        entries.sort_by(|a, b| a.key.cmp(&b.key));
+        entries
+    }
 }
 
 #[cfg(test)]
@@ -523,6 +808,15 @@ mod tests {
         assert!(!cache.config.enable_background_refresh);
     }
 
+    #[test]
+    fn test_config_ignores_removed_max_concurrent_refreshes() {
+        let mut json = serde_json::to_value(MetricsCacheConfig::default()).unwrap();
+        json["max_concurrent_refreshes"] = serde_json::json!(3);
+
+        let config: MetricsCacheConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(config.max_entries, 1000);
+    }
+
     #[tokio::test]
     async fn test_cache_miss_and_hit() {
         let service = create_test_metrics_service();
@@ -545,6 +839,296 @@ mod tests {
         assert!(response2.has_data());
     }
 
+    #[tokio::test]
+    async fn test_background_refresh_spawns_named_task() {
+        let service = create_test_metrics_service();
+        let cache = MetricsCache::new(service);
+
+        cache.start_background_refresh().await.unwrap();
+        assert!(crate::services::tasks::spawned_task_names()
+            .contains(&METRICS_CACHE_REFRESH_TASK.to_string()));
+
+        cache.stop_background_refresh().await;
+    }
+
+    #[tokio::test]
+    async fn test_background_refresh_pauses_when_idle() {
+        let service = create_test_metrics_service();
+        service.initialize().await.unwrap();
+
+        let config = MetricsCacheConfig {
+            pause_when_idle: true,
+            idle_window_seconds: 1,
+            ..Default::default()
+        };
+        let cache = MetricsCache::with_config(config, service);
+
+        // Pretend the last request was well outside the idle window
+        *cache.last_activity.write().unwrap() = Instant::now() - Duration::from_secs(5);
+        assert!(cache.is_idle());
+
+        // The first tick fires immediately and is skipped
+        cache.start_background_refresh().await.unwrap();
+        sleep(TokioDuration::from_millis(100)).await;
+        let stats = cache.get_stats();
+        assert!(stats.idle_skipped_refreshes >= 1);
+        assert_eq!(stats.background_refreshes, 0);
+
+        // A request resumes refreshing
+        cache.get_metrics(None).await;
+        assert!(!cache.is_idle());
+
+        cache.stop_background_refresh().await;
+    }
+
+    #[tokio::test]
+    async fn test_on_demand_mode_skips_background_collection() {
+        let service = create_test_metrics_service();
+        service.initialize().await.unwrap();
+
+        let config = MetricsCacheConfig {
+            collection_mode: CollectionMode::OnDemand,
+            on_demand_ttl_ms: 50,
+            prefetch_threshold_percent: 1.0,
+            background_refresh_interval_seconds: 1,
+            ..Default::default()
+        };
+        let cache = MetricsCache::with_config(config, Arc::clone(&service));
+
+        cache.start_background_refresh().await.unwrap();
+        assert!(!*cache.background_refresh_active.lock().await);
+
+        cache.get_metrics(None).await;
+        let collections = service.get_stats().await.total_collections;
+        sleep(TokioDuration::from_millis(1200)).await;
+        assert_eq!(service.get_stats().await.total_collections, collections);
+        assert_eq!(cache.get_stats().background_refreshes, 0);
+
+        // The short TTL has lapsed, so the next request collects fresh
+        cache.get_metrics(None).await;
+        assert_eq!(cache.get_stats().cache_misses, 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_misses_share_one_collection() {
+        let service = create_test_metrics_service();
+        service.initialize().await.unwrap();
+
+        let config = MetricsCacheConfig {
+            collection_mode: CollectionMode::OnDemand,
+            on_demand_ttl_ms: 5000,
+            ..Default::default()
+        };
+        let cache = Arc::new(MetricsCache::with_config(config, Arc::clone(&service)));
+        let before = service.get_stats().await.total_collections;
+
+        let requests: Vec<_> = (0..8)
+            .map(|_| {
+                let cache = Arc::clone(&cache);
+                tokio::spawn(async move { cache.get_metrics(Some("burst".to_string())).await })
+            })
+            .collect();
+        for request in requests {
+            assert!(request.await.unwrap().has_data());
+        }
+
+        assert_eq!(service.get_stats().await.total_collections, before + 1);
+        let stats = cache.get_stats();
+        assert_eq!(stats.cache_misses, 1);
+        assert_eq!(stats.cache_hits, 7);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_requests_arriving_after_a_failed_collection_share_the_retry() {
+        // The first collection outlives its timeout and fails; the next one succeeds
+        let service = Arc::new(MetricsService::with_config(crate::services::metrics_service::MetricsServiceConfig {
+            collection_timeout_ms: 1000,
+            ..Default::default()
+        }));
+        service.initialize().await.unwrap();
+        service.delay_next_collection(Duration::from_millis(1500));
+
+        let config = MetricsCacheConfig {
+            collection_mode: CollectionMode::OnDemand,
+            on_demand_ttl_ms: 10_000,
+            collection_attempts: 1,
+            ..Default::default()
+        };
+        let cache = Arc::new(MetricsCache::with_config(config, Arc::clone(&service)));
+        let before = service.get_stats().await.total_collections;
+
+        let request = |cache: &Arc<MetricsCache>| {
+            let cache = Arc::clone(cache);
+            tokio::spawn(async move { cache.get_metrics(Some("burst".to_string())).await })
+        };
+        let mut requests: Vec<_> = (0..4).map(|_| request(&cache)).collect();
+        // Arrive after the first collection failed, while its waiters retry
+        tokio::time::sleep(Duration::from_millis(1200)).await;
+        requests.extend((0..4).map(|_| request(&cache)));
+
+        let results = futures_util::future::join_all(requests).await;
+        let served = results.into_iter().filter(|result| result.as_ref().unwrap().has_data()).count();
+
+        // The timed-out collection plus one retry, never a parallel one for the late arrivals
+        assert_eq!(service.get_stats().await.total_collections, before + 2);
+        assert_eq!(served, 7);
+        assert!(cache.in_flight.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_background_refresh_collects_once_for_all_keys() {
+        let service = create_test_metrics_service();
+        service.initialize().await.unwrap();
+
+        let config = MetricsCacheConfig {
+            ttl_seconds: 10,
+            prefetch_threshold_percent: 0.5,
+            ..Default::default()
+        };
+        let cache = MetricsCache::with_config(config, Arc::clone(&service));
+
+        let keys = ["a", "b", "c", "d", "e"];
+        for key in keys {
+            cache.get_metrics(Some(key.to_string())).await;
+        }
+        // Age every entry into the prefetch window
+        for entry in cache.cache.write().await.values_mut() {
+            entry.created_at = Instant::now() - Duration::from_secs(7);
+        }
+
+        let before = service.get_stats().await.total_collections;
+        let refreshed = refresh_eligible_entries(&cache.cache, &cache.stats, &service, &cache.config).await;
+
+        assert_eq!(refreshed, keys.len());
+        assert_eq!(service.get_stats().await.total_collections, before + 1);
+        assert_eq!(cache.get_stats().background_refreshes, keys.len() as u64);
+
+        // Nothing is due any more, so the next tick collects nothing
+        let refreshed = refresh_eligible_entries(&cache.cache, &cache.stats, &service, &cache.config).await;
+        assert_eq!(refreshed, 0);
+        assert_eq!(service.get_stats().await.total_collections, before + 1);
+    }
+
+    #[test]
+    fn test_collection_mode_parsing() {
+        assert_eq!("on-demand".parse::<CollectionMode>().unwrap(), CollectionMode::OnDemand);
+        assert_eq!("Scheduled".parse::<CollectionMode>().unwrap(), CollectionMode::Scheduled);
+        assert!("sometimes".parse::<CollectionMode>().is_err());
+        assert_eq!(MetricsCacheConfig::default().ttl(), Duration::from_secs(30));
+    }
+
+    #[tokio::test]
+    async fn test_pinned_key_survives_eviction() {
+        let service = create_test_metrics_service();
+        service.initialize().await.unwrap();
+
+        let config = MetricsCacheConfig {
+            max_entries: 4,
+            enable_background_refresh: false,
+            pinned_keys: ["health_check".to_string()].into_iter().collect(),
+            ..Default::default()
+        };
+        let cache = MetricsCache::with_config(config, service);
+
+        // The pinned key is the least recently used entry throughout
+        cache.get_metrics(Some("health_check".to_string())).await;
+        for i in 0..10 {
+            cache.get_metrics(Some(format!("key_{}", i))).await;
+        }
+
+        let keys = cache.get_cache_keys().await;
+        assert!(keys.contains(&"health_check".to_string()));
+        assert!(!keys.contains(&"key_0".to_string()));
+        assert!(keys.len() <= 4);
+        assert!(cache.get_stats().evictions > 0);
+    }
+
+    #[tokio::test]
+    async fn test_serves_stale_entry_on_collection_failure() {
+        let service = create_test_metrics_service();
+        service.initialize().await.unwrap();
+
+        let config = MetricsCacheConfig {
+            ttl_seconds: 0,
+            max_stale_seconds: 60,
+            enable_background_refresh: false,
+            collection_attempts: 1,
+            ..Default::default()
+        };
+        let cache = MetricsCache::with_config(config, Arc::clone(&service));
+
+        let fresh = cache.get_metrics(Some("blip".to_string())).await.data().unwrap();
+        sleep(TokioDuration::from_millis(10)).await;
+
+        // The entry has expired, so this goes to collection - which fails
+        service.fail_next_collection(MetricsCollectionError::timeout(2000));
+        match cache.get_metrics(Some("blip".to_string())).await {
+            MetricsResponse::PartialData { data, errors } => {
+                assert_eq!(data.timestamp, fresh.timestamp);
+                assert!(matches!(&errors[..], [MetricsCollectionError::StaleData { age_seconds: 0, .. }]));
+            }
+            other => panic!("Expected stale data, got {:?}", other),
+        }
+        assert_eq!(cache.get_stats().stale_served, 1);
+
+        // Without a stale window the error propagates
+        let strict = MetricsCache::with_config(
+            MetricsCacheConfig { ttl_seconds: 0, max_stale_seconds: 0, collection_attempts: 1, ..Default::default() },
+            Arc::clone(&service),
+        );
+        strict.get_metrics(Some("blip".to_string())).await;
+        sleep(TokioDuration::from_millis(10)).await;
+        service.fail_next_collection(MetricsCollectionError::timeout(2000));
+        assert!(matches!(
+            strict.get_metrics(Some("blip".to_string())).await,
+            MetricsResponse::Error(MetricsCollectionError::Timeout { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cache_miss_retries_recoverable_error() {
+        let service = create_test_metrics_service();
+        service.initialize().await.unwrap();
+
+        let config = MetricsCacheConfig {
+            enable_background_refresh: false,
+            collection_attempts: 2,
+            ..Default::default()
+        };
+        let cache = MetricsCache::with_config(config, Arc::clone(&service));
+
+        service.fail_next_collection(MetricsCollectionError::timeout(2000));
+        assert!(matches!(cache.get_metrics(Some("retry".to_string())).await, MetricsResponse::Ok(_)));
+        let stats = service.get_stats().await;
+        assert_eq!(stats.total_collections, 2);
+        assert_eq!(stats.failed_collections, 1);
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_single_key() {
+        let service = create_test_metrics_service();
+        service.initialize().await.unwrap();
+
+        let config = MetricsCacheConfig {
+            enable_background_refresh: false,
+            ..Default::default()
+        };
+        let cache = MetricsCache::with_config(config, service);
+
+        cache.get_metrics(Some("first".to_string())).await;
+        cache.get_metrics(Some("second".to_string())).await;
+
+        assert!(cache.invalidate("first").await);
+        assert!(!cache.invalidate("first").await);
+
+        let keys = cache.get_cache_keys().await;
+        assert_eq!(keys, vec!["second".to_string()]);
+
+        // Access order must not keep a stale reference to the removed key
+        let access_order = cache.access_order.lock().await;
+        assert_eq!(access_order.iter().collect::<Vec<_>>(), vec!["second"]);
+    }
+
     #[tokio::test]
     async fn test_cache_expiration() {
         let service = create_test_metrics_service();
@@ -661,6 +1245,41 @@ mod tests {
         assert_eq!(access_count, 1);
     }
 
+    #[tokio::test]
+    async fn test_cache_entries_track_hits() {
+        let service = create_test_metrics_service();
+        service.initialize().await.unwrap();
+
+        let cache = MetricsCache::new(service);
+        cache.get_metrics(Some("b".to_string())).await;
+        cache.get_metrics(Some("a".to_string())).await;
+        cache.get_metrics(Some("a".to_string())).await;
+
+        let entries = cache.get_cache_entries().await;
+        let keys: Vec<&str> = entries.iter().map(|entry| entry.key.as_str()).collect();
+        assert_eq!(keys, vec!["a", "b"]);
+        assert_eq!(entries[0].access_count, 2);
+        assert_eq!(entries[1].access_count, 1);
+        assert_eq!(entries[0].age_seconds, 0);
+    }
+
+    #[tokio::test]
+    async fn test_cache_hits_share_the_read_lock() {
+        let service = create_test_metrics_service();
+        service.initialize().await.unwrap();
+
+        let cache = MetricsCache::new(service);
+        cache.get_metrics(Some("a".to_string())).await;
+
+        // A hit must not wait for readers to finish
+        let reader = cache.cache.read().await;
+        let hit = tokio::time::timeout(Duration::from_secs(1), cache.get_from_cache("a")).await;
+        drop(reader);
+
+        assert!(hit.expect("cache hit waited on the write lock").is_some());
+        assert_eq!(cache.get_cache_entries().await[0].access_count, 2);
+    }
+
     #[tokio::test]
     async fn test_hit_ratio_calculation() {
         let service = create_test_metrics_service();
@@ -0,0 +1,124 @@
+// Metrics history
+// Bounded ring buffer of recent successful collections, for sparklines and short trends
+
+use crate::models::ServerMetrics;
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
+use std::sync::RwLock;
+
+/// Default number of samples kept: one hour at the default 5s collection interval
+pub const DEFAULT_HISTORY_CAPACITY: usize = 720;
+
+/// Ring buffer of recent metrics, evicting the oldest sample when full
+#[derive(Debug)]
+pub struct MetricsHistory {
+    capacity: usize,
+    samples: RwLock<VecDeque<ServerMetrics>>,
+}
+
+impl MetricsHistory {
+    /// Create a history holding up to `capacity` samples (0 disables recording)
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            samples: RwLock::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// Maximum number of samples kept
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of samples currently stored
+    pub fn len(&self) -> usize {
+        self.samples.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Append a sample, evicting the oldest one if the buffer is full
+    pub fn record(&self, metrics: ServerMetrics) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut samples = self.samples.write().unwrap();
+        while samples.len() >= self.capacity {
+            samples.pop_front();
+        }
+        samples.push_back(metrics);
+    }
+
+    /// Samples collected strictly after `since`, oldest first, keeping only the newest `limit`
+    pub fn query(&self, since: Option<DateTime<Utc>>, limit: Option<usize>) -> Vec<ServerMetrics> {
+        let samples = self.samples.read().unwrap();
+        let matching: Vec<&ServerMetrics> = samples
+            .iter()
+            .filter(|metrics| since.is_none_or(|since| metrics.timestamp > since))
+            .collect();
+
+        let skip = limit.map_or(0, |limit| matching.len().saturating_sub(limit));
+        matching.into_iter().skip(skip).cloned().collect()
+    }
+}
+
+impl Default for MetricsHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::MetricsService;
+    use chrono::Duration;
+
+    /// Real collected metrics, restamped at `start + i` seconds
+    async fn samples(count: i64) -> (DateTime<Utc>, Vec<ServerMetrics>) {
+        let base = MetricsService::new().collect_fresh_metrics().await.data().unwrap();
+        let start = base.timestamp;
+        let samples = (0..count)
+            .map(|i| ServerMetrics {
+                timestamp: start + Duration::seconds(i),
+                ..base.clone()
+            })
+            .collect();
+        (start, samples)
+    }
+
+    #[tokio::test]
+    async fn test_history_evicts_oldest() {
+        let history = MetricsHistory::new(3);
+        let (start, samples) = samples(5).await;
+        samples.into_iter().for_each(|metrics| history.record(metrics));
+
+        assert_eq!(history.len(), 3);
+        let timestamps: Vec<_> = history.query(None, None).iter().map(|m| m.timestamp).collect();
+        assert_eq!(timestamps, (2..5).map(|i| start + Duration::seconds(i)).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_history_query_filters() {
+        let history = MetricsHistory::new(10);
+        let (start, samples) = samples(6).await;
+        samples.into_iter().for_each(|metrics| history.record(metrics));
+
+        assert_eq!(history.query(Some(start + Duration::seconds(3)), None).len(), 2);
+        let newest = history.query(None, Some(2));
+        assert_eq!(newest.len(), 2);
+        assert_eq!(newest[1].timestamp, start + Duration::seconds(5));
+        assert!(history.query(Some(start + Duration::seconds(5)), None).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_zero_capacity_disables_history() {
+        let history = MetricsHistory::new(0);
+        let (_, samples) = samples(1).await;
+        samples.into_iter().for_each(|metrics| history.record(metrics));
+        assert!(history.is_empty());
+    }
+}
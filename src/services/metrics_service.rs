@@ -3,17 +3,46 @@
 
 use crate::models::{
     MetricsCollectionError, MetricsResponse, ServerMetrics, MemoryMetrics, 
-    CpuMetrics, NetworkMetrics, OsInfo
+    CpuMetrics, CpuScale, CounterDelta, DiskMetrics, FanReading, GpuMetrics, MetricSource, MountMetrics, NetworkMetrics, OsInfo, ProcessInfo, ProcessMetrics,
+    SensorMetrics, TemperatureReading
 };
 use crate::models::cpu_metrics::LoadAverage;
-use chrono::Utc;
+use crate::services::metrics_history::{MetricsHistory, DEFAULT_HISTORY_CAPACITY};
+use chrono::{DateTime, Utc};
 use std::time::Duration;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
 use std::sync::{Arc, RwLock};
 use std::time::Instant;
-use sysinfo::{System, RefreshKind, CpuRefreshKind, MemoryRefreshKind};
+use sysinfo::{Components, Disks, Networks, System, RefreshKind, CpuRefreshKind, MemoryRefreshKind};
 use tokio::sync::Mutex;
-use tracing::{debug, error, instrument};
+use tracing::{debug, error, instrument, warn};
+use crate::services::tasks::{
+    spawn_blocking_named, FILE_COLLECTORS_TASK, GPU_COLLECTOR_TASK, SENSOR_COLLECTOR_TASK,
+    SYSINFO_REFRESH_TASK
+};
+use std::future::Future;
+
+/// Smallest interval in whole seconds that gives sysinfo time to compute CPU deltas
+pub fn minimum_interval_seconds() -> u32 {
+    let minimum = sysinfo::MINIMUM_CPU_UPDATE_INTERVAL;
+    let seconds = minimum.as_secs() as u32 + u32::from(minimum.subsec_nanos() > 0);
+    seconds.max(1)
+}
+
+/// Clamp an interval to the sysinfo CPU refresh floor, warning when it has to be raised
+pub fn clamp_interval_seconds(name: &str, seconds: u32) -> u32 {
+    let floor = minimum_interval_seconds();
+    if seconds < floor {
+        warn!(
+            "{} of {}s is below the minimum CPU refresh interval ({:?}), clamping to {}s",
+            name, seconds, sysinfo::MINIMUM_CPU_UPDATE_INTERVAL, floor
+        );
+        floor
+    } else {
+        seconds
+    }
+}
 
 /// Normalize OS name to standard identifiers
 fn normalize_os_name(raw_name: &str, distribution: Option<&str>) -> String {
@@ -62,6 +91,241 @@ pub struct MetricsServiceConfig {
     pub collect_network_metrics: bool,
     /// Whether to collect detailed CPU metrics per core
     pub collect_cpu_per_core: bool,
+    /// Run file-based collectors concurrently with the sysinfo refresh
+    #[serde(default = "default_concurrent_collection")]
+    pub concurrent_collection: bool,
+    /// Share of total memory (percent) that may go unaccounted for by
+    /// used + available before a warning is reported
+    pub memory_discrepancy_tolerance_percent: f32,
+    /// Scale for reported CPU usage (average across cores or total)
+    #[serde(default)]
+    pub cpu_scale: CpuScale,
+    /// Glob patterns of network interfaces to count (empty counts all)
+    #[serde(default)]
+    pub interface_include: Vec<String>,
+    /// Glob patterns of network interfaces to skip, applied after the include list
+    #[serde(default = "default_interface_exclude")]
+    pub interface_exclude: Vec<String>,
+    /// Mount point of procfs used by the file-based collectors
+    #[serde(default = "default_proc_root")]
+    pub proc_root: String,
+    /// Collect storage usage for every mounted filesystem
+    #[serde(default = "default_collect_disk_metrics")]
+    pub collect_disk_metrics: bool,
+    /// Collect per-device GPU metrics via NVML (requires the `gpu` feature)
+    #[serde(default)]
+    pub collect_gpu_metrics: bool,
+    /// Collect temperature sensors and fan speeds
+    #[serde(default)]
+    pub collect_sensor_metrics: bool,
+    /// Report the average CPU temperature in `CpuMetrics`
+    #[serde(default)]
+    pub collect_temperature: bool,
+    /// Collect process counts and the heaviest processes by CPU
+    #[serde(default)]
+    pub collect_processes: bool,
+    /// Processes listed in `ProcessMetrics::top`
+    #[serde(default = "default_max_top_processes")]
+    pub max_top_processes: usize,
+    /// sysfs directory holding hwmon chips, read for fan speeds on Linux
+    #[serde(default = "default_hwmon_root")]
+    pub hwmon_root: String,
+    /// Skip the optional network, GPU and process collectors while available memory is
+    /// below this many bytes (0 disables the guard)
+    #[serde(default)]
+    pub min_available_memory_bytes: u64,
+    /// Successful collections kept in the history ring buffer (0 disables history)
+    #[serde(default = "default_history_capacity")]
+    pub history_capacity: usize,
+    /// Age in seconds beyond which streamed metrics are sent as `stale_data` events
+    #[serde(default = "default_stale_threshold_seconds")]
+    pub stale_threshold_seconds: u32,
+}
+
+/// Accepted range for `collection_interval_seconds`
+pub const COLLECTION_INTERVAL_RANGE: std::ops::RangeInclusive<u64> = 1..=3600;
+
+/// Accepted range for `collection_timeout_ms`
+pub const COLLECTION_TIMEOUT_RANGE_MS: std::ops::RangeInclusive<u64> = 100..=30_000;
+
+/// Accepted range for `max_top_processes`
+pub const MAX_TOP_PROCESSES_RANGE: std::ops::RangeInclusive<u64> = 0..=50;
+
+/// Accepted range for `max_cache_entries`
+pub const MAX_CACHE_ENTRIES_RANGE: std::ops::RangeInclusive<u64> = 1..=10_000;
+
+/// Accepted range for `history_capacity`, a day of samples at the shortest interval
+pub const HISTORY_CAPACITY_RANGE: std::ops::RangeInclusive<u64> = 0..=86_400;
+
+/// A configuration value that can't be applied
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ConfigValidationError {
+    /// A value outside its accepted range
+    #[error("{field} must be between {min} and {max}, got {value}")]
+    OutOfRange {
+        field: &'static str,
+        value: u64,
+        min: u64,
+        max: u64,
+    },
+    /// A host path that only the process configuration may set
+    #[error("{field} can't be changed at runtime, expected {expected}")]
+    PathOverride {
+        field: &'static str,
+        expected: String,
+    },
+    /// A threshold that would trigger after the more severe one it pairs with
+    #[error("{field} must not exceed {limit}")]
+    Misordered {
+        field: &'static str,
+        limit: &'static str,
+    },
+}
+
+impl ConfigValidationError {
+    /// Name of the rejected field
+    pub fn field(&self) -> &'static str {
+        match self {
+            Self::OutOfRange { field, .. } | Self::PathOverride { field, .. } | Self::Misordered { field, .. } => field,
+        }
+    }
+}
+
+impl MetricsServiceConfig {
+    /// Check values that would make collection misbehave
+    ///
+    /// Collector paths are fixed: pointing `proc_root` or `hwmon_root` elsewhere
+    /// would let a config update read arbitrary host files.
+    pub fn validate(&self) -> Result<(), ConfigValidationError> {
+        let check = |field, value: u64, range: std::ops::RangeInclusive<u64>| {
+            if range.contains(&value) {
+                Ok(())
+            } else {
+                Err(ConfigValidationError::OutOfRange { field, value, min: *range.start(), max: *range.end() })
+            }
+        };
+        let check_path = |field, value: &str, expected: String| {
+            if value == expected {
+                Ok(())
+            } else {
+                Err(ConfigValidationError::PathOverride { field, expected })
+            }
+        };
+
+        check("collection_interval_seconds", self.collection_interval_seconds as u64, COLLECTION_INTERVAL_RANGE)?;
+        check("collection_timeout_ms", self.collection_timeout_ms, COLLECTION_TIMEOUT_RANGE_MS)?;
+        check("max_top_processes", self.max_top_processes as u64, MAX_TOP_PROCESSES_RANGE)?;
+        check("max_cache_entries", self.max_cache_entries as u64, MAX_CACHE_ENTRIES_RANGE)?;
+        check("history_capacity", self.history_capacity as u64, HISTORY_CAPACITY_RANGE)?;
+        check_path("proc_root", &self.proc_root, default_proc_root())?;
+        check_path("hwmon_root", &self.hwmon_root, default_hwmon_root())
+    }
+}
+
+fn default_history_capacity() -> usize {
+    DEFAULT_HISTORY_CAPACITY
+}
+
+fn default_stale_threshold_seconds() -> u32 {
+    crate::models::server_metrics::DEFAULT_STALE_THRESHOLD_SECONDS as u32
+}
+
+fn default_concurrent_collection() -> bool {
+    true
+}
+
+fn default_collect_disk_metrics() -> bool {
+    true
+}
+
+fn default_max_top_processes() -> usize {
+    5
+}
+
+fn default_proc_root() -> String {
+    "/proc".to_string()
+}
+
+fn default_hwmon_root() -> String {
+    "/sys/class/hwmon".to_string()
+}
+
+/// Loopback and virtual interfaces skipped by default
+fn default_interface_exclude() -> Vec<String> {
+    ["lo*", "docker*", "br-*", "veth*"]
+        .iter()
+        .map(|pattern| pattern.to_string())
+        .collect()
+}
+
+/// Match a name against a glob pattern supporting `*` and `?`
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = backtrack {
+            // Let the last '*' swallow one more character
+            p = star_p + 1;
+            n = star_n + 1;
+            backtrack = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Summed counters for the interfaces that passed filtering
+#[derive(Debug, Default, PartialEq)]
+struct InterfaceTotals {
+    bytes_received: u64,
+    packets_received: u64,
+    bytes_sent: u64,
+    packets_sent: u64,
+}
+
+/// Parse `/proc/net/dev` contents, summing counters for interfaces accepted by `filter`
+fn parse_net_dev(contents: &str, filter: impl Fn(&str) -> bool) -> InterfaceTotals {
+    let mut totals = InterfaceTotals::default();
+
+    for line in contents.lines().skip(2) { // Skip header lines
+        let Some((interface, counters)) = line.split_once(':') else {
+            continue;
+        };
+        let interface = interface.trim();
+        if !filter(interface) {
+            continue;
+        }
+
+        let parts: Vec<&str> = counters.split_whitespace().collect();
+        if parts.len() < 16 {
+            continue;
+        }
+
+        if let (Ok(rx_bytes), Ok(rx_packets), Ok(tx_bytes), Ok(tx_packets)) = (
+            parts[0].parse::<u64>(), // received bytes
+            parts[1].parse::<u64>(), // received packets
+            parts[8].parse::<u64>(), // transmitted bytes
+            parts[9].parse::<u64>(), // transmitted packets
+        ) {
+            totals.bytes_received += rx_bytes;
+            totals.packets_received += rx_packets;
+            totals.bytes_sent += tx_bytes;
+            totals.packets_sent += tx_packets;
+        }
+    }
+
+    totals
 }
 
 impl Default for MetricsServiceConfig {
@@ -73,6 +337,22 @@ impl Default for MetricsServiceConfig {
             max_cache_entries: 100,
             collect_network_metrics: true,
             collect_cpu_per_core: true,
+            concurrent_collection: true,
+            memory_discrepancy_tolerance_percent: 10.0,
+            cpu_scale: CpuScale::Average,
+            interface_include: Vec::new(),
+            interface_exclude: default_interface_exclude(),
+            proc_root: default_proc_root(),
+            collect_disk_metrics: default_collect_disk_metrics(),
+            collect_gpu_metrics: false,
+            collect_sensor_metrics: false,
+            collect_temperature: false,
+            collect_processes: false,
+            max_top_processes: default_max_top_processes(),
+            hwmon_root: default_hwmon_root(),
+            min_available_memory_bytes: 0,
+            history_capacity: default_history_capacity(),
+            stale_threshold_seconds: default_stale_threshold_seconds(),
         }
     }
 }
@@ -103,16 +383,342 @@ impl CachedMetrics {
     }
 }
 
+/// Collect storage usage for every mounted filesystem via sysinfo
+fn collect_disk_usage() -> Result<DiskMetrics, MetricsCollectionError> {
+    let disks = Disks::new_with_refreshed_list();
+    let mounts = disks
+        .list()
+        .iter()
+        .filter(|disk| disk.total_space() > 0)
+        .map(|disk| {
+            let total = disk.total_space();
+            let available = disk.available_space().min(total);
+            MountMetrics::new(
+                disk.mount_point().to_string_lossy(),
+                disk.file_system().to_string_lossy(),
+                total,
+                total - available,
+                available,
+            )
+            .map_err(|e| MetricsCollectionError::internal(format!("invalid disk reading: {}", e)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(DiskMetrics::from_mounts(mounts))
+}
+
+/// Collect metrics for every GPU visible to NVML
+#[cfg(feature = "gpu")]
+fn collect_gpu_devices() -> Result<Vec<GpuMetrics>, MetricsCollectionError> {
+    use nvml_wrapper::enum_wrappers::device::TemperatureSensor;
+    use nvml_wrapper::Nvml;
+    use std::sync::OnceLock;
+
+    // Loading the NVML library is slow, so it happens once per process
+    static NVML: OnceLock<Result<Nvml, String>> = OnceLock::new();
+    let nvml = NVML
+        .get_or_init(|| Nvml::init().map_err(|e| e.to_string()))
+        .as_ref()
+        .map_err(|e| MetricsCollectionError::system_unavailable(format!("NVML unavailable: {}", e)))?;
+
+    let gpu_error = |e: nvml_wrapper::error::NvmlError| MetricsCollectionError::internal(format!("NVML query failed: {}", e));
+    let device_count = nvml.device_count().map_err(gpu_error)?;
+
+    (0..device_count)
+        .map(|index| {
+            let device = nvml.device_by_index(index).map_err(gpu_error)?;
+            let memory = device.memory_info().map_err(gpu_error)?;
+
+            Ok(GpuMetrics {
+                index,
+                name: device.name().unwrap_or_else(|_| format!("GPU {}", index)),
+                utilization_percentage: device.utilization_rates().map_err(gpu_error)?.gpu as f32,
+                memory_used_bytes: memory.used,
+                memory_total_bytes: memory.total,
+                temperature_celsius: device.temperature(TemperatureSensor::Gpu).ok().map(|t| t as f32),
+                // NVML reports milliwatts
+                power_draw_watts: device.power_usage().ok().map(|mw| mw as f32 / 1000.0),
+            })
+        })
+        .collect()
+}
+
+/// GPU collection is compiled out without the `gpu` feature
+#[cfg(not(feature = "gpu"))]
+fn collect_gpu_devices() -> Result<Vec<GpuMetrics>, MetricsCollectionError> {
+    Err(MetricsCollectionError::system_unavailable(
+        "GPU metrics require building with the `gpu` feature",
+    ))
+}
+
+/// Read a sysfs attribute, trimmed, if present
+fn read_sysfs_value(path: &std::path::Path) -> Option<String> {
+    std::fs::read_to_string(path)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Read fan speeds from every `fan*_input` under the hwmon chips in `hwmon_root`
+///
+/// Fans without a `fan*_label` are labelled `<chip name>/fanN`.
+fn read_hwmon_fans(hwmon_root: &str) -> Vec<FanReading> {
+    let Ok(entries) = std::fs::read_dir(hwmon_root) else {
+        return Vec::new();
+    };
+    let mut chips: Vec<_> = entries.flatten().map(|entry| entry.path()).collect();
+    chips.sort();
+
+    let mut fans = Vec::new();
+    for chip in chips {
+        let chip_name = read_sysfs_value(&chip.join("name"))
+            .unwrap_or_else(|| chip.file_name().unwrap_or_default().to_string_lossy().to_string());
+
+        let Ok(attributes) = std::fs::read_dir(&chip) else {
+            continue;
+        };
+        let mut inputs: Vec<String> = attributes
+            .flatten()
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|name| name.starts_with("fan") && name.ends_with("_input"))
+            .collect();
+        inputs.sort();
+
+        for input in inputs {
+            let fan = input.trim_end_matches("_input");
+            let Some(rpm) = read_sysfs_value(&chip.join(&input)).and_then(|value| value.parse().ok()) else {
+                continue;
+            };
+            let label = read_sysfs_value(&chip.join(format!("{}_label", fan)))
+                .unwrap_or_else(|| format!("{}/{}", chip_name, fan));
+            fans.push(FanReading { label, rpm });
+        }
+    }
+    fans
+}
+
+/// Sensor label fragments that identify CPU thermal zones across platforms
+/// (Intel coretemp, AMD k10temp, Raspberry Pi cpu_thermal, ARM SoC zones)
+const CPU_SENSOR_LABELS: [&str; 7] = ["cpu", "core", "package", "tctl", "tdie", "k10temp", "soc"];
+
+/// Average of the readings whose label looks like a CPU sensor, or `None` when there are none
+fn average_cpu_temperature<'a>(readings: impl IntoIterator<Item = (&'a str, f32)>) -> Option<f32> {
+    let (sum, count) = readings
+        .into_iter()
+        .filter(|(label, celsius)| {
+            let label = label.to_lowercase();
+            celsius.is_finite() && CPU_SENSOR_LABELS.iter().any(|fragment| label.contains(fragment))
+        })
+        .fold((0.0, 0), |(sum, count), (_, celsius)| (sum + celsius, count + 1));
+
+    (count > 0).then(|| sum / count as f32)
+}
+
+/// Average CPU temperature from sysinfo's thermal components, `None` without CPU sensors
+fn read_cpu_temperature() -> Option<f32> {
+    let components = Components::new_with_refreshed_list();
+    average_cpu_temperature(
+        components
+            .iter()
+            .map(|component| (component.label(), component.temperature())),
+    )
+}
+
+/// Collect every temperature sensor sysinfo knows about, plus hwmon fans on Linux
+fn collect_sensor_readings(hwmon_root: &str) -> SensorMetrics {
+    let components = Components::new_with_refreshed_list();
+    let temperatures = components
+        .iter()
+        .filter(|component| component.temperature().is_finite())
+        .map(|component| TemperatureReading {
+            label: component.label().to_string(),
+            temperature_celsius: component.temperature(),
+            max_celsius: Some(component.max()).filter(|max| max.is_finite() && *max > 0.0),
+            critical_celsius: component.critical().filter(|critical| critical.is_finite()),
+        })
+        .collect();
+
+    let fans = if cfg!(target_os = "linux") {
+        read_hwmon_fans(hwmon_root)
+    } else {
+        Vec::new()
+    };
+
+    SensorMetrics { temperatures, fans }
+}
+
+/// Network collector that reads `/proc` files rather than sysinfo
+///
+/// Owns its configuration so it can run on a blocking thread without
+/// holding the sysinfo lock. Where `/proc` is unavailable (macOS, Windows,
+/// or a container without procfs) it falls back to `sysinfo::Networks`.
+#[derive(Debug, Clone)]
+struct NetworkCollector {
+    include: Vec<String>,
+    exclude: Vec<String>,
+    proc_root: String,
+}
+
+impl NetworkCollector {
+    /// Check an interface against the include/exclude globs
+    fn is_counted(&self, interface: &str) -> bool {
+        let included = self.include.is_empty()
+            || self.include.iter().any(|pattern| glob_match(pattern, interface));
+        let excluded = self.exclude.iter().any(|pattern| glob_match(pattern, interface));
+
+        included && !excluded
+    }
+
+    /// Collect network metrics and the backend that produced them
+    fn collect(&self) -> Result<(NetworkMetrics, MetricSource), MetricsCollectionError> {
+        let path = format!("{}/net/dev", self.proc_root);
+        if cfg!(target_os = "linux") && std::path::Path::new(&path).exists() {
+            Ok((self.collect_from_proc(&path)?, MetricSource::Proc))
+        } else {
+            Ok((self.collect_from_sysinfo(), MetricSource::Sysinfo))
+        }
+    }
+
+    /// Collect network metrics from /proc/net/dev
+    fn collect_from_proc(&self, path: &str) -> Result<NetworkMetrics, MetricsCollectionError> {
+        let contents = std::fs::read_to_string(path).map_err(|error| {
+            MetricsCollectionError::network_error("all", format!("failed to read {}: {}", path, error))
+        })?;
+        let totals = parse_net_dev(&contents, |interface| self.is_counted(interface));
+
+        // Get active connections count
+        let active_connections = self.estimate_active_connections();
+
+        Ok(NetworkMetrics {
+            bytes_sent: totals.bytes_sent,
+            bytes_received: totals.bytes_received,
+            packets_sent: totals.packets_sent,
+            packets_received: totals.packets_received,
+            active_connections,
+            counter_reset: false,
+            rate_bytes_sent_per_sec: 0.0,
+            rate_bytes_received_per_sec: 0.0,
+        })
+    }
+
+    /// Collect interface counters via sysinfo where `/proc/net/dev` is unavailable
+    fn collect_from_sysinfo(&self) -> NetworkMetrics {
+        let networks = Networks::new_with_refreshed_list();
+        let mut totals = InterfaceTotals::default();
+        for (interface, data) in networks.iter().filter(|(interface, _)| self.is_counted(interface)) {
+            debug!("Counting interface {} via sysinfo", interface);
+            totals.bytes_received += data.total_received();
+            totals.packets_received += data.total_packets_received();
+            totals.bytes_sent += data.total_transmitted();
+            totals.packets_sent += data.total_packets_transmitted();
+        }
+
+        NetworkMetrics {
+            bytes_sent: totals.bytes_sent,
+            bytes_received: totals.bytes_received,
+            packets_sent: totals.packets_sent,
+            packets_received: totals.packets_received,
+            active_connections: self.estimate_active_connections(),
+            ..NetworkMetrics::default()
+        }
+    }
+
+    /// Estimate active network connections
+    fn estimate_active_connections(&self) -> u32 {
+        use std::fs;
+        
+        // Connection tables are only readable from procfs
+        if !cfg!(target_os = "linux") {
+            static UNSUPPORTED: std::sync::Once = std::sync::Once::new();
+            UNSUPPORTED.call_once(|| {
+                warn!("Active connection counting is not supported on this platform, reporting 0");
+            });
+            return 0;
+        }
+
+        // Count TCP connections in ESTABLISHED state
+        let mut count = 0;
+        
+        if let Ok(contents) = fs::read_to_string(format!("{}/net/tcp", self.proc_root)) {
+            for line in contents.lines().skip(1) { // Skip header
+                let parts: Vec<&str> = line.split_whitespace().collect();
+                if parts.len() >= 4 && parts[3] == "01" { // 01 = ESTABLISHED
+                    count += 1;
+                }
+            }
+        }
+        
+        // Also count IPv6 connections
+        if let Ok(contents) = fs::read_to_string(format!("{}/net/tcp6", self.proc_root)) {
+            for line in contents.lines().skip(1) { // Skip header
+                let parts: Vec<&str> = line.split_whitespace().collect();
+                if parts.len() >= 4 && parts[3] == "01" { // 01 = ESTABLISHED
+                    count += 1;
+                }
+            }
+        }
+        
+        count
+    }
+}
+
+/// Run a sysinfo-bound collector alongside a file-based one
+///
+/// When `concurrent` is set, the file collector runs on a blocking thread
+/// while the sysinfo collector holds the `System` lock, so total latency is
+/// roughly the slower of the two instead of their sum.
+async fn join_collectors<A, B, FA, FB>(
+    concurrent: bool,
+    sysinfo_collector: FA,
+    file_collector: FB,
+) -> (A, Result<B, MetricsCollectionError>)
+where
+    FA: Future<Output = A>,
+    FB: FnOnce() -> B + Send + 'static,
+    B: Send + 'static,
+{
+    if concurrent {
+        let file_task = spawn_blocking_named(FILE_COLLECTORS_TASK, file_collector);
+        let (sysinfo_result, file_result) = tokio::join!(sysinfo_collector, file_task);
+        let file_result = file_result.map_err(|e| {
+            MetricsCollectionError::internal(format!("file collector task failed: {}", e))
+        });
+        (sysinfo_result, file_result)
+    } else {
+        let sysinfo_result = sysinfo_collector.await;
+        (sysinfo_result, Ok(file_collector()))
+    }
+}
+
+/// Previous network counter sample used as the delta baseline
+#[derive(Debug, Clone)]
+struct NetworkSample {
+    metrics: NetworkMetrics,
+    sampled_at: Instant,
+}
+
 /// Service for collecting system metrics
 pub struct MetricsService {
-    config: MetricsServiceConfig,
+    config: Arc<RwLock<MetricsServiceConfig>>,
     system: Arc<Mutex<System>>,
     cache: Arc<RwLock<Option<CachedMetrics>>>,
     collection_stats: Arc<RwLock<CollectionStats>>,
+    previous_network: Arc<RwLock<Option<NetworkSample>>>,
+    /// Recent successful collections, oldest first
+    history: Arc<MetricsHistory>,
+    /// Errors returned by the next collections in place of collecting, in order
+    #[cfg(test)]
+    injected_failures: std::sync::Mutex<VecDeque<MetricsCollectionError>>,
+    /// Extra time the next sysinfo refresh blocks for, simulating a hung call
+    #[cfg(test)]
+    injected_delay: std::sync::Mutex<Option<Duration>>,
 }
 
+/// Collection errors kept in `CollectionStats::recent_errors`
+pub const RECENT_ERRORS_CAPACITY: usize = 50;
+
 /// Statistics about metrics collection performance
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CollectionStats {
     pub total_collections: u64,
     pub successful_collections: u64,
@@ -123,6 +729,62 @@ pub struct CollectionStats {
     pub cache_misses: u64,
     pub average_collection_time_ms: f64,
     pub last_error: Option<MetricsCollectionError>,
+    /// Errors from failed and partial collections with when they happened, newest first
+    #[serde(default)]
+    pub recent_errors: VecDeque<(DateTime<Utc>, MetricsCollectionError)>,
+}
+
+impl CollectionStats {
+    /// Remember `error`, dropping the oldest beyond `RECENT_ERRORS_CAPACITY`
+    fn record_error(&mut self, at: DateTime<Utc>, error: MetricsCollectionError) {
+        self.recent_errors.push_front((at, error));
+        self.recent_errors.truncate(RECENT_ERRORS_CAPACITY);
+    }
+}
+
+/// Timing summary from [`MetricsService::benchmark`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkStats {
+    pub iterations: u32,
+    pub min_ms: f64,
+    pub max_ms: f64,
+    pub avg_ms: f64,
+    pub p95_ms: f64,
+}
+
+impl BenchmarkStats {
+    /// Summarize a set of durations
+    fn from_durations(durations: &[Duration]) -> Self {
+        let mut millis: Vec<f64> = durations
+            .iter()
+            .map(|duration| duration.as_secs_f64() * 1000.0)
+            .collect();
+        millis.sort_by(|a, b| a.total_cmp(b));
+
+        let avg_ms = if millis.is_empty() {
+            0.0
+        } else {
+            millis.iter().sum::<f64>() / millis.len() as f64
+        };
+
+        Self {
+            iterations: millis.len() as u32,
+            min_ms: millis.first().copied().unwrap_or(0.0),
+            max_ms: millis.last().copied().unwrap_or(0.0),
+            avg_ms,
+            p95_ms: percentile(&millis, 95.0),
+        }
+    }
+}
+
+/// Nearest-rank percentile of an ascending slice (0 when empty)
+pub fn percentile(sorted: &[f64], percent: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+
+    let rank = ((percent / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
 }
 
 impl MetricsService {
@@ -132,20 +794,43 @@ impl MetricsService {
     }
 
     /// Create a new metrics service with custom configuration
-    pub fn with_config(config: MetricsServiceConfig) -> Self {
+    pub fn with_config(mut config: MetricsServiceConfig) -> Self {
+        config.collection_interval_seconds =
+            clamp_interval_seconds("collection_interval_seconds", config.collection_interval_seconds);
+
         let system = System::new_with_specifics(RefreshKind::new()
             .with_cpu(CpuRefreshKind::everything())
             .with_memory(MemoryRefreshKind::everything())
         );
 
+        let history = Arc::new(MetricsHistory::new(config.history_capacity));
+
         Self {
-            config,
+            config: Arc::new(RwLock::new(config)),
             system: Arc::new(Mutex::new(system)),
             cache: Arc::new(RwLock::new(None)),
             collection_stats: Arc::new(RwLock::new(CollectionStats::default())),
+            previous_network: Arc::new(RwLock::new(None)),
+            history,
+            #[cfg(test)]
+            injected_failures: std::sync::Mutex::new(VecDeque::new()),
+            #[cfg(test)]
+            injected_delay: std::sync::Mutex::new(None),
         }
     }
 
+    /// Make the next collection fail with `error`; repeated calls queue further failures
+    #[cfg(test)]
+    pub(crate) fn fail_next_collection(&self, error: MetricsCollectionError) {
+        self.injected_failures.lock().unwrap().push_back(error);
+    }
+
+    /// Make the next sysinfo refresh block for `delay`
+    #[cfg(test)]
+    pub(crate) fn delay_next_collection(&self, delay: Duration) {
+        *self.injected_delay.lock().unwrap() = Some(delay);
+    }
+
     /// Initialize the service by performing an initial system refresh
     #[instrument(skip(self))]
     pub async fn initialize(&self) -> Result<(), MetricsCollectionError> {
@@ -188,17 +873,26 @@ impl MetricsService {
         let result = self.perform_collection().await;
         let collection_duration = start_time.elapsed().as_millis() as u64;
 
+        let failed_at = Utc::now();
         match &result {
             MetricsResponse::Ok(metrics) | MetricsResponse::PartialData { data: metrics, .. } => {
                 // Cache successful result
                 let cached = CachedMetrics::new(metrics.clone(), collection_duration);
                 *self.cache.write().unwrap() = Some(cached);
+                self.history.record(metrics.clone());
                 
+                let partial_errors = match &result {
+                    MetricsResponse::PartialData { errors, .. } => errors.clone(),
+                    _ => Vec::new(),
+                };
                 self.update_stats(|stats| {
                     stats.successful_collections += 1;
                     stats.average_collection_time_ms = 
                         (stats.average_collection_time_ms * (stats.successful_collections - 1) as f64 + collection_duration as f64) 
                         / stats.successful_collections as f64;
+                    for error in partial_errors {
+                        stats.record_error(failed_at, error);
+                    }
                 }).await;
 
                 debug!("Metrics collected successfully in {}ms", collection_duration);
@@ -207,6 +901,7 @@ impl MetricsService {
                 self.update_stats(|stats| {
                     stats.failed_collections += 1;
                     stats.last_error = Some(error.clone());
+                    stats.record_error(failed_at, error.clone());
                 }).await;
 
                 error!("Failed to collect metrics: {}", error);
@@ -216,6 +911,35 @@ impl MetricsService {
         result
     }
 
+    /// Collect fresh metrics, retrying recoverable errors after their `retry_delay_ms`
+    ///
+    /// Makes at most `max_attempts` collections (at least one) and returns the
+    /// last error once they are used up.
+    #[instrument(skip(self))]
+    pub async fn collect_with_retry(&self, max_attempts: u32) -> MetricsResponse<ServerMetrics> {
+        let max_attempts = max_attempts.max(1);
+        let mut attempt = 1;
+        loop {
+            let result = self.collect_fresh_metrics().await;
+            let delay_ms = match &result {
+                MetricsResponse::Error(error) if attempt < max_attempts && error.is_recoverable() => error.retry_delay_ms(),
+                _ => None,
+            };
+            let Some(delay_ms) = delay_ms else {
+                return result;
+            };
+
+            warn!("Collection attempt {}/{} failed, retrying in {}ms", attempt, max_attempts, delay_ms);
+            tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+            attempt += 1;
+        }
+    }
+
+    /// Recent successful collections, oldest first
+    pub fn history(&self) -> &MetricsHistory {
+        &self.history
+    }
+
     /// Collect OS information independently
     #[instrument(skip(self))]
     pub async fn collect_os_info(&self) -> Result<OsInfo, MetricsCollectionError> {
@@ -223,44 +947,151 @@ impl MetricsService {
         self.collect_os_info_from_system(&system)
     }
 
-    /// Perform the actual metrics collection
+    /// Perform the actual metrics collection, bounded by `collection_timeout_ms`
     async fn perform_collection(&self) -> MetricsResponse<ServerMetrics> {
+        #[cfg(test)]
+        if let Some(error) = self.injected_failures.lock().unwrap().pop_front() {
+            return MetricsResponse::Error(error);
+        }
+
+        let timeout_ms = self.config().collection_timeout_ms;
+        match tokio::time::timeout(Duration::from_millis(timeout_ms), self.collect_all()).await {
+            Ok(result) => result,
+            Err(_) => {
+                warn!("Metrics collection exceeded {}ms timeout", timeout_ms);
+                MetricsResponse::Error(MetricsCollectionError::timeout(timeout_ms))
+            }
+        }
+    }
+
+    /// Run every enabled collector and assemble the result
+    async fn collect_all(&self) -> MetricsResponse<ServerMetrics> {
         let mut errors = Vec::new();
         let collection_time = Utc::now();
 
-        // Refresh system information
-        let mut system = self.system.lock().await;
-        system.refresh_all();
+        // Optional collectors can push a starved host into OOM, so they run only above the floor
+        let memory_pressure = self.check_memory_floor().await;
+        let mut skip_optional = |collector: &str| match memory_pressure {
+            Some((available, floor)) => {
+                warn!("Skipping {} collection: {} bytes available, floor is {}", collector, available, floor);
+                errors.push(MetricsCollectionError::skipped_due_to_memory_pressure(collector, available, floor));
+                true
+            }
+            None => false,
+        };
+        let collect_network = self.config().collect_network_metrics && !skip_optional("network");
+        let collect_gpu = self.config().collect_gpu_metrics && !skip_optional("gpu");
+        let collect_processes = self.config().collect_processes && !skip_optional("process");
+
+        // sysinfo-based collectors share the System lock
+        let sysinfo_collector = async {
+            let system = self.refresh_system().await?;
+            Ok((
+                self.collect_memory_metrics(&system),
+                self.collect_cpu_metrics(&system),
+                collect_processes.then(|| self.collect_process_metrics(&system)),
+            ))
+        };
+        let collect_disk = self.config().collect_disk_metrics;
+
+        // File-based collectors don't need the lock
+        let network_collector = collect_network.then(|| self.network_collector());
+        let file_collector = move || network_collector.map(|collector| collector.collect());
+
+        let concurrent = self.config().concurrent_collection;
+        let (sysinfo_result, file_result) = join_collectors(
+            concurrent,
+            sysinfo_collector,
+            file_collector,
+        ).await;
+        let (memory_result, cpu_result, process_metrics) = match sysinfo_result {
+            Ok(results) => results,
+            Err(error) => return MetricsResponse::Error(error),
+        };
+
+        // Record where each category's values came from
+        let mut sources = BTreeMap::new();
 
         // Collect memory metrics
-        let memory_metrics = match self.collect_memory_metrics(&system) {
-            Ok(metrics) => metrics,
+        let memory_metrics = match memory_result {
+            Ok(metrics) => {
+                sources.insert("memory".to_string(), MetricSource::Sysinfo);
+                metrics
+            }
             Err(error) => {
                 errors.push(error);
+                sources.insert("memory".to_string(), MetricSource::Default);
                 MemoryMetrics::default() // Use default if collection fails
             }
         };
 
         // Collect CPU metrics
-        let cpu_metrics = match self.collect_cpu_metrics(&system) {
-            Ok(metrics) => metrics,
+        let cpu_metrics = match cpu_result {
+            Ok(metrics) => {
+                sources.insert("cpu".to_string(), MetricSource::Sysinfo);
+                metrics
+            }
             Err(error) => {
                 errors.push(error);
+                sources.insert("cpu".to_string(), MetricSource::Default);
                 CpuMetrics::default() // Use default if collection fails
             }
         };
 
+        if process_metrics.is_some() {
+            sources.insert("process".to_string(), MetricSource::Sysinfo);
+        }
+
         // Collect network metrics
-        let network_metrics = if self.config.collect_network_metrics {
-            match self.collect_network_metrics(&system) {
-                Ok(metrics) => metrics,
+        let network_metrics = match file_result.and_then(|result| result.transpose()) {
+            Ok(Some((mut metrics, source))) => {
+                self.track_network_counters(&mut metrics, Instant::now());
+                sources.insert("network".to_string(), source);
+                metrics
+            }
+            Ok(None) => {
+                sources.insert("network".to_string(), MetricSource::Default);
+                NetworkMetrics::default()
+            }
+            Err(error) => {
+                errors.push(error);
+                sources.insert("network".to_string(), MetricSource::Default);
+                NetworkMetrics::default() // Use default if collection fails
+            }
+        };
+
+        // Collect GPU metrics when enabled
+        let gpu_metrics = if collect_gpu {
+            match collect_gpu_devices() {
+                Ok(gpus) => {
+                    sources.insert("gpu".to_string(), MetricSource::Sysinfo);
+                    gpus
+                }
+                Err(error) => {
+                    errors.push(error);
+                    sources.insert("gpu".to_string(), MetricSource::Default);
+                    Vec::new()
+                }
+            }
+        } else {
+            Vec::new()
+        };
+
+        // Collect disk metrics when enabled
+        let disk_metrics = if collect_disk {
+            match collect_disk_usage() {
+                Ok(disk) => {
+                    sources.insert("disk".to_string(), MetricSource::Sysinfo);
+                    Some(disk)
+                }
                 Err(error) => {
                     errors.push(error);
-                    NetworkMetrics::default() // Use default if collection fails
+                    sources.insert("disk".to_string(), MetricSource::Default);
+                    None
                 }
             }
         } else {
-            NetworkMetrics::default()
+            None
         };
 
         // Get system uptime using sysinfo 0.30 API
@@ -283,6 +1114,10 @@ impl MetricsService {
             cpu_usage: cpu_metrics,
             uptime,
             network_metrics,
+            sources,
+            gpu_metrics,
+            disk_metrics,
+            process_metrics,
         };
 
         // Return appropriate response based on errors
@@ -309,13 +1144,49 @@ impl MetricsService {
         }
     }
 
+    /// Refresh sysinfo on a blocking thread, returning the locked `System`
+    ///
+    /// `refresh_all` can stall on slow procfs reads, so it runs off the async
+    /// workers where the collection timeout can abandon it.
+    async fn refresh_system(&self) -> Result<tokio::sync::OwnedMutexGuard<System>, MetricsCollectionError> {
+        let mut system = self.system.clone().lock_owned().await;
+        #[cfg(test)]
+        let delay = self.injected_delay.lock().unwrap().take();
+
+        spawn_blocking_named(SYSINFO_REFRESH_TASK, move || {
+            #[cfg(test)]
+            if let Some(delay) = delay {
+                std::thread::sleep(delay);
+            }
+            system.refresh_all();
+            system
+        })
+        .await
+        .map_err(|e| MetricsCollectionError::internal(format!("sysinfo refresh task failed: {}", e)))
+    }
+
+    /// Available and floor bytes when available memory is below the configured floor
+    async fn check_memory_floor(&self) -> Option<(u64, u64)> {
+        let floor = self.config().min_available_memory_bytes;
+        if floor == 0 {
+            return None;
+        }
+
+        let available = {
+            let mut system = self.system.lock().await;
+            system.refresh_memory();
+            system.available_memory()
+        };
+        (available < floor).then_some((available, floor))
+    }
+
     /// Collect memory metrics from system
     fn collect_memory_metrics(&self, system: &System) -> Result<MemoryMetrics, MetricsCollectionError> {
         let total_memory = system.total_memory();
         let used_memory = system.used_memory();
         let available_memory = system.available_memory();
-        let _total_swap = system.total_swap();
-        let _used_swap = system.used_swap();
+        let total_swap = system.total_swap();
+        let used_swap = system.used_swap().min(total_swap);
 
         if total_memory == 0 {
             return Err(MetricsCollectionError::memory_error("total memory is zero"));
@@ -326,6 +1197,9 @@ impl MetricsService {
             used_bytes: used_memory,
             available_bytes: available_memory,
             usage_percentage: ((used_memory as f64 / total_memory as f64) * 100.0) as f32,
+            swap_total_bytes: total_swap,
+            swap_used_bytes: used_swap,
+            swap_usage_percentage: MemoryMetrics::swap_percentage(total_swap, used_swap),
         })
     }
 
@@ -339,7 +1213,7 @@ impl MetricsService {
 
         let overall_usage = system.global_cpu_info().cpu_usage();
         
-        let _per_core_usage = if self.config.collect_cpu_per_core {
+        let core_usage_percentages = if self.config().collect_cpu_per_core {
             cpus.iter().map(|cpu| cpu.cpu_usage()).collect()
         } else {
             vec![]
@@ -352,89 +1226,85 @@ impl MetricsService {
             fifteen_minute: system_load_average.fifteen as f32,
         };
 
-        Ok(CpuMetrics {
+        // sysinfo reports the average across cores
+        let cpu_metrics = CpuMetrics {
             usage_percentage: overall_usage,
             core_count: cpus.len() as u32,
+            scale: CpuScale::Average,
             load_average,
-        })
+            core_usage_percentages,
+            temperature_celsius: if self.config().collect_temperature { read_cpu_temperature() } else { None },
+        };
+
+        Ok(cpu_metrics.to_scale(self.config().cpu_scale))
     }
 
-    /// Collect network metrics from system
-    fn collect_network_metrics(&self, _system: &System) -> Result<NetworkMetrics, MetricsCollectionError> {
-        // Read network statistics from /proc/net/dev on Linux
-        use std::fs;
-        
-        let mut total_bytes_sent = 0;
-        let mut total_bytes_received = 0;
-        let mut total_packets_sent = 0;
-        let mut total_packets_received = 0;
+    /// Count processes and pick the heaviest by CPU
+    fn collect_process_metrics(&self, system: &System) -> ProcessMetrics {
+        let mut running = 0;
+        let processes = system
+            .processes()
+            .iter()
+            .map(|(pid, process)| {
+                if process.status() == sysinfo::ProcessStatus::Run {
+                    running += 1;
+                }
+                ProcessInfo {
+                    pid: pid.as_u32(),
+                    name: process.name().to_string(),
+                    cpu_percentage: process.cpu_usage(),
+                    memory_bytes: process.memory(),
+                }
+            })
+            .collect();
 
-        if let Ok(contents) = fs::read_to_string("/proc/net/dev") {
-            for line in contents.lines().skip(2) { // Skip header lines
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if parts.len() >= 17 {
-                    let interface = parts[0].trim_end_matches(':');
-                    
-                    // Skip loopback and virtual interfaces
-                    if interface.starts_with("lo") || interface.starts_with("docker") || 
-                       interface.starts_with("br-") || interface.starts_with("veth") {
-                        continue;
-                    }
+        ProcessMetrics::from_processes(processes, running, self.config().max_top_processes)
+    }
 
-                    if let (Ok(rx_bytes), Ok(rx_packets), Ok(tx_bytes), Ok(tx_packets)) = (
-                        parts[1].parse::<u64>(),  // received bytes
-                        parts[2].parse::<u64>(),  // received packets
-                        parts[9].parse::<u64>(),  // transmitted bytes
-                        parts[10].parse::<u64>(), // transmitted packets
-                    ) {
-                        total_bytes_received += rx_bytes;
-                        total_packets_received += rx_packets;
-                        total_bytes_sent += tx_bytes;
-                        total_packets_sent += tx_packets;
-                    }
-                }
+    /// Compare network counters with the previous sample and flag resets
+    ///
+    /// The current sample always becomes the new baseline, so a reset only
+    /// affects the sample in which it is detected.
+    fn track_network_counters(&self, metrics: &mut NetworkMetrics, sampled_at: Instant) {
+        let mut previous = self.previous_network.write().unwrap();
+
+        if let Some(sample) = previous.as_ref() {
+            let elapsed = sampled_at.saturating_duration_since(sample.sampled_at);
+            let deltas = [
+                CounterDelta::between(sample.metrics.bytes_sent, metrics.bytes_sent, elapsed),
+                CounterDelta::between(sample.metrics.bytes_received, metrics.bytes_received, elapsed),
+                CounterDelta::between(sample.metrics.packets_sent, metrics.packets_sent, elapsed),
+                CounterDelta::between(sample.metrics.packets_received, metrics.packets_received, elapsed),
+            ];
+
+            if deltas.iter().any(|delta| delta.counter_reset) {
+                warn!("Network counter reset detected, resetting rate baseline");
+                metrics.counter_reset = true;
+            } else {
+                metrics.rate_bytes_sent_per_sec = deltas[0].rate_per_second;
+                metrics.rate_bytes_received_per_sec = deltas[1].rate_per_second;
             }
         }
 
-        // Get active connections count
-        let active_connections = self.estimate_active_connections();
-
-        Ok(NetworkMetrics {
-            bytes_sent: total_bytes_sent,
-            bytes_received: total_bytes_received,
-            packets_sent: total_packets_sent,
-            packets_received: total_packets_received,
-            active_connections,
-        })
+        *previous = Some(NetworkSample {
+            metrics: metrics.clone(),
+            sampled_at,
+        });
     }
 
-    /// Estimate active network connections
-    fn estimate_active_connections(&self) -> u32 {
-        use std::fs;
-        
-        // Count TCP connections in ESTABLISHED state
-        let mut count = 0;
-        
-        if let Ok(contents) = fs::read_to_string("/proc/net/tcp") {
-            for line in contents.lines().skip(1) { // Skip header
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if parts.len() >= 4 && parts[3] == "01" { // 01 = ESTABLISHED
-                    count += 1;
-                }
-            }
+    /// File-based network collector using the configured interface filters
+    fn network_collector(&self) -> NetworkCollector {
+        NetworkCollector {
+            include: self.config().interface_include.clone(),
+            exclude: self.config().interface_exclude.clone(),
+            proc_root: self.config().proc_root.clone(),
         }
-        
-        // Also count IPv6 connections
-        if let Ok(contents) = fs::read_to_string("/proc/net/tcp6") {
-            for line in contents.lines().skip(1) { // Skip header
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if parts.len() >= 4 && parts[3] == "01" { // 01 = ESTABLISHED
-                    count += 1;
-                }
-            }
-        }
-        
-        count
+    }
+
+    /// Check an interface against the configured include/exclude globs
+    #[cfg(test)]
+    fn is_interface_counted(&self, interface: &str) -> bool {
+        self.network_collector().is_counted(interface)
     }
 
     /// Collect OS information from system
@@ -505,7 +1375,7 @@ impl MetricsService {
     async fn get_from_cache(&self) -> Option<CachedMetrics> {
         let cache = self.cache.read().unwrap();
         if let Some(ref cached) = *cache {
-            let cache_duration = Duration::from_secs(self.config.cache_duration_seconds as u64);
+            let cache_duration = Duration::from_secs(self.config().cache_duration_seconds as u64);
             if !cached.is_expired(cache_duration) {
                 return Some(cached.clone());
             }
@@ -522,14 +1392,67 @@ impl MetricsService {
         updater(&mut *stats);
     }
 
+    /// Run fresh collection `iterations` times and summarize how long it took
+    ///
+    /// Bypasses the cache entirely, so results and stats are left untouched.
+    #[instrument(skip(self))]
+    pub async fn benchmark(&self, iterations: u32) -> BenchmarkStats {
+        let mut durations = Vec::with_capacity(iterations as usize);
+
+        for _ in 0..iterations {
+            let start = Instant::now();
+            let _ = self.perform_collection().await;
+            durations.push(start.elapsed());
+        }
+
+        let stats = BenchmarkStats::from_durations(&durations);
+        debug!("Benchmarked {} collections: {:?}", iterations, stats);
+        stats
+    }
+
+    /// Collect GPU metrics on demand, one entry per device
+    pub async fn collect_gpu_metrics(&self) -> Result<Vec<GpuMetrics>, MetricsCollectionError> {
+        if !self.config().collect_gpu_metrics {
+            return Err(MetricsCollectionError::system_unavailable("GPU metrics collection is disabled"));
+        }
+
+        spawn_blocking_named(GPU_COLLECTOR_TASK, collect_gpu_devices)
+            .await
+            .map_err(|e| MetricsCollectionError::internal(format!("GPU collector task failed: {}", e)))?
+    }
+
+    /// Collect temperature and fan sensors on demand
+    ///
+    /// Hosts without sensors get empty lists rather than an error.
+    pub async fn collect_sensor_metrics(&self) -> Result<SensorMetrics, MetricsCollectionError> {
+        if !self.config().collect_sensor_metrics {
+            return Err(MetricsCollectionError::system_unavailable("Sensor metrics collection is disabled"));
+        }
+
+        let hwmon_root = self.config().hwmon_root.clone();
+        spawn_blocking_named(SENSOR_COLLECTOR_TASK, move || collect_sensor_readings(&hwmon_root))
+            .await
+            .map_err(|e| MetricsCollectionError::internal(format!("Sensor collector task failed: {}", e)))
+    }
+
+    /// Whether at least one collection has returned data
+    pub fn has_collected(&self) -> bool {
+        self.collection_stats.read().unwrap().successful_collections > 0
+    }
+
     /// Get service statistics
     pub async fn get_stats(&self) -> CollectionStats {
         self.collection_stats.read().unwrap().clone()
     }
 
-    /// Get service configuration
-    pub fn get_config(&self) -> &MetricsServiceConfig {
-        &self.config
+    /// Current service configuration
+    pub fn get_config(&self) -> MetricsServiceConfig {
+        self.config().clone()
+    }
+
+    /// Read access to the live configuration; don't hold it across an await
+    fn config(&self) -> std::sync::RwLockReadGuard<'_, MetricsServiceConfig> {
+        self.config.read().unwrap()
     }
 
     /// Clear the metrics cache
@@ -539,13 +1462,22 @@ impl MetricsService {
         debug!("Metrics cache cleared");
     }
 
-    /// Update service configuration
-    #[allow(dead_code)]
-    pub async fn update_config(&mut self, new_config: MetricsServiceConfig) {
-        self.config = new_config;
+    /// Validate and apply a new configuration, returning the effective config
+    ///
+    /// `history_capacity` only takes effect on restart, since the history
+    /// buffer is sized when the service is created.
+    pub async fn update_config(
+        &self,
+        mut new_config: MetricsServiceConfig,
+    ) -> Result<MetricsServiceConfig, ConfigValidationError> {
+        new_config.validate()?;
+        new_config.collection_interval_seconds =
+            clamp_interval_seconds("collection_interval_seconds", new_config.collection_interval_seconds);
+        *self.config.write().unwrap() = new_config.clone();
         // Clear cache since configuration changed
         self.clear_cache().await;
         debug!("MetricsService configuration updated");
+        Ok(new_config)
     }
 }
 
@@ -559,12 +1491,515 @@ impl Default for MetricsService {
 mod tests {
     use super::*;
     use tokio::time::{sleep, Duration as TokioDuration};
+    use tracing_test::traced_test;
+
+    #[test]
+    #[traced_test]
+    fn test_sub_minimum_interval_is_clamped() {
+        let config = MetricsServiceConfig {
+            collection_interval_seconds: 0,
+            ..MetricsServiceConfig::default()
+        };
+        let service = MetricsService::with_config(config);
+
+        assert_eq!(service.get_config().collection_interval_seconds, minimum_interval_seconds());
+        assert!(Duration::from_secs(minimum_interval_seconds() as u64) >= sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+        assert!(logs_contain("below the minimum CPU refresh interval"));
+    }
+
+    #[test]
+    fn test_network_counter_reset_detection() {
+        let service = MetricsService::new();
+        let start = Instant::now();
+        let sample = |bytes: u64| NetworkMetrics {
+            bytes_sent: bytes,
+            bytes_received: bytes,
+            packets_sent: bytes / 100,
+            packets_received: bytes / 100,
+            ..NetworkMetrics::default()
+        };
+
+        let mut first = sample(10_000);
+        service.track_network_counters(&mut first, start);
+        assert!(!first.counter_reset);
+
+        let mut second = sample(20_000);
+        service.track_network_counters(&mut second, start + Duration::from_secs(1));
+        assert!(!second.counter_reset);
+
+        // Interface bounce: counters drop back near zero
+        let mut third = sample(500);
+        service.track_network_counters(&mut third, start + Duration::from_secs(2));
+        assert!(third.counter_reset);
+
+        // The reset sample is the new baseline
+        let mut fourth = sample(1_500);
+        service.track_network_counters(&mut fourth, start + Duration::from_secs(3));
+        assert!(!fourth.counter_reset);
+    }
+
+    #[test]
+    fn test_network_rates_from_proc_snapshots() {
+        let proc_root = std::env::temp_dir().join(format!("axum-sse-proc-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(proc_root.join("net")).unwrap();
+        let service = MetricsService::with_config(MetricsServiceConfig {
+            proc_root: proc_root.to_string_lossy().into_owned(),
+            interface_exclude: vec!["lo".to_string()],
+            ..MetricsServiceConfig::default()
+        });
+        let snapshot = |rx: u64, tx: u64| {
+            let contents = format!(
+                "Inter-|   Receive |  Transmit\n face |bytes packets|bytes packets\n  eth0: {} 10 0 0 0 0 0 0 {} 10 0 0 0 0 0 0\n",
+                rx, tx
+            );
+            std::fs::write(proc_root.join("net/dev"), contents).unwrap();
+            service.network_collector().collect().unwrap().0
+        };
+        let start = Instant::now();
+
+        let mut first = snapshot(10_000, 4_000);
+        service.track_network_counters(&mut first, start);
+        assert_eq!(first.rate_bytes_received_per_sec, 0.0);
+        assert_eq!(first.rate_bytes_sent_per_sec, 0.0);
+
+        let mut second = snapshot(30_000, 5_000);
+        service.track_network_counters(&mut second, start + Duration::from_secs(2));
+        assert_eq!(second.rate_bytes_received_per_sec, 10_000.0);
+        assert_eq!(second.rate_bytes_sent_per_sec, 500.0);
+
+        std::fs::remove_dir_all(&proc_root).unwrap();
+    }
+
+    const SYNTHETIC_NET_DEV: &str = "\
+Inter-|   Receive                                                |  Transmit
+ face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+    lo: 1000      10    0    0    0     0          0         0     1000      10    0    0    0     0       0          0
+  eth0: 2000      20    0    0    0     0          0         0     3000      30    0    0    0     0       0          0
+  eth1: 4000      40    0    0    0     0          0         0     5000      50    0    0    0     0       0          0
+docker0: 100       1    0    0    0     0          0         0      200       2    0    0    0     0       0          0
+br-abc123: 300     3    0    0    0     0          0         0      400       4    0    0    0     0       0          0
+vethxyz: 500       5    0    0    0     0          0         0      600       6    0    0    0     0       0          0
+";
+
+    fn counted_interfaces(service: &MetricsService) -> Vec<&'static str> {
+        ["lo", "eth0", "eth1", "docker0", "br-abc123", "vethxyz"]
+            .into_iter()
+            .filter(|interface| service.is_interface_counted(interface))
+            .collect()
+    }
+
+    #[test]
+    fn test_default_interface_exclusions() {
+        let service = MetricsService::new();
+        assert_eq!(counted_interfaces(&service), vec!["eth0", "eth1"]);
+
+        let totals = parse_net_dev(SYNTHETIC_NET_DEV, |interface| service.is_interface_counted(interface));
+        assert_eq!(totals.bytes_received, 6000);
+        assert_eq!(totals.packets_received, 60);
+        assert_eq!(totals.bytes_sent, 8000);
+        assert_eq!(totals.packets_sent, 80);
+    }
+
+    #[test]
+    fn test_interface_include_and_exclude() {
+        // Count bridge traffic but nothing else virtual
+        let service = MetricsService::with_config(MetricsServiceConfig {
+            interface_include: vec!["eth*".to_string(), "br-*".to_string()],
+            interface_exclude: vec!["eth1".to_string()],
+            ..MetricsServiceConfig::default()
+        });
+        assert_eq!(counted_interfaces(&service), vec!["eth0", "br-abc123"]);
+
+        let totals = parse_net_dev(SYNTHETIC_NET_DEV, |interface| service.is_interface_counted(interface));
+        assert_eq!(totals.bytes_received, 2300);
+        assert_eq!(totals.bytes_sent, 3400);
+
+        // No filters at all counts everything
+        let service = MetricsService::with_config(MetricsServiceConfig {
+            interface_exclude: Vec::new(),
+            ..MetricsServiceConfig::default()
+        });
+        assert_eq!(counted_interfaces(&service).len(), 6);
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("eth*", "eth0"));
+        assert!(glob_match("*", "anything"));
+        assert!(glob_match("enp?s*", "enp0s3"));
+        assert!(glob_match("br-*-x", "br-a-b-x"));
+        assert!(!glob_match("eth?", "eth10"));
+        assert!(!glob_match("lo", "lo0"));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_concurrent_collectors_overlap() {
+        let delay = Duration::from_millis(200);
+
+        let start = Instant::now();
+        let (sysinfo_result, file_result) = join_collectors(
+            true,
+            async move {
+                // sysinfo refreshes block the calling thread
+                std::thread::sleep(delay);
+                "sysinfo"
+            },
+            move || {
+                std::thread::sleep(delay);
+                "files"
+            },
+        ).await;
+        let elapsed = start.elapsed();
+
+        assert_eq!(sysinfo_result, "sysinfo");
+        assert_eq!(file_result.unwrap(), "files");
+        // Closer to the slowest collector (200ms) than the sum (400ms)
+        assert!(elapsed < delay * 3 / 2, "collection took {:?}", elapsed);
+    }
+
+    #[tokio::test]
+    async fn test_sequential_collectors_when_disabled() {
+        let delay = Duration::from_millis(50);
+
+        let start = Instant::now();
+        let (_, file_result) = join_collectors(
+            false,
+            async move { std::thread::sleep(delay) },
+            move || std::thread::sleep(delay),
+        ).await;
+
+        assert!(file_result.is_ok());
+        assert!(start.elapsed() >= delay * 2);
+    }
+
+    #[tokio::test]
+    #[cfg(target_os = "linux")]
+    async fn test_network_failure_reports_default_source() {
+        // net/dev exists but can't be read as a file
+        let proc_root = std::env::temp_dir().join(format!("axum-sse-proc-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(proc_root.join("net/dev")).unwrap();
+        let config = MetricsServiceConfig {
+            proc_root: proc_root.to_string_lossy().into_owned(),
+            ..MetricsServiceConfig::default()
+        };
+        let service = MetricsService::with_config(config);
+
+        match service.collect_fresh_metrics().await {
+            MetricsResponse::PartialData { data, errors } => {
+                assert_eq!(data.sources.get("network"), Some(&MetricSource::Default));
+                assert_eq!(data.sources.get("memory"), Some(&MetricSource::Sysinfo));
+                assert!(errors.iter().any(|error| matches!(error, MetricsCollectionError::NetworkError { .. })));
+            }
+            other => panic!("Expected partial data, got {:?}", other),
+        }
+        std::fs::remove_dir_all(&proc_root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_network_falls_back_to_sysinfo_without_proc() {
+        let config = MetricsServiceConfig {
+            proc_root: "/nonexistent-proc".to_string(),
+            ..MetricsServiceConfig::default()
+        };
+        let service = MetricsService::with_config(config);
+
+        let data = service.collect_fresh_metrics().await.data().expect("collection should not fail");
+        assert_eq!(data.sources.get("network"), Some(&MetricSource::Sysinfo));
+    }
+
+    #[tokio::test]
+    #[cfg(not(target_os = "linux"))]
+    async fn test_network_metrics_without_procfs() {
+        let service = MetricsService::new();
+        let response = service.collect_fresh_metrics().await;
+        assert!(!matches!(response, MetricsResponse::Error(_)));
+
+        let data = response.data().unwrap();
+        assert_eq!(data.sources.get("network"), Some(&MetricSource::Sysinfo));
+        assert_eq!(data.network_metrics.active_connections, 0);
+    }
+
+    #[tokio::test]
+    async fn test_collection_timeout() {
+        let service = MetricsService::with_config(MetricsServiceConfig {
+            collection_timeout_ms: 50,
+            ..MetricsServiceConfig::default()
+        });
+        service.delay_next_collection(Duration::from_millis(500));
+
+        match service.collect_fresh_metrics().await {
+            MetricsResponse::Error(MetricsCollectionError::Timeout { timeout_ms }) => assert_eq!(timeout_ms, 50),
+            other => panic!("Expected timeout, got {:?}", other),
+        }
+        let stats = service.get_stats().await;
+        assert_eq!(stats.failed_collections, 1);
+        assert!(matches!(stats.last_error, Some(MetricsCollectionError::Timeout { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_recent_errors_capped_newest_first() {
+        let service = MetricsService::new();
+        let failures = RECENT_ERRORS_CAPACITY as u64 + 5;
+        for timeout_ms in 0..failures {
+            service.fail_next_collection(MetricsCollectionError::timeout(timeout_ms));
+            service.collect_fresh_metrics().await;
+        }
+
+        let stats = service.get_stats().await;
+        assert_eq!(stats.failed_collections, failures);
+        assert_eq!(stats.recent_errors.len(), RECENT_ERRORS_CAPACITY);
+
+        // Newest first: the last failure leads, the five oldest were dropped
+        let timeouts: Vec<u64> = stats
+            .recent_errors
+            .iter()
+            .map(|(_, error)| match error {
+                MetricsCollectionError::Timeout { timeout_ms } => *timeout_ms,
+                other => panic!("Unexpected error {:?}", other),
+            })
+            .collect();
+        assert_eq!(timeouts, (5..failures).rev().collect::<Vec<_>>());
+        assert!(stats.recent_errors.iter().zip(stats.recent_errors.iter().skip(1)).all(|(newer, older)| newer.0 >= older.0));
+    }
+
+    #[tokio::test]
+    async fn test_collect_with_retry_recovers_from_transient_errors() {
+        let service = MetricsService::new();
+        service.fail_next_collection(MetricsCollectionError::internal("transient"));
+        service.fail_next_collection(MetricsCollectionError::internal("transient"));
+
+        assert!(service.collect_with_retry(3).await.has_data());
+        let stats = service.get_stats().await;
+        assert_eq!(stats.total_collections, 3);
+        assert_eq!(stats.failed_collections, 2);
+        assert_eq!(stats.successful_collections, 1);
+    }
+
+    #[tokio::test]
+    async fn test_collect_with_retry_does_not_retry_permission_denied() {
+        let service = MetricsService::new();
+        service.fail_next_collection(MetricsCollectionError::permission_denied("cpu"));
+        service.fail_next_collection(MetricsCollectionError::permission_denied("cpu"));
+
+        match service.collect_with_retry(3).await {
+            MetricsResponse::Error(MetricsCollectionError::PermissionDenied { resource }) => assert_eq!(resource, "cpu"),
+            other => panic!("Expected permission error, got {:?}", other),
+        }
+        let stats = service.get_stats().await;
+        assert_eq!(stats.total_collections, 1);
+    }
+
+    #[tokio::test]
+    async fn test_disk_collection() {
+        let service = MetricsService::new();
+        let data = service.collect_fresh_metrics().await.data().unwrap();
+        let disk = data.disk_metrics.expect("disk metrics are collected by default");
+        assert_eq!(data.sources.get("disk"), Some(&MetricSource::Sysinfo));
+        assert!(disk.validate().is_ok());
+        assert_eq!(disk.total_bytes, disk.mounts.iter().map(|m| m.total_bytes).sum::<u64>());
+
+        let service = MetricsService::with_config(MetricsServiceConfig {
+            collect_disk_metrics: false,
+            ..MetricsServiceConfig::default()
+        });
+        let data = service.collect_fresh_metrics().await.data().unwrap();
+        assert!(data.disk_metrics.is_none());
+        assert!(!data.sources.contains_key("disk"));
+    }
+
+    #[tokio::test]
+    async fn test_process_collection_respects_top_cap() {
+        let service = MetricsService::with_config(MetricsServiceConfig {
+            collect_processes: true,
+            max_top_processes: 3,
+            ..MetricsServiceConfig::default()
+        });
+        let data = service.collect_fresh_metrics().await.data().unwrap();
+        let processes = data.process_metrics.expect("process metrics were enabled");
+        assert_eq!(data.sources.get("process"), Some(&MetricSource::Sysinfo));
+        assert!(processes.total_processes > 0);
+        assert!(processes.running_processes <= processes.total_processes);
+        assert_eq!(processes.top.len(), 3.min(processes.total_processes as usize));
+
+        // Processes are off by default
+        let data = MetricsService::new().collect_fresh_metrics().await.data().unwrap();
+        assert!(data.process_metrics.is_none());
+        assert!(!data.sources.contains_key("process"));
+    }
+
+    #[tokio::test]
+    async fn test_gpu_collection_disabled_by_default() {
+        let service = MetricsService::new();
+        assert!(matches!(
+            service.collect_gpu_metrics().await,
+            Err(MetricsCollectionError::SystemUnavailable { .. })
+        ));
+    }
+
+    #[cfg(not(feature = "gpu"))]
+    #[tokio::test]
+    async fn test_gpu_collection_requires_feature() {
+        let config = MetricsServiceConfig {
+            collect_gpu_metrics: true,
+            ..MetricsServiceConfig::default()
+        };
+        let service = MetricsService::with_config(config);
+
+        assert!(service.collect_gpu_metrics().await.is_err());
+        match service.collect_fresh_metrics().await {
+            MetricsResponse::PartialData { data, .. } => {
+                assert!(data.gpu_metrics.is_empty());
+                assert_eq!(data.sources.get("gpu"), Some(&MetricSource::Default));
+            }
+            other => panic!("Expected partial data, got {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "gpu")]
+    #[tokio::test]
+    async fn test_gpu_collection_with_nvml() {
+        let config = MetricsServiceConfig {
+            collect_gpu_metrics: true,
+            ..MetricsServiceConfig::default()
+        };
+        let service = MetricsService::with_config(config);
+
+        // Skip on hosts without an NVIDIA driver
+        let Ok(gpus) = service.collect_gpu_metrics().await else {
+            return;
+        };
+        for (position, gpu) in gpus.iter().enumerate() {
+            assert_eq!(gpu.index as usize, position);
+            assert!(gpu.utilization_percentage <= 100.0);
+            assert!(gpu.memory_used_bytes <= gpu.memory_total_bytes);
+        }
+    }
+
+    #[test]
+    fn test_read_hwmon_fans() {
+        let root = std::env::temp_dir().join(format!("axum-sse-hwmon-{}", uuid::Uuid::new_v4()));
+        let chip = root.join("hwmon0");
+        std::fs::create_dir_all(&chip).unwrap();
+        std::fs::write(chip.join("name"), "nct6775\n").unwrap();
+        std::fs::write(chip.join("fan1_input"), "1180\n").unwrap();
+        std::fs::write(chip.join("fan1_label"), "CPU Fan\n").unwrap();
+        std::fs::write(chip.join("fan2_input"), "640\n").unwrap();
+        std::fs::write(chip.join("temp1_input"), "41000\n").unwrap();
+
+        let fans = read_hwmon_fans(root.to_str().unwrap());
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(
+            fans,
+            vec![
+                FanReading { label: "CPU Fan".to_string(), rpm: 1180 },
+                FanReading { label: "nct6775/fan2".to_string(), rpm: 640 },
+            ]
+        );
+        assert!(read_hwmon_fans("/nonexistent-hwmon").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sensor_collection_empty_without_sensors() {
+        let service = MetricsService::new();
+        assert!(service.collect_sensor_metrics().await.is_err());
+
+        let config = MetricsServiceConfig {
+            collect_sensor_metrics: true,
+            hwmon_root: "/nonexistent-hwmon".to_string(),
+            ..MetricsServiceConfig::default()
+        };
+        let service = MetricsService::with_config(config);
+
+        let sensors = service.collect_sensor_metrics().await.unwrap();
+        assert!(sensors.fans.is_empty());
+        assert!(sensors.temperatures.iter().all(|reading| reading.temperature_celsius.is_finite()));
+    }
+
+    #[tokio::test]
+    async fn test_memory_floor_skips_optional_collectors() {
+        // No host has this much free memory, so every reading is below the floor
+        let config = MetricsServiceConfig {
+            min_available_memory_bytes: u64::MAX,
+            ..MetricsServiceConfig::default()
+        };
+        let service = MetricsService::with_config(config);
+
+        match service.collect_fresh_metrics().await {
+            MetricsResponse::PartialData { data, errors } => {
+                assert_eq!(errors.len(), 1);
+                assert!(matches!(
+                    &errors[0],
+                    MetricsCollectionError::SkippedDueToMemoryPressure { collector, floor_bytes: u64::MAX, .. }
+                        if collector == "network"
+                ));
+                assert_eq!(data.sources.get("network"), Some(&MetricSource::Default));
+                assert_eq!(data.network_metrics, NetworkMetrics::default());
+                // Core collectors still run
+                assert_eq!(data.sources.get("memory"), Some(&MetricSource::Sysinfo));
+                assert!(data.memory_usage.total_bytes > 0);
+            }
+            other => panic!("Expected partial data, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_memory_floor_skips_process_collection() {
+        let config = MetricsServiceConfig {
+            min_available_memory_bytes: u64::MAX,
+            collect_processes: true,
+            ..MetricsServiceConfig::default()
+        };
+        let service = MetricsService::with_config(config);
+
+        match service.collect_fresh_metrics().await {
+            MetricsResponse::PartialData { data, errors } => {
+                assert!(errors.iter().any(|error| matches!(
+                    error,
+                    MetricsCollectionError::SkippedDueToMemoryPressure { collector, .. } if collector == "process"
+                )));
+                assert!(data.process_metrics.is_none());
+            }
+            other => panic!("Expected partial data, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_percentile_nearest_rank() {
+        let values: Vec<f64> = (1..=20).map(f64::from).collect();
+        assert_eq!(percentile(&values, 95.0), 19.0);
+        assert_eq!(percentile(&values, 50.0), 10.0);
+        assert_eq!(percentile(&values, 100.0), 20.0);
+        assert_eq!(percentile(&[], 95.0), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_benchmark_stats() {
+        let service = MetricsService::new();
+        service.initialize().await.unwrap();
+
+        let stats = service.benchmark(5).await;
+        assert_eq!(stats.iterations, 5);
+        assert!(stats.min_ms > 0.0);
+        assert!(stats.min_ms <= stats.avg_ms && stats.avg_ms <= stats.max_ms);
+        assert!(stats.min_ms <= stats.p95_ms && stats.p95_ms <= stats.max_ms);
+
+        // Benchmarking never populates the cache or collection stats
+        assert!(service.get_from_cache().await.is_none());
+        assert_eq!(service.get_stats().await.total_collections, 0);
+    }
+
+    #[test]
+    fn test_valid_interval_is_unchanged() {
+        assert_eq!(clamp_interval_seconds("interval", 5), 5);
+        assert_eq!(clamp_interval_seconds("interval", 0), minimum_interval_seconds());
+    }
 
     #[tokio::test]
     async fn test_metrics_service_creation() {
         let service = MetricsService::new();
-        assert_eq!(service.config.collection_interval_seconds, 5);
-        assert_eq!(service.config.cache_duration_seconds, 3);
+        assert_eq!(service.get_config().collection_interval_seconds, 5);
+        assert_eq!(service.get_config().cache_duration_seconds, 3);
     }
 
     #[tokio::test]
@@ -576,11 +2011,29 @@ mod tests {
             max_cache_entries: 50,
             collect_network_metrics: false,
             collect_cpu_per_core: false,
+            concurrent_collection: false,
+            memory_discrepancy_tolerance_percent: 5.0,
+            cpu_scale: CpuScale::Total,
+            interface_include: vec!["eth*".to_string()],
+            interface_exclude: Vec::new(),
+            proc_root: "/proc".to_string(),
+            collect_disk_metrics: false,
+            collect_gpu_metrics: true,
+            collect_sensor_metrics: true,
+            collect_temperature: true,
+            collect_processes: true,
+            max_top_processes: 10,
+            hwmon_root: "/sys/class/hwmon".to_string(),
+            min_available_memory_bytes: 256 * 1024 * 1024,
+            history_capacity: 12,
+            stale_threshold_seconds: 30,
         };
 
         let service = MetricsService::with_config(config.clone());
-        assert_eq!(service.config.collection_interval_seconds, 10);
-        assert!(!service.config.collect_network_metrics);
+        assert_eq!(service.get_config().collection_interval_seconds, 10);
+        assert!(!service.get_config().collect_network_metrics);
+        assert_eq!(service.history().capacity(), 12);
+        assert!(service.get_config().collect_temperature);
     }
 
     #[tokio::test]
@@ -602,6 +2055,7 @@ mod tests {
                 // Verify we got some meaningful data
                 assert!(metrics.memory_usage.total_bytes > 0);
                 assert!(metrics.cpu_usage.core_count > 0);
+                assert_eq!(metrics.cpu_usage.core_usage_percentages.len(), metrics.cpu_usage.core_count as usize);
                 assert!(!metrics.timestamp.naive_utc().format("%Y").to_string().is_empty());
             }
             MetricsResponse::Error(error) => {
@@ -611,6 +2065,19 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_per_core_usage_empty_when_disabled() {
+        let config = MetricsServiceConfig {
+            collect_cpu_per_core: false,
+            ..MetricsServiceConfig::default()
+        };
+        let service = MetricsService::with_config(config);
+        service.initialize().await.unwrap();
+
+        let metrics = service.collect_fresh_metrics().await.data().expect("Expected metrics");
+        assert!(metrics.cpu_usage.core_usage_percentages.is_empty());
+    }
+
     #[tokio::test]
     async fn test_metrics_caching() {
         let mut config = MetricsServiceConfig::default();
@@ -713,9 +2180,95 @@ mod tests {
         assert!(stats.average_collection_time_ms > 0.0);
     }
 
+    #[test]
+    fn test_average_cpu_temperature() {
+        let readings = [
+            ("coretemp Package id 0", 50.0),
+            ("coretemp Core 0", 46.0),
+            ("nvme Composite", 38.0),
+            ("acpitz temp1", f32::NAN),
+        ];
+        assert_eq!(average_cpu_temperature(readings), Some(48.0));
+        assert_eq!(average_cpu_temperature([("nvme Composite", 38.0)]), None);
+        assert_eq!(average_cpu_temperature(std::iter::empty()), None);
+    }
+
+    #[tokio::test]
+    async fn test_cpu_temperature_collection() {
+        let disabled = MetricsService::new();
+        disabled.initialize().await.unwrap();
+        let metrics = disabled.collect_fresh_metrics().await.data().unwrap();
+        assert_eq!(metrics.cpu_usage.temperature_celsius, None);
+
+        let enabled = MetricsService::with_config(MetricsServiceConfig {
+            collect_temperature: true,
+            ..Default::default()
+        });
+        enabled.initialize().await.unwrap();
+        let metrics = enabled.collect_fresh_metrics().await.data().unwrap();
+
+        // CI runners usually have no thermal sensors, which must not be an error
+        if let Some(celsius) = metrics.cpu_usage.temperature_celsius {
+            assert!(celsius.is_finite());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_config_update_rejects_out_of_range_values() {
+        let service = MetricsService::new();
+
+        let error = service
+            .update_config(MetricsServiceConfig {
+                collection_interval_seconds: 3601,
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(error.field(), "collection_interval_seconds");
+
+        let error = service
+            .update_config(MetricsServiceConfig {
+                collection_timeout_ms: 50,
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(error.field(), "collection_timeout_ms");
+
+        let error = service
+            .update_config(MetricsServiceConfig {
+                max_top_processes: usize::MAX,
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(error.field(), "max_top_processes");
+
+        let error = service
+            .update_config(MetricsServiceConfig {
+                proc_root: "/etc".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ConfigValidationError::PathOverride { field: "proc_root", .. }));
+
+        let error = service
+            .update_config(MetricsServiceConfig {
+                hwmon_root: "/".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(error.field(), "hwmon_root");
+
+        // The rejected configs left the original in place
+        assert_eq!(service.get_config().collection_interval_seconds, 5);
+    }
+
     #[tokio::test]
     async fn test_config_update() {
-        let mut service = MetricsService::new();
+        let service = MetricsService::new();
         service.initialize().await.unwrap();
 
         // Populate cache
@@ -728,10 +2281,11 @@ mod tests {
             ..Default::default()
         };
         
-        service.update_config(new_config).await;
+        let effective = service.update_config(new_config).await.unwrap();
+        assert_eq!(effective.cache_duration_seconds, 10);
         
         // Configuration should be updated and cache cleared
-        assert_eq!(service.config.collection_interval_seconds, 15);
+        assert_eq!(service.get_config().collection_interval_seconds, 15);
         
         // Next call should be cache miss due to cache clear
         let _response2 = service.get_metrics().await;
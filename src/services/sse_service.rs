@@ -3,41 +3,106 @@ use axum::{
     response::Sse,
     response::sse::{Event, KeepAlive},
 };
-use futures::stream::{self, Stream};
+use futures::stream::{self, Stream, StreamExt};
 use std::{convert::Infallible, time::Duration};
-use tokio::time::interval;
+use tokio::time::{sleep_until, Instant};
 use tokio::sync::broadcast;
-use crate::models::TimeEvent;
+use crate::models::{BroadcastEvent, TimeEvent, TimeFormat};
+use crate::services::jitter::jittered;
+use crate::services::tasks::{spawn_named, TIME_BROADCASTER_TASK};
 use uuid::Uuid;
 use tracing::{info, warn, error};
 
+/// Default time between broadcast time events
+pub const DEFAULT_BROADCAST_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Fastest accepted broadcast interval
+pub const MIN_BROADCAST_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Slowest accepted broadcast interval
+pub const MAX_BROADCAST_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Default reconnect delay advertised to time stream clients
+pub const DEFAULT_TIME_STREAM_RETRY_MS: u64 = 3000;
+
+/// Accepted range for the advertised time stream reconnect delay
+pub const TIME_STREAM_RETRY_RANGE_MS: std::ops::RangeInclusive<u64> = 500..=30_000;
+
+/// Clamp a broadcast interval to 100ms-60s, warning when it has to be adjusted
+pub fn clamp_broadcast_interval(broadcast_interval: Duration) -> Duration {
+    let clamped = broadcast_interval.clamp(MIN_BROADCAST_INTERVAL, MAX_BROADCAST_INTERVAL);
+    if clamped != broadcast_interval {
+        warn!(
+            "Time broadcast interval of {:?} is outside {:?}-{:?}, clamping to {:?}",
+            broadcast_interval, MIN_BROADCAST_INTERVAL, MAX_BROADCAST_INTERVAL, clamped
+        );
+    }
+    clamped
+}
+
+/// Message received by a time stream from either broadcast channel
+enum StreamMessage {
+    Time(TimeEvent),
+    Custom(BroadcastEvent),
+}
+
 /// SSE connection manager for handling multiple client connections
 #[derive(Clone)]
 pub struct SseService {
     /// Broadcast channel for sending time events to all connected clients
     time_sender: broadcast::Sender<TimeEvent>,
+    /// Broadcast channel for operator messages to all connected clients
+    custom_sender: broadcast::Sender<BroadcastEvent>,
+    /// Time between broadcast time events
+    broadcast_interval: Duration,
+    /// Random ± percentage applied to each broadcast interval
+    broadcast_jitter_percent: f64,
 }
 
 impl SseService {
-    /// Create a new SSE service
+    /// Create a new SSE service broadcasting every 10 seconds
     pub fn new() -> Self {
+        Self::with_broadcast_interval(DEFAULT_BROADCAST_INTERVAL)
+    }
+
+    /// Create a new SSE service broadcasting every `broadcast_interval`, clamped to 100ms-60s
+    pub fn with_broadcast_interval(broadcast_interval: Duration) -> Self {
         // Create broadcast channel with buffer for disconnected clients
         let (time_sender, _) = broadcast::channel(100);
+        let (custom_sender, _) = broadcast::channel(16);
         
         Self {
             time_sender,
+            custom_sender,
+            broadcast_interval: clamp_broadcast_interval(broadcast_interval),
+            broadcast_jitter_percent: 0.0,
         }
     }
 
+    /// Randomly lengthen or shorten each broadcast interval by up to `percent` (capped at 50)
+    pub fn with_broadcast_jitter(mut self, percent: f64) -> Self {
+        self.broadcast_jitter_percent = percent;
+        self
+    }
+
+    /// Time between broadcast time events
+    pub fn broadcast_interval(&self) -> Duration {
+        self.broadcast_interval
+    }
+
     /// Start the time broadcasting background task
     pub fn start_time_broadcaster(&self) {
         let sender = self.time_sender.clone();
+        let broadcast_interval = self.broadcast_interval;
+        let jitter_percent = self.broadcast_jitter_percent;
         
-        tokio::spawn(async move {
-            let mut interval = interval(Duration::from_secs(10));
+        spawn_named(TIME_BROADCASTER_TASK, async move {
+            // The first event goes out straight away, like an interval's first tick
+            let mut next_broadcast = Instant::now();
             
             loop {
-                interval.tick().await;
+                sleep_until(next_broadcast).await;
+                next_broadcast = Instant::now() + jittered(broadcast_interval, jitter_percent);
                 
                 let time_event = TimeEvent::new();
                 info!("Broadcasting time event: {}", time_event.formatted_time);
@@ -56,17 +121,37 @@ impl SseService {
     }
 
     /// Create an SSE stream for a new client connection
-    pub fn create_time_stream(&self) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    ///
+    /// With a `time_format`, each event's `formatted_time` is re-rendered in
+    /// that timezone and pattern; otherwise the broadcast UK format is sent.
+    /// With a `retry`, the stream opens with an event carrying only that
+    /// reconnect delay, which EventSource applies to later reconnects.
+    pub fn create_time_stream(
+        &self,
+        time_format: Option<TimeFormat>,
+        retry: Option<Duration>,
+    ) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
         let connection_id = Uuid::new_v4().to_string();
         let receiver = self.time_sender.subscribe();
+        let custom_receiver = self.custom_sender.subscribe();
         
         info!("New SSE connection: {}", connection_id);
         
         let stream = stream::unfold(
-            (receiver, connection_id.clone()),
-            |(mut rx, conn_id)| async move {
-                match rx.recv().await {
-                    Ok(time_event) => {
+            (receiver, custom_receiver, connection_id.clone(), time_format),
+            |(mut rx, mut custom_rx, conn_id, time_format)| async move {
+                let message = tokio::select! {
+                    time_event = rx.recv() => time_event.map(StreamMessage::Time),
+                    custom_event = custom_rx.recv() => custom_event.map(StreamMessage::Custom),
+                };
+
+                match message {
+                    Ok(StreamMessage::Time(time_event)) => {
+                        let time_event = match &time_format {
+                            Some(time_format) => time_format.apply(time_event),
+                            None => time_event,
+                        };
+
                         // Create SSE event with the time data
                         let event_data = match serde_json::to_string(&time_event) {
                             Ok(json) => json,
@@ -81,7 +166,23 @@ impl SseService {
                             .id(&conn_id)
                             .data(event_data);
                         
-                        Some((Ok(event), (rx, conn_id)))
+                        Some((Ok(event), (rx, custom_rx, conn_id, time_format)))
+                    }
+                    Ok(StreamMessage::Custom(custom_event)) => {
+                        let event_data = match serde_json::to_string(&custom_event) {
+                            Ok(json) => json,
+                            Err(e) => {
+                                error!("Failed to serialize broadcast event: {}", e);
+                                return None;
+                            }
+                        };
+
+                        let event = Event::default()
+                            .event(&custom_event.event_type)
+                            .id(&conn_id)
+                            .data(event_data);
+
+                        Some((Ok(event), (rx, custom_rx, conn_id, time_format)))
                     }
                     Err(broadcast::error::RecvError::Closed) => {
                         info!("SSE connection {} closed: channel closed", conn_id);
@@ -95,16 +196,35 @@ impl SseService {
                             .id(&conn_id)
                             .data(format!("{{\"missed_events\": {}}}", missed));
                         
-                        Some((Ok(event), (rx, conn_id)))
+                        Some((Ok(event), (rx, custom_rx, conn_id, time_format)))
                     }
                 }
             },
         );
         
-        Sse::new(stream)
+        let retry_hint = stream::iter(retry.map(|retry| Ok(Event::default().retry(retry))));
+
+        Sse::new(retry_hint.chain(stream))
             .keep_alive(KeepAlive::default().interval(Duration::from_secs(30)))
     }
 
+    /// Send a time event for the current time right away, returning how many streams received it
+    pub fn broadcast_time_now(&self) -> usize {
+        self.time_sender.send(TimeEvent::new()).unwrap_or(0)
+    }
+
+    /// Send an operator message to every connected stream, returning how many received it
+    pub fn broadcast_custom_event(&self, event: BroadcastEvent) -> usize {
+        info!("Broadcasting {} event: {} bytes", event.event_type, event.message.len());
+        self.custom_sender.send(event).unwrap_or(0)
+    }
+
+    /// Send a time event to every connected stream
+    #[cfg(test)]
+    pub(crate) fn send_time_event(&self, event: TimeEvent) {
+        let _ = self.time_sender.send(event);
+    }
+
     /// Get the number of current receivers (approximate active connections)
     pub fn receiver_count(&self) -> usize {
         self.time_sender.receiver_count()
@@ -156,6 +276,34 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_configured_broadcast_interval() {
+        let service = SseService::with_broadcast_interval(Duration::from_millis(200));
+        let mut receiver = service.time_sender.subscribe();
+        service.start_time_broadcaster();
+
+        // The first tick fires immediately, the second 200ms later
+        let ticks = timeout(Duration::from_secs(1), async {
+            receiver.recv().await.unwrap();
+            receiver.recv().await.unwrap();
+        })
+        .await;
+        assert!(ticks.is_ok(), "expected two time events within a second");
+    }
+
+    #[test]
+    fn test_broadcast_interval_clamped() {
+        assert_eq!(SseService::new().broadcast_interval(), DEFAULT_BROADCAST_INTERVAL);
+        assert_eq!(
+            SseService::with_broadcast_interval(Duration::from_millis(10)).broadcast_interval(),
+            MIN_BROADCAST_INTERVAL
+        );
+        assert_eq!(
+            SseService::with_broadcast_interval(Duration::from_secs(3600)).broadcast_interval(),
+            MAX_BROADCAST_INTERVAL
+        );
+    }
+
     #[tokio::test]
     async fn test_multiple_receivers() {
         let service = SseService::new();
@@ -1,39 +1,142 @@
 // Static asset serving service for embedded SvelteKit build
 use axum::{
-    response::{Html, Response},
-    http::{StatusCode, HeaderMap, HeaderValue},
+    response::{Html, IntoResponse, Json, Response},
+    http::{header, StatusCode, HeaderMap, HeaderValue},
     body::Body,
 };
 use include_dir::{include_dir, Dir};
+use serde_json::json;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 use std::path::Path;
-use tracing::{info, warn, debug};
+use std::sync::{Arc, OnceLock};
+use tracing::{debug, error, info, warn};
 
 // Embed the frontend build directory at compile time
 // Note: The build directory will be created during the frontend build process
 static FRONTEND_DIR: Dir<'_> = include_dir!("frontend/build");
 
+/// Index file served when no other is configured, or the configured one is missing
+const DEFAULT_INDEX_FILE: &str = "index.html";
+
+/// Pre-compressed sibling files, in order of preference: (`Content-Encoding`, file suffix)
+const COMPRESSED_VARIANTS: [(&str, &str); 2] = [("br", ".br"), ("gzip", ".gz")];
+
+/// Reasons a static asset or page could not be served
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum StaticServiceError {
+    /// No embedded file at the path
+    #[error("asset not found: {0}")]
+    NotFound(String),
+    /// The path tried to leave the asset directory
+    #[error("path traversal blocked: {0}")]
+    TraversalBlocked(String),
+    /// The embedded index page is not valid UTF-8
+    #[error("asset could not be decoded: {0}")]
+    DecodeFailed(String),
+    /// Nothing was embedded because the frontend was not built before compiling
+    #[error("no frontend assets embedded; run `npm run build` in frontend/ and rebuild")]
+    MissingAssets,
+}
+
+impl StaticServiceError {
+    /// Status code and machine-readable error code
+    fn status_and_code(&self) -> (StatusCode, &'static str) {
+        match self {
+            Self::NotFound(_) => (StatusCode::NOT_FOUND, "not_found"),
+            Self::TraversalBlocked(_) => (StatusCode::BAD_REQUEST, "traversal_blocked"),
+            Self::DecodeFailed(_) => (StatusCode::INTERNAL_SERVER_ERROR, "decode_failed"),
+            Self::MissingAssets => (StatusCode::SERVICE_UNAVAILABLE, "missing_assets"),
+        }
+    }
+}
+
+impl IntoResponse for StaticServiceError {
+    fn into_response(self) -> Response {
+        let (status, code) = self.status_and_code();
+        (
+            status,
+            Json(json!({
+                "error": code,
+                "message": self.to_string(),
+                "timestamp": chrono::Utc::now(),
+            })),
+        )
+            .into_response()
+    }
+}
+
 /// Static asset serving service for embedded frontend
 #[derive(Clone)]
 pub struct StaticService {
     /// Default index file name
     index_file: String,
+    /// Embedded directory assets are served from
+    assets: &'static Dir<'static>,
+    /// ETag of every embedded file keyed by path, computed on first use
+    ///
+    /// Embedded content cannot change after compilation, so the hashes never go stale.
+    etags: Arc<OnceLock<HashMap<String, String>>>,
 }
 
 impl StaticService {
     /// Create a new static service
     pub fn new() -> Self {
         Self {
-            index_file: "index.html".to_string(),
+            index_file: DEFAULT_INDEX_FILE.to_string(),
+            assets: &FRONTEND_DIR,
+            etags: Arc::new(OnceLock::new()),
+        }
+    }
+
+    /// Create a static service using the `INDEX_FILE` environment variable, if set
+    pub fn from_env() -> Self {
+        match std::env::var("INDEX_FILE") {
+            Ok(name) if !name.trim().is_empty() => Self::new().with_index_file(name.trim()),
+            _ => Self::new(),
+        }
+    }
+
+    /// Serve a different embedded file as the index, e.g. `index.staging.html`
+    ///
+    /// Falls back to `index.html` if the file is not embedded.
+    pub fn with_index_file(mut self, name: impl Into<String>) -> Self {
+        match self.sanitize_path(&name.into()) {
+            Ok(index_file) => self.index_file = index_file,
+            Err(error) => warn!("Ignoring index file: {}", error),
         }
+        self
+    }
+
+    /// Serve assets from a different embedded directory
+    #[cfg(test)]
+    pub(crate) fn with_assets(mut self, assets: &'static Dir<'static>) -> Self {
+        self.assets = assets;
+        self.etags = Arc::new(OnceLock::new());
+        self
     }
 
     /// Serve the main page (index.html)
-    pub async fn serve_index(&self) -> Result<Html<String>, StatusCode> {
+    ///
+    /// A missing index is replaced by a built-in fallback page; an index
+    /// that is embedded but not UTF-8 is an error.
+    pub async fn serve_index(&self) -> Result<Html<String>, StaticServiceError> {
         debug!("Serving index page");
         
-        match self.get_file_content(&self.index_file) {
+        let content = match self.get_text_content(&self.index_file)? {
+            Some(content) => Some(content),
+            None => {
+                if self.index_file != DEFAULT_INDEX_FILE {
+                    warn!("{} not found in embedded assets, falling back to {}", self.index_file, DEFAULT_INDEX_FILE);
+                }
+                self.get_text_content(DEFAULT_INDEX_FILE)?
+            }
+        };
+
+        match content {
             Some(content) => {
-                info!("Successfully served index.html ({} bytes)", content.len());
+                info!("Successfully served index page ({} bytes)", content.len());
                 Ok(Html(content))
             }
             None => {
@@ -46,70 +149,297 @@ impl StaticService {
     }
 
     /// Serve a static asset by path
-    pub async fn serve_asset(&self, path: &str) -> Result<Response<Body>, StatusCode> {
+    ///
+    /// Responses carry an `ETag`; a matching `If-None-Match` in `request_headers`
+    /// gets `304 Not Modified` with an empty body. When `Accept-Encoding` allows
+    /// it and a `.br`/`.gz` sibling is embedded, the compressed variant is served.
+    pub async fn serve_asset(&self, path: &str, request_headers: &HeaderMap) -> Result<Response<Body>, StaticServiceError> {
         debug!("Serving static asset: {}", path);
         
         // Clean the path to prevent directory traversal
-        let clean_path = self.sanitize_path(path);
+        let clean_path = self.sanitize_path(path)?;
+
+        let Some(content) = self.get_file_content(&clean_path) else {
+            warn!("Static asset not found: {}", clean_path);
+            return Err(StaticServiceError::NotFound(clean_path));
+        };
+
+        let mut headers = HeaderMap::new();
         
-        match self.get_file_content(&clean_path) {
-            Some(content) => {
-                let mut headers = HeaderMap::new();
-                
-                // Set content type based on file extension
-                if let Some(content_type) = self.get_content_type(&clean_path) {
-                    headers.insert("content-type", HeaderValue::from_static(content_type));
+        // Set content type based on the original file's extension, also for compressed variants
+        if let Some(content_type) = self.get_content_type(&clean_path) {
+            headers.insert("content-type", HeaderValue::from_static(content_type));
+        }
+        
+        // Set cache headers for static assets
+        headers.insert("cache-control", HeaderValue::from_static("public, max-age=3600"));
+        headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+
+        let variant = self.compressed_variant(&clean_path, request_headers);
+        if variant.is_some() || self.has_compressed_variant(&clean_path) {
+            headers.insert(header::VARY, HeaderValue::from_static("accept-encoding"));
+        }
+        let served_path = match variant {
+            Some((variant_path, encoding)) => {
+                headers.insert(header::CONTENT_ENCODING, HeaderValue::from_static(encoding));
+                variant_path
+            }
+            None => clean_path.clone(),
+        };
+
+        if let Some(etag) = self.etag(&served_path) {
+            if let Ok(value) = HeaderValue::from_str(etag) {
+                headers.insert(header::ETAG, value);
+            }
+
+            if Self::if_none_match(request_headers, etag) {
+                debug!("Asset {} not modified", served_path);
+                headers.remove(header::CONTENT_TYPE);
+                let mut response = Response::new(Body::empty());
+                *response.status_mut() = StatusCode::NOT_MODIFIED;
+                *response.headers_mut() = headers;
+                return Ok(response);
+            }
+        }
+
+        let body = if served_path != clean_path {
+            self.get_file_content(&served_path).unwrap_or(content)
+        } else {
+            content
+        };
+
+        match Self::requested_range(request_headers, body.len()) {
+            Some(Ok((start, end))) => {
+                debug!("Serving bytes {}-{} of asset {}", start, end, served_path);
+                if let Ok(value) = HeaderValue::from_str(&format!("bytes {}-{}/{}", start, end, body.len())) {
+                    headers.insert(header::CONTENT_RANGE, value);
                 }
-                
-                // Set cache headers for static assets
-                headers.insert("cache-control", HeaderValue::from_static("public, max-age=3600"));
-                
-                info!("Successfully served asset {} ({} bytes)", clean_path, content.len());
-                
-                let mut response = Response::new(Body::from(content));
+                let mut response = Response::new(Body::from(&body[start..=end]));
+                *response.status_mut() = StatusCode::PARTIAL_CONTENT;
+                *response.headers_mut() = headers;
+                Ok(response)
+            }
+            Some(Err(())) => {
+                warn!("Unsatisfiable range requested for asset {} ({} bytes)", served_path, body.len());
+                if let Ok(value) = HeaderValue::from_str(&format!("bytes */{}", body.len())) {
+                    headers.insert(header::CONTENT_RANGE, value);
+                }
+                headers.remove(header::CONTENT_TYPE);
+                let mut response = Response::new(Body::empty());
+                *response.status_mut() = StatusCode::RANGE_NOT_SATISFIABLE;
                 *response.headers_mut() = headers;
                 Ok(response)
             }
             None => {
-                warn!("Static asset not found: {}", clean_path);
-                Err(StatusCode::NOT_FOUND)
+                info!("Successfully served asset {} ({} bytes)", served_path, body.len());
+
+                let mut response = Response::new(Body::from(body));
+                *response.headers_mut() = headers;
+                Ok(response)
+            }
+        }
+    }
+
+    /// Startup check that the frontend build was embedded, returning the asset count
+    ///
+    /// An empty build is logged loudly; with `strict` it is an error.
+    pub fn check_assets(&self, strict: bool) -> Result<usize, StaticServiceError> {
+        let count = self.asset_count();
+        if count == 0 {
+            if strict {
+                error!("No frontend assets embedded and STRICT_ASSETS is set, refusing to start");
+                return Err(StaticServiceError::MissingAssets);
             }
+            warn!("⚠️  No frontend assets embedded: every page and asset will fail until frontend/ is built and the server recompiled");
         }
+        Ok(count)
+    }
+
+    /// Page explaining that the frontend build is missing, served instead of the app
+    pub fn missing_assets_page(&self) -> String {
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <title>Frontend not built</title>
+    <style>
+        body { font-family: Arial, sans-serif; max-width: 800px; margin: 0 auto; padding: 20px; }
+        code { background-color: #eee; padding: 2px 4px; }
+    </style>
+</head>
+<body>
+    <h1>Frontend not built</h1>
+    <p>This server was compiled without any frontend assets, so the web interface is unavailable.
+    The API under <code>/api</code> still works.</p>
+    <p>To fix it, build the frontend and recompile the server:</p>
+    <pre><code>cd frontend &amp;&amp; npm install &amp;&amp; npm run build
+cargo build</code></pre>
+    <p>Set <code>STRICT_ASSETS=1</code> to make the server refuse to start in this state.</p>
+</body>
+</html>"#.to_string()
     }
 
     /// Check if the static service is healthy (has embedded assets)
     pub fn is_healthy(&self) -> bool {
-        !FRONTEND_DIR.entries().is_empty()
+        !self.assets.entries().is_empty()
     }
 
     /// Get the number of embedded files
     pub fn asset_count(&self) -> usize {
-        self.count_files_recursive(&FRONTEND_DIR)
+        self.count_files_recursive(self.assets)
     }
 
     /// List all available assets (for debugging)
     pub fn list_assets(&self) -> Vec<String> {
-        self.list_files_recursive(&FRONTEND_DIR, "")
+        self.list_files_recursive(self.assets, "")
     }
 
     // Private helper methods
 
-    fn get_file_content(&self, path: &str) -> Option<String> {
-        FRONTEND_DIR.get_file(path)
-            .and_then(|file| file.contents_utf8())
-            .map(|content| content.to_string())
+    /// Raw bytes of the embedded file at `path`
+    fn get_file_content(&self, path: &str) -> Option<&'static [u8]> {
+        self.assets.get_file(path).map(|file| file.contents())
+    }
+
+    /// Text of the embedded page at `path`, or `None` if there is none
+    fn get_text_content(&self, path: &str) -> Result<Option<String>, StaticServiceError> {
+        let Some(content) = self.get_file_content(path) else {
+            return Ok(None);
+        };
+
+        match std::str::from_utf8(content) {
+            Ok(content) => Ok(Some(content.to_string())),
+            Err(_) => {
+                warn!("Embedded page {} is not valid UTF-8", path);
+                Err(StaticServiceError::DecodeFailed(path.to_string()))
+            }
+        }
     }
 
-    fn sanitize_path(&self, path: &str) -> String {
+    /// Embedded pre-compressed sibling of `path` the client accepts, with its `Content-Encoding`
+    ///
+    /// Brotli is preferred over gzip when both are acceptable.
+    fn compressed_variant(&self, path: &str, request_headers: &HeaderMap) -> Option<(String, &'static str)> {
+        COMPRESSED_VARIANTS.iter().find_map(|&(encoding, suffix)| {
+            let variant = format!("{}{}", path, suffix);
+            (Self::accepts_encoding(request_headers, encoding) && self.assets.get_file(&variant).is_some())
+                .then_some((variant, encoding))
+        })
+    }
+
+    fn has_compressed_variant(&self, path: &str) -> bool {
+        COMPRESSED_VARIANTS
+            .iter()
+            .any(|(_, suffix)| self.assets.get_file(format!("{}{}", path, suffix)).is_some())
+    }
+
+    /// Whether `Accept-Encoding` lists `encoding` (or `*`) with a non-zero quality
+    fn accepts_encoding(request_headers: &HeaderMap, encoding: &str) -> bool {
+        request_headers
+            .get_all(header::ACCEPT_ENCODING)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .any(|coding| {
+                let mut parts = coding.split(';').map(str::trim);
+                let name = parts.next().unwrap_or_default();
+                let quality = parts
+                    .find_map(|param| param.strip_prefix("q="))
+                    .and_then(|q| q.parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                (name.eq_ignore_ascii_case(encoding) || name == "*") && quality > 0.0
+            })
+    }
+
+    /// Inclusive byte range requested by a single-range `Range` header
+    ///
+    /// Returns `None` when the full file should be served (no header, a
+    /// malformed one, or several ranges) and `Some(Err(()))` when the range
+    /// lies outside a file of `len` bytes.
+    fn requested_range(request_headers: &HeaderMap, len: usize) -> Option<Result<(usize, usize), ()>> {
+        let spec = request_headers.get(header::RANGE)?.to_str().ok()?.trim().strip_prefix("bytes=")?;
+        if spec.contains(',') {
+            debug!("Multiple ranges requested, serving the full asset");
+            return None;
+        }
+
+        let (start, end) = spec.split_once('-')?;
+        let (start, end) = (start.trim(), end.trim());
+        let last = len.checked_sub(1);
+
+        let range = if start.is_empty() {
+            // Suffix range: the final `end` bytes
+            let suffix: usize = end.parse().ok()?;
+            match last {
+                Some(last) if suffix > 0 => Ok((len.saturating_sub(suffix), last)),
+                _ => Err(()),
+            }
+        } else {
+            let start: usize = start.parse().ok()?;
+            let end: Option<usize> = if end.is_empty() { None } else { Some(end.parse().ok()?) };
+            if end.is_some_and(|end| end < start) {
+                return None;
+            }
+            match last {
+                Some(last) if start <= last => Ok((start, end.map_or(last, |end| end.min(last)))),
+                _ => Err(()),
+            }
+        };
+
+        Some(range)
+    }
+
+    /// Cached ETag for an embedded file
+    fn etag(&self, path: &str) -> Option<&str> {
+        self.etags
+            .get_or_init(|| {
+                let etags: HashMap<String, String> = self
+                    .list_assets()
+                    .into_iter()
+                    .filter_map(|path| {
+                        let contents = self.assets.get_file(&path)?.contents();
+                        let etag = Self::compute_etag(contents);
+                        Some((path, etag))
+                    })
+                    .collect();
+                debug!("Computed ETags for {} embedded assets", etags.len());
+                etags
+            })
+            .get(path)
+            .map(String::as_str)
+    }
+
+    /// Strong ETag from a hash of the file bytes and their length
+    fn compute_etag(contents: &[u8]) -> String {
+        let mut hasher = DefaultHasher::new();
+        contents.hash(&mut hasher);
+        format!("\"{:016x}-{:x}\"", hasher.finish(), contents.len())
+    }
+
+    /// Whether any `If-None-Match` entity tag matches `etag`
+    ///
+    /// Uses the weak comparison RFC 9110 prescribes for `If-None-Match`.
+    fn if_none_match(request_headers: &HeaderMap, etag: &str) -> bool {
+        request_headers
+            .get_all(header::IF_NONE_MATCH)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(|tag| tag.trim())
+            .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+    }
+
+    fn sanitize_path(&self, path: &str) -> Result<String, StaticServiceError> {
         // Remove leading slash and resolve any relative path components
         let clean = path.trim_start_matches('/');
         
         // Basic sanitization - in production you'd want more robust path validation
         if clean.contains("..") || clean.contains('\0') {
-            return "404.html".to_string(); // Safe fallback
+            warn!("Blocked path traversal attempt: {:?}", path);
+            return Err(StaticServiceError::TraversalBlocked(path.to_string()));
         }
         
-        clean.to_string()
+        Ok(clean.to_string())
     }
 
     fn get_content_type(&self, path: &str) -> Option<&'static str> {
@@ -257,6 +587,42 @@ mod tests {
         assert_eq!(service.index_file, "index.html");
     }
 
+    static FIXTURE_DIR: Dir<'_> = include_dir!("tests/fixtures/static");
+
+    /// What `include_dir!` embeds when the frontend was never built
+    static EMPTY_DIR: Dir<'_> = Dir::new("", &[]);
+
+    #[test]
+    fn test_check_assets_strict_refuses_empty_build() {
+        let service = StaticService::new().with_assets(&EMPTY_DIR);
+        assert_eq!(service.asset_count(), 0);
+        assert_eq!(service.check_assets(true), Err(StaticServiceError::MissingAssets));
+        assert_eq!(service.check_assets(false), Ok(0));
+
+        let service = StaticService::new().with_assets(&FIXTURE_DIR);
+        assert_eq!(service.check_assets(true), Ok(service.asset_count()));
+    }
+
+    #[tokio::test]
+    async fn test_alternate_index_file() {
+        let service = StaticService::new()
+            .with_assets(&FIXTURE_DIR)
+            .with_index_file("index.staging.html");
+
+        let index_html = service.serve_index().await.unwrap().0;
+        assert!(index_html.contains("STAGING"));
+    }
+
+    #[tokio::test]
+    async fn test_missing_index_file_falls_back() {
+        let service = StaticService::new()
+            .with_assets(&FIXTURE_DIR)
+            .with_index_file("index.missing.html");
+
+        let index_html = service.serve_index().await.unwrap().0;
+        assert!(index_html.contains("default index"));
+    }
+
     #[tokio::test]
     async fn test_fallback_pages() {
         let service = StaticService::new();
@@ -269,13 +635,209 @@ mod tests {
         assert!(index_html.contains("html") || index_html.contains("HTML"));
     }
 
+    #[tokio::test]
+    async fn test_asset_etag() {
+        let service = StaticService::new().with_assets(&FIXTURE_DIR);
+
+        let response = service.serve_asset("/index.html", &HeaderMap::new()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let etag = response.headers().get(header::ETAG).expect("asset response has an ETag").clone();
+        assert!(etag.to_str().unwrap().starts_with('"'));
+
+        // Stable across requests and distinct per file
+        let again = service.serve_asset("index.html", &HeaderMap::new()).await.unwrap();
+        assert_eq!(again.headers().get(header::ETAG), Some(&etag));
+        let other = service.serve_asset("index.staging.html", &HeaderMap::new()).await.unwrap();
+        assert_ne!(other.headers().get(header::ETAG), Some(&etag));
+    }
+
+    #[tokio::test]
+    async fn test_asset_not_modified() {
+        let service = StaticService::new().with_assets(&FIXTURE_DIR);
+        let etag = service
+            .serve_asset("index.html", &HeaderMap::new())
+            .await
+            .unwrap()
+            .headers()
+            .get(header::ETAG)
+            .unwrap()
+            .clone();
+
+        let mut request_headers = HeaderMap::new();
+        request_headers.insert(header::IF_NONE_MATCH, etag.clone());
+        let response = service.serve_asset("index.html", &request_headers).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers().get(header::ETAG), Some(&etag));
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(body.is_empty());
+
+        // Weak and list forms match too; a different tag does not
+        let listed = format!("\"other\", W/{}", etag.to_str().unwrap());
+        request_headers.insert(header::IF_NONE_MATCH, HeaderValue::from_str(&listed).unwrap());
+        let response = service.serve_asset("index.html", &request_headers).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+        request_headers.insert(header::IF_NONE_MATCH, HeaderValue::from_static("\"stale\""));
+        let response = service.serve_asset("index.html", &request_headers).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_gzip_variant_served() {
+        let service = StaticService::new().with_assets(&FIXTURE_DIR);
+        let mut request_headers = HeaderMap::new();
+        request_headers.insert(header::ACCEPT_ENCODING, HeaderValue::from_static("br;q=0, gzip, deflate"));
+
+        let response = service.serve_asset("app.js", &request_headers).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_ENCODING], "gzip");
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/javascript");
+        assert_eq!(response.headers()[header::VARY], "accept-encoding");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], include_bytes!("../../tests/fixtures/static/app.js.gz"));
+    }
+
+    #[tokio::test]
+    async fn test_plain_asset_without_accept_encoding() {
+        let service = StaticService::new().with_assets(&FIXTURE_DIR);
+
+        let response = service.serve_asset("app.js", &HeaderMap::new()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/javascript");
+        assert_eq!(response.headers()[header::VARY], "accept-encoding");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], include_bytes!("../../tests/fixtures/static/app.js"));
+
+        // No variant exists for index.html, so there is nothing to vary on
+        let response = service.serve_asset("index.html", &HeaderMap::new()).await.unwrap();
+        assert!(response.headers().get(header::VARY).is_none());
+    }
+
+    async fn request_range(service: &StaticService, range: &'static str) -> Response<Body> {
+        let mut request_headers = HeaderMap::new();
+        request_headers.insert(header::RANGE, HeaderValue::from_static(range));
+        service.serve_asset("app.js", &request_headers).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_range_request() {
+        let service = StaticService::new().with_assets(&FIXTURE_DIR);
+        let file = include_bytes!("../../tests/fixtures/static/app.js");
+
+        let response = request_range(&service, "bytes=0-6").await;
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(response.headers()[header::CONTENT_RANGE], format!("bytes 0-6/{}", file.len()));
+        assert_eq!(response.headers()[header::ACCEPT_RANGES], "bytes");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"console");
+
+        // An end past the file is clamped to the last byte
+        let response = request_range(&service, "bytes=8-9999").await;
+        assert_eq!(response.headers()[header::CONTENT_RANGE], format!("bytes 8-{}/{}", file.len() - 1, file.len()));
+    }
+
+    #[tokio::test]
+    async fn test_open_ended_range_request() {
+        let service = StaticService::new().with_assets(&FIXTURE_DIR);
+        let file = include_bytes!("../../tests/fixtures/static/app.js");
+
+        let response = request_range(&service, "bytes=8-").await;
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(response.headers()[header::CONTENT_RANGE], format!("bytes 8-{}/{}", file.len() - 1, file.len()));
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], &file[8..]);
+
+        let response = request_range(&service, "bytes=-3").await;
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], &file[file.len() - 3..]);
+    }
+
+    #[tokio::test]
+    async fn test_unsatisfiable_range_request() {
+        let service = StaticService::new().with_assets(&FIXTURE_DIR);
+        let file = include_bytes!("../../tests/fixtures/static/app.js");
+
+        let response = request_range(&service, "bytes=1000-2000").await;
+        assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+        assert_eq!(response.headers()[header::CONTENT_RANGE], format!("bytes */{}", file.len()));
+
+        // Multiple ranges are not supported and fall back to the full file
+        let response = request_range(&service, "bytes=0-1, 4-5").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], file);
+    }
+
+    #[tokio::test]
+    async fn test_binary_asset_served() {
+        let service = StaticService::new().with_assets(&FIXTURE_DIR);
+        let file = include_bytes!("../../tests/fixtures/static/favicon.png");
+
+        let response = service.serve_asset("/favicon.png", &HeaderMap::new()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "image/png");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body.len(), file.len());
+        assert_eq!(&body[..], file);
+    }
+
     #[test]
     fn test_path_sanitization() {
         let service = StaticService::new();
         
-        assert_eq!(service.sanitize_path("/normal/path.html"), "normal/path.html");
-        assert_eq!(service.sanitize_path("../../../etc/passwd"), "404.html");
-        assert_eq!(service.sanitize_path("path/with/../traversal"), "404.html");
+        assert_eq!(service.sanitize_path("/normal/path.html"), Ok("normal/path.html".to_string()));
+        assert!(matches!(
+            service.sanitize_path("../../../etc/passwd"),
+            Err(StaticServiceError::TraversalBlocked(_))
+        ));
+        assert!(matches!(
+            service.sanitize_path("path/with/../traversal"),
+            Err(StaticServiceError::TraversalBlocked(_))
+        ));
+    }
+
+    async fn error_body(error: StaticServiceError) -> (StatusCode, serde_json::Value) {
+        let response = error.into_response();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_asset_not_found_error() {
+        let service = StaticService::new().with_assets(&FIXTURE_DIR);
+        let error = service.serve_asset("/missing.css", &HeaderMap::new()).await.unwrap_err();
+        assert_eq!(error, StaticServiceError::NotFound("missing.css".to_string()));
+
+        let (status, body) = error_body(error).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["error"], "not_found");
+    }
+
+    #[tokio::test]
+    async fn test_traversal_blocked_error() {
+        let service = StaticService::new().with_assets(&FIXTURE_DIR);
+        let error = service.serve_asset("/../Cargo.toml", &HeaderMap::new()).await.unwrap_err();
+        assert!(matches!(error, StaticServiceError::TraversalBlocked(_)));
+
+        let (status, body) = error_body(error).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "traversal_blocked");
+    }
+
+    #[tokio::test]
+    async fn test_decode_failed_error() {
+        let service = StaticService::new()
+            .with_assets(&FIXTURE_DIR)
+            .with_index_file("favicon.png");
+        let error = service.serve_index().await.unwrap_err();
+        assert_eq!(error, StaticServiceError::DecodeFailed("favicon.png".to_string()));
+
+        let (status, body) = error_body(error).await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body["error"], "decode_failed");
     }
 
     #[test]
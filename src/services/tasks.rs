@@ -0,0 +1,142 @@
+// Named task spawning
+// Gives background tasks stable names for tokio-console and stack traces
+
+use std::future::Future;
+use tokio::task::JoinHandle;
+
+/// Task name for the metrics cache background refresh loop
+pub const METRICS_CACHE_REFRESH_TASK: &str = "metrics-cache-refresh";
+/// Task name for the file-based metrics collectors
+pub const FILE_COLLECTORS_TASK: &str = "metrics-file-collectors";
+/// Task name for the blocking sysinfo refresh within a collection
+pub const SYSINFO_REFRESH_TASK: &str = "metrics-sysinfo-refresh";
+/// Task name for on-demand GPU metrics collection
+pub const GPU_COLLECTOR_TASK: &str = "metrics-gpu-collector";
+/// Task name for on-demand sensor collection
+pub const SENSOR_COLLECTOR_TASK: &str = "metrics-sensor-collector";
+/// Task name for the first collection kicked off by a warming-up status request
+pub const METRICS_WARMUP_TASK: &str = "metrics-warmup";
+/// Task name for the SSE time broadcaster
+pub const TIME_BROADCASTER_TASK: &str = "sse-time-broadcaster";
+
+/// Default prefix for runtime worker and blocking threads
+const DEFAULT_THREAD_NAME: &str = "axum-sse-worker";
+
+/// Name for runtime threads, configurable via `THREAD_NAME`
+pub fn runtime_thread_name() -> String {
+    std::env::var("THREAD_NAME")
+        .ok()
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_THREAD_NAME.to_string())
+}
+
+/// Spawn a future as a named task
+///
+/// Names are only attached when built with `--cfg tokio_unstable`, which is
+/// also what tokio-console requires; otherwise this is a plain `tokio::spawn`.
+pub fn spawn_named<F>(name: &str, future: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    #[cfg(test)]
+    record_spawn(name);
+
+    spawn_with_name(name, future)
+}
+
+/// Run a blocking closure on a named blocking task
+pub fn spawn_blocking_named<F, R>(name: &str, f: F) -> JoinHandle<R>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    #[cfg(test)]
+    record_spawn(name);
+
+    spawn_blocking_with_name(name, f)
+}
+
+#[cfg(tokio_unstable)]
+fn spawn_with_name<F>(name: &str, future: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    tokio::task::Builder::new()
+        .name(name)
+        .spawn(future)
+        .expect("Failed to spawn named task")
+}
+
+#[cfg(not(tokio_unstable))]
+fn spawn_with_name<F>(name: &str, future: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    tracing::trace!("Spawning task: {}", name);
+    tokio::spawn(future)
+}
+
+#[cfg(tokio_unstable)]
+fn spawn_blocking_with_name<F, R>(name: &str, f: F) -> JoinHandle<R>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    tokio::task::Builder::new()
+        .name(name)
+        .spawn_blocking(f)
+        .expect("Failed to spawn named blocking task")
+}
+
+#[cfg(not(tokio_unstable))]
+fn spawn_blocking_with_name<F, R>(name: &str, f: F) -> JoinHandle<R>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    tracing::trace!("Spawning blocking task: {}", name);
+    tokio::task::spawn_blocking(f)
+}
+
+#[cfg(test)]
+static SPAWNED_TASKS: std::sync::Mutex<Vec<String>> = std::sync::Mutex::new(Vec::new());
+
+#[cfg(test)]
+fn record_spawn(name: &str) {
+    SPAWNED_TASKS.lock().unwrap().push(name.to_string());
+}
+
+/// Names of every task spawned through this module (test builds only)
+#[cfg(test)]
+pub fn spawned_task_names() -> Vec<String> {
+    SPAWNED_TASKS.lock().unwrap().clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_spawn_named_runs_task() {
+        let result = spawn_named("test-task", async { 21 * 2 }).await.unwrap();
+        assert_eq!(result, 42);
+        assert!(spawned_task_names().contains(&"test-task".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_spawn_blocking_named_runs_closure() {
+        let result = spawn_blocking_named("test-blocking-task", || "done").await.unwrap();
+        assert_eq!(result, "done");
+        assert!(spawned_task_names().contains(&"test-blocking-task".to_string()));
+    }
+
+    #[test]
+    fn test_runtime_thread_name_default() {
+        if std::env::var("THREAD_NAME").is_err() {
+            assert_eq!(runtime_thread_name(), DEFAULT_THREAD_NAME);
+        }
+    }
+}
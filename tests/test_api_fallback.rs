@@ -0,0 +1,43 @@
+// Integration tests for unmatched routes
+// Unknown API paths are JSON 404s; everything else is left to the SPA
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+fn get(path: &str) -> Request<Body> {
+    Request::builder().uri(path).body(Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn test_unknown_api_path_returns_json_404() {
+    let app = axum_sse::create_app().await;
+
+    let response = app.oneshot(get("/api/does-not-exist")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert!(response.headers()[header::CONTENT_TYPE]
+        .to_str()
+        .unwrap()
+        .starts_with("application/json"));
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["error_type"], "not_found");
+    assert!(body["error"].as_str().unwrap().contains("/api/does-not-exist"));
+    assert!(body["timestamp"].is_string());
+}
+
+#[tokio::test]
+async fn test_unknown_page_path_serves_spa_index() {
+    let app = axum_sse::create_app().await;
+
+    let response = app.oneshot(get("/some/spa/route")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers()[header::CONTENT_TYPE]
+        .to_str()
+        .unwrap()
+        .starts_with("text/html"));
+}
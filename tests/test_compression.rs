@@ -0,0 +1,49 @@
+// Integration tests for response compression
+// JSON responses are compressed on request; event streams must never be
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use tower::ServiceExt;
+
+fn request(path: &str, accept_encoding: &str) -> Request<Body> {
+    Request::builder()
+        .uri(path)
+        .header(header::ACCEPT_ENCODING, accept_encoding)
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_json_response_gzip_encoded() {
+    let app = axum_sse::create_app().await;
+
+    let response = app.oneshot(request("/api/server-status", "gzip")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_ENCODING], "gzip");
+    assert!(response.headers()[header::CONTENT_TYPE]
+        .to_str()
+        .unwrap()
+        .starts_with("application/json"));
+}
+
+#[tokio::test]
+async fn test_json_response_uncompressed_without_accept_encoding() {
+    let app = axum_sse::create_app().await;
+
+    let response = app.oneshot(request("/api/server-status", "identity")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+}
+
+#[tokio::test]
+async fn test_sse_stream_not_compressed() {
+    let app = axum_sse::create_app().await;
+
+    // Only the headers are inspected, so the endless body is never read
+    let response = app.oneshot(request("/api/server-status-stream", "gzip, br")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "text/event-stream");
+    assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+}
@@ -0,0 +1,56 @@
+// Integration test for runtime route toggles
+// Disabling a route returns 503 feature_disabled while other routes keep working
+
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use serde_json::{json, Value};
+
+const API_KEY: &str = "feature-toggle-test-key";
+
+#[tokio::test]
+async fn test_disabled_route_returns_503() {
+    // Toggling routes is an admin action and needs the API key
+    std::env::set_var("API_KEY", API_KEY);
+    let app = axum_sse::create_app().await;
+    let mut server = TestServer::new(app).unwrap();
+    server.add_header("x-api-key".parse().unwrap(), API_KEY.parse().unwrap());
+
+    // Switch off the metrics endpoint
+    let response = server
+        .post("/api/features")
+        .json(&json!({ "route": "/api/server-status", "enabled": false }))
+        .await;
+    response.assert_status_ok();
+
+    let response = server.get("/api/server-status").await;
+    response.assert_status(StatusCode::SERVICE_UNAVAILABLE);
+    let body: Value = response.json();
+    assert_eq!(body["error"], "feature_disabled");
+    assert_eq!(body["route"], "/api/server-status");
+
+    // Other routes are unaffected
+    server.get("/api/health").await.assert_status_ok();
+    server.get("/api/server-status/health").await.assert_status_ok();
+
+    // Re-enabling restores the route
+    server
+        .post("/api/features")
+        .json(&json!({ "route": "/api/server-status", "enabled": true }))
+        .await
+        .assert_status_ok();
+    let response = server.get("/api/server-status").await;
+    assert_ne!(response.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[tokio::test]
+async fn test_toggle_requires_api_key() {
+    std::env::set_var("API_KEY", API_KEY);
+    let app = axum_sse::create_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .post("/api/features")
+        .json(&json!({ "route": "/api/server-status", "enabled": false }))
+        .await;
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
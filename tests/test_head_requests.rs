@@ -0,0 +1,42 @@
+// Integration tests for HEAD requests against GET endpoints
+// Health-checkers probe with HEAD and expect 200 with no body
+
+use axum::http::{Method, StatusCode};
+use axum_test::TestServer;
+
+#[tokio::test]
+async fn test_head_server_status_health_returns_empty_200() {
+    let app = axum_sse::create_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let get = server.get("/api/server-status/health").await;
+    let head = server.method(Method::HEAD, "/api/server-status/health").await;
+
+    head.assert_status(StatusCode::OK);
+    assert!(head.as_bytes().is_empty());
+    assert_eq!(head.header("content-type"), get.header("content-type"));
+}
+
+#[tokio::test]
+async fn test_head_status_endpoints_return_empty_200() {
+    let app = axum_sse::create_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    for path in ["/api/server-status", "/api/health", "/api/status"] {
+        let head = server.method(Method::HEAD, path).await;
+        head.assert_status(StatusCode::OK);
+        assert!(head.as_bytes().is_empty(), "HEAD {} returned a body", path);
+    }
+}
+
+#[tokio::test]
+async fn test_head_not_allowed_on_event_streams() {
+    let app = axum_sse::create_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    for path in ["/api/server-status-stream", "/api/time-stream"] {
+        let head = server.method(Method::HEAD, path).await;
+        head.assert_status(StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(head.header("allow"), "GET");
+    }
+}
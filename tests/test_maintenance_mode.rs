@@ -0,0 +1,69 @@
+// Integration test for maintenance mode
+// Pages and API routes answer 503 while the probes keep responding
+
+use axum::http::{header, StatusCode};
+use axum_test::{TestResponse, TestServer};
+use serde_json::{json, Value};
+
+const API_KEY: &str = "maintenance-test-key";
+
+fn content_type(response: &TestResponse) -> String {
+    response.header(header::CONTENT_TYPE).to_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn test_maintenance_mode_toggle() {
+    // Switching maintenance mode is an admin action and needs the API key
+    std::env::set_var("API_KEY", API_KEY);
+    let app = axum_sse::create_app().await;
+    let mut server = TestServer::new(app).unwrap();
+    server.add_header("x-api-key".parse().unwrap(), API_KEY.parse().unwrap());
+
+    // Normal operation
+    server.get("/").await.assert_status_ok();
+    server.get("/api/server-status").await.assert_status_ok();
+
+    let response = server.post("/api/maintenance").json(&json!({ "enabled": true })).await;
+    response.assert_status_ok();
+    assert_eq!(response.json::<Value>()["enabled"], true);
+
+    // Pages get the maintenance page
+    let response = server.get("/").await;
+    response.assert_status(StatusCode::SERVICE_UNAVAILABLE);
+    assert!(content_type(&response).starts_with("text/html"));
+    assert!(response.text().contains("maintenance"));
+    server.get("/some/spa/route").await.assert_status(StatusCode::SERVICE_UNAVAILABLE);
+
+    // API routes get JSON
+    let response = server.get("/api/server-status").await;
+    response.assert_status(StatusCode::SERVICE_UNAVAILABLE);
+    assert!(content_type(&response).starts_with("application/json"));
+    assert_eq!(response.json::<Value>()["error"], "maintenance");
+
+    // Probes and the switch itself stay up
+    server.get("/api/health").await.assert_status_ok();
+    server.get("/api/readiness").await.assert_status_ok();
+    let response = server.get("/api/maintenance").await;
+    response.assert_status_ok();
+    assert_eq!(response.json::<Value>()["enabled"], true);
+
+    // Leaving maintenance restores normal service
+    server
+        .post("/api/maintenance")
+        .json(&json!({ "enabled": false }))
+        .await
+        .assert_status_ok();
+    server.get("/").await.assert_status_ok();
+    server.get("/api/server-status").await.assert_status_ok();
+}
+
+#[tokio::test]
+async fn test_maintenance_switch_requires_api_key() {
+    std::env::set_var("API_KEY", API_KEY);
+    let app = axum_sse::create_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server.post("/api/maintenance").json(&json!({ "enabled": true })).await;
+    response.assert_status(StatusCode::UNAUTHORIZED);
+    server.get("/").await.assert_status_ok();
+}
@@ -0,0 +1,24 @@
+// Integration test for probes under API key authentication
+// Orchestrators probe without credentials, so the probes must answer even when API_KEY is set
+
+use axum::http::StatusCode;
+use axum_test::TestServer;
+
+#[tokio::test]
+async fn test_probes_answer_without_api_key() {
+    std::env::set_var("API_KEY", "probe-test-key");
+    let app = axum_sse::create_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    // Ready or still warming up, but never rejected for the missing key
+    let status = server.get("/api/readiness").await.status_code();
+    assert!(
+        status == StatusCode::OK || status == StatusCode::SERVICE_UNAVAILABLE,
+        "unexpected readiness status {}",
+        status
+    );
+    server.get("/api/health").await.assert_status_ok();
+
+    // Everything else under /api still needs the key
+    server.get("/api/server-status").await.assert_status(StatusCode::UNAUTHORIZED);
+}
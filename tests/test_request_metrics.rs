@@ -0,0 +1,29 @@
+// Integration tests for per-endpoint request metrics
+
+use axum_sse::routes::request_metrics::RequestMetricsResponse;
+use axum_test::TestServer;
+
+#[tokio::test]
+async fn test_health_requests_recorded() {
+    let app = axum_sse::create_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    for _ in 0..3 {
+        server.get("/api/health").await.assert_status_ok();
+    }
+
+    let response = server.get("/api/request-metrics").await;
+    response.assert_status_ok();
+    let metrics: RequestMetricsResponse = response.json();
+
+    let health = metrics
+        .endpoints
+        .iter()
+        .find(|endpoint| endpoint.method == "GET" && endpoint.route == "/api/health")
+        .expect("health endpoint recorded");
+    assert_eq!(health.count, 3);
+    assert!(health.total_duration_ms > 0.0);
+    assert!(health.p50_ms > 0.0);
+    assert!(health.p95_ms >= health.p50_ms);
+    assert!(metrics.total_requests >= 3);
+}
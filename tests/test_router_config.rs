@@ -0,0 +1,63 @@
+// Integration tests for opting out of middleware layers
+// Embedding apps may already apply CORS, security headers, tracing or rate limiting
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use axum_sse::{build_router, MetricsCache, MetricsService, OsInfo, RouterConfig, ServerInfo, SseService, StaticService};
+use std::sync::Arc;
+use tower::ServiceExt;
+
+fn router(router_config: RouterConfig) -> Router {
+    let metrics_service = Arc::new(MetricsService::new());
+    let metrics_cache = Arc::new(MetricsCache::new(Arc::clone(&metrics_service)));
+    let server_info = ServerInfo::new(
+        "test-server".to_string(),
+        "1.0.0".to_string(),
+        chrono::Utc::now(),
+        "development".to_string(),
+        OsInfo::fallback(),
+    )
+    .unwrap();
+
+    build_router(
+        Arc::new(SseService::new()),
+        Arc::new(StaticService::new()),
+        metrics_cache,
+        metrics_service,
+        server_info,
+        router_config,
+    )
+}
+
+fn cross_origin_request() -> Request<Body> {
+    Request::builder()
+        .uri("/api/health")
+        .header(header::ORIGIN, "https://embedding.example")
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_cors_applied_by_default() {
+    let response = router(RouterConfig::default()).oneshot(cross_origin_request()).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN));
+}
+
+#[tokio::test]
+async fn test_cors_disabled_adds_no_cors_headers() {
+    let config = RouterConfig {
+        enable_cors: false,
+        ..Default::default()
+    };
+    let response = router(config).oneshot(cross_origin_request()).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN).is_none());
+    // Layers left enabled still apply
+    assert!(response.headers().contains_key(header::CONTENT_SECURITY_POLICY));
+}
@@ -0,0 +1,67 @@
+// Integration tests for the WebSocket metrics transport
+// The server runs on a real socket so the connection can be upgraded
+
+use futures::{SinkExt, StreamExt};
+use std::time::{Duration, Instant};
+use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+
+type Socket = WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>;
+
+/// Serve the full application on an ephemeral port and return its address
+async fn spawn_app() -> std::net::SocketAddr {
+    // Allow one-second updates regardless of the collection interval
+    std::env::set_var("SSE_ALLOW_SUB_COLLECTION_INTERVAL", "true");
+    let app = axum_sse::create_app().await;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    addr
+}
+
+/// Next metrics event, skipping control frames
+async fn next_event(socket: &mut Socket) -> serde_json::Value {
+    loop {
+        let message = tokio::time::timeout(Duration::from_secs(10), socket.next())
+            .await
+            .expect("Expected an event")
+            .unwrap()
+            .unwrap();
+        if let Message::Text(text) = message {
+            return serde_json::from_str(&text).unwrap();
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_websocket_events_and_set_interval() {
+    let addr = spawn_app().await;
+    let (mut socket, _) = connect_async(format!("ws://{}/api/server-status-ws?interval=30&client_id=ws-test", addr))
+        .await
+        .unwrap();
+
+    // The first event arrives right away
+    let event = next_event(&mut socket).await;
+    assert_eq!(event["event_type"], "status-update");
+    assert_eq!(event["sequence"], 0);
+    assert_eq!(event["connection_info"]["client_id"], "ws-test");
+    assert_eq!(event["connection_info"]["update_interval_seconds"], 30);
+    assert!(event["data"]["server_metrics"]["memory_usage"]["total_bytes"].as_u64().unwrap() > 0);
+
+    // Switching to one-second updates takes effect without waiting out the old interval
+    socket.send(Message::Text(r#"{"set_interval": 1}"#.into())).await.unwrap();
+    let started = Instant::now();
+    let event = next_event(&mut socket).await;
+    assert!(started.elapsed() < Duration::from_secs(5), "took {:?}", started.elapsed());
+    assert_eq!(event["sequence"], 1);
+    assert_eq!(event["connection_info"]["update_interval_seconds"], 1);
+
+    let started = Instant::now();
+    let event = next_event(&mut socket).await;
+    assert!(started.elapsed() < Duration::from_secs(3), "took {:?}", started.elapsed());
+    assert_eq!(event["sequence"], 2);
+
+    socket.close(None).await.unwrap();
+}